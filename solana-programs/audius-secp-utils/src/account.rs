@@ -0,0 +1,236 @@
+//! Program account helpers
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program, sysvar,
+};
+
+/// First byte of a closed account's data. The programs keep their account
+/// version in the first byte, so a closed account reads as neither
+/// uninitialized nor of any known version.
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: u8 = u8::MAX;
+
+/// Whether `data` belongs to an account closed with `close_account`
+pub fn is_closed(data: &[u8]) -> bool {
+    data.first() == Some(&CLOSED_ACCOUNT_DISCRIMINATOR)
+}
+
+/// Closes a program owned `account`, moving all of its lamports to `refund`.
+///
+/// The runtime only garbage-collects the account at the end of the
+/// transaction, so its data is zeroed and marked closed to keep later
+/// instructions of the same transaction from using it.
+pub fn close_account(account: &AccountInfo, refund: &AccountInfo) -> ProgramResult {
+    {
+        let mut data = account.try_borrow_mut_data()?;
+        data.fill(0);
+        if let Some(discriminator) = data.first_mut() {
+            *discriminator = CLOSED_ACCOUNT_DISCRIMINATOR;
+        }
+    }
+
+    let mut from = account.try_borrow_mut_lamports()?;
+    let mut to = refund.try_borrow_mut_lamports()?;
+    **to = to
+        .checked_add(**from)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **from = 0;
+    Ok(())
+}
+
+/// Creates the program derived `account` of `space` bytes owned by `owner`,
+/// paid for by `funder`.
+///
+/// Anyone can send lamports to a derived address before it is created,
+/// which makes the system program refuse to create it. Such an account is
+/// topped up to rent exemption from `funder`, then allocated and assigned.
+pub fn create_derived_account<'a>(
+    funder: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    signers_seeds: &[&[&[u8]]],
+    rent: &Rent,
+) -> ProgramResult {
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                funder.key,
+                account.key,
+                rent.minimum_balance(space),
+                space as u64,
+                owner,
+            ),
+            &[funder.clone(), account.clone()],
+            signers_seeds,
+        );
+    }
+
+    let required_lamports = rent
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(funder.key, account.key, required_lamports),
+            &[funder.clone(), account.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        std::slice::from_ref(account),
+        signers_seeds,
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, owner),
+        std::slice::from_ref(account),
+        signers_seeds,
+    )
+}
+
+/// Resizes a program owned `account` to `new_len` bytes, zeroing any new
+/// space, and tops its lamports up from `funder` to stay rent exempt.
+///
+/// The runtime limits how much an account can grow in one instruction,
+/// see `MAX_PERMITTED_DATA_INCREASE`.
+pub fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    funder: &AccountInfo<'a>,
+    new_len: usize,
+    rent: &Rent,
+) -> ProgramResult {
+    account.realloc(new_len, true)?;
+
+    let required_lamports = rent
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(funder.key, account.key, required_lamports),
+            &[funder.clone(), account.clone()],
+        )?;
+    }
+    Ok(())
+}
+
+fn assert_sysvar(account_info: &AccountInfo, id: &Pubkey) -> ProgramResult {
+    if account_info.key != id {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Assert `account_info` is the instructions sysvar
+pub fn assert_instructions_sysvar(account_info: &AccountInfo) -> ProgramResult {
+    assert_sysvar(account_info, &sysvar::instructions::id())
+}
+
+/// Assert `account_info` is the clock sysvar
+pub fn assert_clock_sysvar(account_info: &AccountInfo) -> ProgramResult {
+    assert_sysvar(account_info, &sysvar::clock::id())
+}
+
+/// Assert `account_info` is the rent sysvar
+pub fn assert_rent_sysvar(account_info: &AccountInfo) -> ProgramResult {
+    assert_sysvar(account_info, &sysvar::rent::id())
+}
+
+/// Assert `account_info` is the program `program_id`
+pub fn assert_program(account_info: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+    if account_info.key != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Assert `account_info` is the system program
+pub fn assert_system_program(account_info: &AccountInfo) -> ProgramResult {
+    assert_program(account_info, &system_program::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn close_account_marks_data_and_moves_lamports() {
+        let owner = Pubkey::new_unique();
+        let account_key = Pubkey::new_unique();
+        let refund_key = Pubkey::new_unique();
+        let mut account_lamports = 100;
+        let mut refund_lamports = 5;
+        let mut account_data = vec![1; 8];
+        let mut refund_data = vec![];
+        let account = AccountInfo::new(
+            &account_key,
+            false,
+            true,
+            &mut account_lamports,
+            &mut account_data,
+            &owner,
+            false,
+            0,
+        );
+        let refund = AccountInfo::new(
+            &refund_key,
+            false,
+            true,
+            &mut refund_lamports,
+            &mut refund_data,
+            &owner,
+            false,
+            0,
+        );
+
+        close_account(&account, &refund).unwrap();
+
+        assert_eq!(account.lamports(), 0);
+        assert_eq!(refund.lamports(), 105);
+        assert!(is_closed(&account.data.borrow()));
+        assert_eq!(&account.data.borrow()[1..], &[0; 7]);
+        assert!(!is_closed(&[]));
+        assert!(!is_closed(&[0; 8]));
+    }
+
+    #[test]
+    fn close_account_overflow() {
+        let owner = Pubkey::new_unique();
+        let account_key = Pubkey::new_unique();
+        let refund_key = Pubkey::new_unique();
+        let mut account_lamports = 100;
+        let mut refund_lamports = u64::MAX;
+        let mut account_data = vec![1; 8];
+        let mut refund_data = vec![];
+        let account = AccountInfo::new(
+            &account_key,
+            false,
+            true,
+            &mut account_lamports,
+            &mut account_data,
+            &owner,
+            false,
+            0,
+        );
+        let refund = AccountInfo::new(
+            &refund_key,
+            false,
+            true,
+            &mut refund_lamports,
+            &mut refund_data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            close_account(&account, &refund),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(account.lamports(), 100);
+    }
+}
@@ -0,0 +1,146 @@
+//! Client side builders of secp256k1 program instructions
+
+use crate::{
+    eth::{eth_address_from_pubkey, keccak256, EthereumAddress, PUBKEY_SERIALIZED_SIZE},
+    secp::SecpSignatureOffsets,
+};
+use solana_program::{instruction::Instruction, secp256k1_program};
+
+/// Ethereum address of `priv_key`
+pub fn eth_address_from_secret_key(priv_key: &libsecp256k1::SecretKey) -> EthereumAddress {
+    let mut pubkey = [0u8; PUBKEY_SERIALIZED_SIZE];
+    pubkey.copy_from_slice(&libsecp256k1::PublicKey::from_secret_key(priv_key).serialize()[1..]);
+    eth_address_from_pubkey(&pubkey)
+}
+
+/// Secp instruction checking a signature of every `(key, message)` pair,
+/// which must be at `instruction_index` of the transaction
+pub fn new_secp256k1_instruction_multi(
+    signatures: &[(&libsecp256k1::SecretKey, &[u8])],
+    instruction_index: u8,
+) -> Instruction {
+    let offsets_start = 1;
+    let mut instruction_data =
+        vec![0u8; offsets_start + signatures.len() * SecpSignatureOffsets::LEN];
+    instruction_data[0] = signatures.len() as u8;
+
+    for (i, (priv_key, message_arr)) in signatures.iter().enumerate() {
+        let eth_address = eth_address_from_secret_key(priv_key);
+        let message = libsecp256k1::Message::parse(&keccak256(message_arr));
+        let (signature, recovery_id) = libsecp256k1::sign(&message, priv_key);
+
+        let eth_address_offset = instruction_data.len();
+        instruction_data.extend_from_slice(&eth_address);
+        let signature_offset = instruction_data.len();
+        instruction_data.extend_from_slice(&signature.serialize());
+        instruction_data.push(recovery_id.serialize());
+        let message_data_offset = instruction_data.len();
+        instruction_data.extend_from_slice(message_arr);
+
+        let offsets = SecpSignatureOffsets {
+            signature_offset: signature_offset as u16,
+            signature_instruction_index: instruction_index,
+            eth_address_offset: eth_address_offset as u16,
+            eth_address_instruction_index: instruction_index,
+            message_data_offset: message_data_offset as u16,
+            message_data_size: message_arr.len() as u16,
+            message_instruction_index: instruction_index,
+        };
+        let start = offsets_start + i * SecpSignatureOffsets::LEN;
+        instruction_data[start..start + SecpSignatureOffsets::LEN].copy_from_slice(&offsets.pack());
+    }
+
+    Instruction {
+        program_id: secp256k1_program::id(),
+        accounts: vec![],
+        data: instruction_data,
+    }
+}
+
+/// Secp instruction checking the signature of `message_arr` by `priv_key`.
+/// Unlike the sdk builder, its offsets point at `instruction_index`
+/// so it doesn't have to be the first instruction of the transaction.
+pub fn new_secp256k1_instruction_2_0(
+    priv_key: &libsecp256k1::SecretKey,
+    message_arr: &[u8],
+    instruction_index: u8,
+) -> Instruction {
+    new_secp256k1_instruction_multi(&[(priv_key, message_arr)], instruction_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp::{
+        parse_signatures, ETH_ADDRESS_OFFSET, MESSAGE_DATA_OFFSET, SIGNATURE_OFFSET,
+    };
+
+    fn secret_key(byte: u8) -> libsecp256k1::SecretKey {
+        libsecp256k1::SecretKey::parse(&[byte; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_single_signature_instruction() {
+        let key = secret_key(1);
+        let instruction = new_secp256k1_instruction_2_0(&key, b"message", 3);
+        let data = &instruction.data;
+
+        assert_eq!(instruction.program_id, secp256k1_program::id());
+        assert_eq!(data[0], 1);
+        assert_eq!(data.len(), MESSAGE_DATA_OFFSET + b"message".len());
+        assert_eq!(
+            SecpSignatureOffsets::unpack(&data[1..ETH_ADDRESS_OFFSET]).unwrap(),
+            SecpSignatureOffsets {
+                signature_offset: SIGNATURE_OFFSET as u16,
+                signature_instruction_index: 3,
+                eth_address_offset: ETH_ADDRESS_OFFSET as u16,
+                eth_address_instruction_index: 3,
+                message_data_offset: MESSAGE_DATA_OFFSET as u16,
+                message_data_size: b"message".len() as u16,
+                message_instruction_index: 3,
+            }
+        );
+        assert_eq!(
+            data[ETH_ADDRESS_OFFSET..SIGNATURE_OFFSET],
+            eth_address_from_secret_key(&key)
+        );
+    }
+
+    #[test]
+    fn test_signature_recovers_to_signer() {
+        let key = secret_key(2);
+        let instruction = new_secp256k1_instruction_2_0(&key, b"message", 0);
+        let data = &instruction.data;
+
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(&data[SIGNATURE_OFFSET..SIGNATURE_OFFSET + 64]);
+        let recovered = libsecp256k1::recover(
+            &libsecp256k1::Message::parse(&keccak256(b"message")),
+            &libsecp256k1::Signature::parse_standard(&signature).unwrap(),
+            &libsecp256k1::RecoveryId::parse(data[SIGNATURE_OFFSET + 64]).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            recovered.serialize(),
+            libsecp256k1::PublicKey::from_secret_key(&key).serialize()
+        );
+    }
+
+    #[test]
+    fn test_multiple_signatures_instruction() {
+        let keys = [secret_key(1), secret_key(2), secret_key(3)];
+        let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+        let pairs: Vec<(&libsecp256k1::SecretKey, &[u8])> =
+            keys.iter().zip(messages.iter().cloned()).collect();
+        let instruction = new_secp256k1_instruction_multi(&pairs, 1);
+
+        let signatures = parse_signatures(1, &instruction.data, |_| unreachable!()).unwrap();
+
+        assert_eq!(signatures.len(), 3);
+        for ((signature, key), message) in signatures.iter().zip(&keys).zip(&messages) {
+            assert_eq!(signature.eth_address, eth_address_from_secret_key(key));
+            assert_eq!(signature.message, message.to_vec());
+        }
+    }
+}
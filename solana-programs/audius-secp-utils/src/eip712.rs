@@ -0,0 +1,181 @@
+//! EIP-712 typed structured data encoding
+//!
+//! Wallets sign `keccak256(0x19 0x01 || domainSeparator || hashStruct(message))`.
+//! The secp256k1 program hashes the message it is given, so a secp instruction
+//! for typed data carries the 66 byte preimage built by `eip712_message`.
+
+use crate::eth::keccak256;
+
+/// Prefix of the encoded typed data, EIP-191 version 0x01
+pub const EIP712_PREFIX: [u8; 2] = [0x19, 0x01];
+
+/// Size of the encoded typed data, the prefix followed by two hashes
+pub const EIP712_MESSAGE_SIZE: usize = 66;
+
+/// Type of the domain used by Solana programs. A program id doesn't fit
+/// an `address`, so the verifying contract is a `bytes32`
+pub const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,bytes32 verifyingContract)";
+
+/// Domain of the typed data, binds signatures to a program deployment
+#[derive(Clone, Debug, PartialEq)]
+pub struct Eip712Domain<'a> {
+    /// Name of the signing domain
+    pub name: &'a str,
+    /// Current major version of the signing domain
+    pub version: &'a str,
+    /// Id of the cluster the program is deployed to, signed as `chainId`
+    pub chain_id: u64,
+    /// Program verifying the signature
+    pub verifying_contract: [u8; 32],
+}
+
+impl<'a> Eip712Domain<'a> {
+    /// `domainSeparator` of the domain
+    pub fn separator(&self) -> [u8; 32] {
+        eip712_hash_struct(
+            EIP712_DOMAIN_TYPE,
+            &[
+                eip712_encode_string(self.name),
+                eip712_encode_string(self.version),
+                eip712_encode_uint(self.chain_id),
+                self.verifying_contract,
+            ],
+        )
+    }
+}
+
+/// Encodes a `string` or `bytes` member
+pub fn eip712_encode_string(value: &str) -> [u8; 32] {
+    keccak256(value.as_bytes())
+}
+
+/// Encodes an unsigned integer member, big endian padded to 32 bytes
+pub fn eip712_encode_uint(value: u64) -> [u8; 32] {
+    let mut encoded = [0u8; 32];
+    encoded[24..].copy_from_slice(&value.to_be_bytes());
+    encoded
+}
+
+/// `hashStruct` of a struct of `struct_type` with the encoded `members`,
+/// `struct_type` must already include referenced struct types
+pub fn eip712_hash_struct(struct_type: &str, members: &[[u8; 32]]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(32 * (members.len() + 1));
+    encoded.extend_from_slice(&keccak256(struct_type.as_bytes()));
+    for member in members {
+        encoded.extend_from_slice(member);
+    }
+    keccak256(&encoded)
+}
+
+/// Typed data message passed to the secp256k1 program
+pub fn eip712_message(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> Vec<u8> {
+    [&EIP712_PREFIX[..], domain_separator, struct_hash].concat()
+}
+
+/// Digest signed by `eth_signTypedData` for the typed data
+pub fn eip712_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    keccak256(&eip712_message(domain_separator, struct_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn encode_address(hex: &str) -> [u8; 32] {
+        let mut encoded = [0u8; 32];
+        encoded[12..].copy_from_slice(&from_hex(hex));
+        encoded
+    }
+
+    fn person(name: &str, wallet: &str) -> [u8; 32] {
+        eip712_hash_struct(
+            "Person(string name,address wallet)",
+            &[eip712_encode_string(name), encode_address(wallet)],
+        )
+    }
+
+    // Example from the EIP-712 specification
+    #[test]
+    fn test_mail_example() {
+        let domain_separator = eip712_hash_struct(
+            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+            &[
+                eip712_encode_string("Ether Mail"),
+                eip712_encode_string("1"),
+                eip712_encode_uint(1),
+                encode_address("cccccccccccccccccccccccccccccccccccccccc"),
+            ],
+        );
+        assert_eq!(
+            domain_separator.to_vec(),
+            from_hex("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+
+        let mail = eip712_hash_struct(
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+            &[
+                person("Cow", "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"),
+                person("Bob", "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+                eip712_encode_string("Hello, Bob!"),
+            ],
+        );
+        assert_eq!(
+            mail.to_vec(),
+            from_hex("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+        );
+
+        let message = eip712_message(&domain_separator, &mail);
+        assert_eq!(message.len(), EIP712_MESSAGE_SIZE);
+        assert_eq!(
+            eip712_hash(&domain_separator, &mail).to_vec(),
+            from_hex("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+    }
+
+    #[test]
+    fn test_domain_separator() {
+        let domain = Eip712Domain {
+            name: "Test",
+            version: "1",
+            chain_id: 101,
+            verifying_contract: [3u8; 32],
+        };
+        assert_eq!(
+            domain.separator(),
+            eip712_hash_struct(
+                EIP712_DOMAIN_TYPE,
+                &[
+                    keccak256(b"Test"),
+                    keccak256(b"1"),
+                    eip712_encode_uint(101),
+                    [3u8; 32],
+                ],
+            )
+        );
+        assert_ne!(
+            domain.separator(),
+            Eip712Domain {
+                chain_id: 103,
+                ..domain.clone()
+            }
+            .separator()
+        );
+    }
+
+    #[test]
+    fn test_encode_uint() {
+        let mut expected = [0u8; 32];
+        expected[30] = 1;
+        expected[31] = 2;
+        assert_eq!(eip712_encode_uint(0x0102), expected);
+        assert_eq!(eip712_encode_uint(0), [0u8; 32]);
+    }
+}
@@ -0,0 +1,26 @@
+//! Error types
+
+use thiserror::Error;
+
+/// Errors that may be returned while checking secp256k1 instructions.
+///
+/// Programs convert these into their own error type so that
+/// their error codes stay unchanged.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum SecpError {
+    /// Instruction could not be loaded from the instructions sysvar
+    #[error("Instruction load error")]
+    InstructionLoadError,
+    /// Expected secp256k1 instruction is missing
+    #[error("Secp256 instruction missing")]
+    Secp256InstructionMissing,
+    /// Secp256k1 instruction data or offsets are malformed
+    #[error("Malformed secp256k1 instruction")]
+    MalformedInstruction,
+    /// No signature by the expected signer
+    #[error("Wrong signer")]
+    WrongSigner,
+    /// Signature is over an unexpected message
+    #[error("Wrong message")]
+    WrongMessage,
+}
@@ -0,0 +1,133 @@
+//! Ethereum hashing and addressing helpers
+
+use solana_program::keccak;
+
+/// Ethereum address, the last 20 bytes of the keccak hash of a public key
+pub type EthereumAddress = [u8; 20];
+
+/// Size of an uncompressed secp256k1 public key without its tag byte
+pub const PUBKEY_SERIALIZED_SIZE: usize = 64;
+
+/// Prefix `personal_sign` puts in front of the decimal length of the message
+pub const EIP191_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+/// Keccak-256 hash of `data`
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    keccak::hash(data).to_bytes()
+}
+
+/// Ethereum address of an uncompressed public key
+pub fn eth_address_from_pubkey(pubkey: &[u8; PUBKEY_SERIALIZED_SIZE]) -> EthereumAddress {
+    let mut address = EthereumAddress::default();
+    address.copy_from_slice(&keccak256(pubkey)[12..]);
+    address
+}
+
+/// Message actually signed by `personal_sign` (EIP-191 version 0x45) for `message`.
+/// The secp256k1 program hashes it, so this is what a secp instruction carries.
+pub fn eip191_message(message: &[u8]) -> Vec<u8> {
+    [
+        EIP191_PREFIX.as_bytes(),
+        message.len().to_string().as_bytes(),
+        message,
+    ]
+    .concat()
+}
+
+/// Message enclosed by `personal_sign` in `signed_message`, if it has a well formed
+/// EIP-191 prefix whose length matches the rest of `signed_message`
+pub fn strip_eip191_prefix(signed_message: &[u8]) -> Option<&[u8]> {
+    let rest = signed_message.strip_prefix(EIP191_PREFIX.as_bytes())?;
+    // The message may itself start with digits, so try every split of the length
+    let digits = rest.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    (1..=digits)
+        .map(|length_size| rest.split_at(length_size))
+        .find(|(length, message)| *length == message.len().to_string().as_bytes())
+        .map(|(_, message)| message)
+}
+
+/// Digest signed by `personal_sign` for `message`
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    keccak256(&eip191_message(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            keccak256(&[]).to_vec(),
+            from_hex("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+    }
+
+    #[test]
+    fn test_eth_address_from_pubkey() {
+        let mut secret = [0u8; 32];
+        secret[31] = 1;
+        let secret_key = libsecp256k1::SecretKey::parse(&secret).unwrap();
+        let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key);
+        let mut pubkey = [0u8; PUBKEY_SERIALIZED_SIZE];
+        pubkey.copy_from_slice(&public_key.serialize()[1..]);
+
+        assert_eq!(
+            eth_address_from_pubkey(&pubkey).to_vec(),
+            from_hex("7e5f4552091a69125d5dfcb7b8c2659029395bdf")
+        );
+    }
+
+    #[test]
+    fn test_strip_eip191_prefix() {
+        assert_eq!(
+            strip_eip191_prefix(&eip191_message(b"hello")),
+            Some(&b"hello"[..])
+        );
+        let long = [7u8; 300];
+        assert_eq!(strip_eip191_prefix(&eip191_message(&long)), Some(&long[..]));
+        assert_eq!(strip_eip191_prefix(&eip191_message(&[])), Some(&[][..]));
+
+        assert_eq!(strip_eip191_prefix(b"hello"), None);
+        assert_eq!(
+            strip_eip191_prefix(b"\x19Ethereum Signed Message:\n4hello"),
+            None
+        );
+        assert_eq!(
+            strip_eip191_prefix(b"\x19Ethereum Signed Message:\n05hello"),
+            None
+        );
+        assert_eq!(
+            strip_eip191_prefix(b"\x19Ethereum Signed Message:\nhello"),
+            None
+        );
+        // Messages starting with digits keep them
+        assert_eq!(
+            strip_eip191_prefix(b"\x19Ethereum Signed Message:\n212"),
+            Some(&b"12"[..])
+        );
+    }
+
+    #[test]
+    fn test_eip191_message() {
+        assert_eq!(
+            eip191_message(b"hello"),
+            b"\x19Ethereum Signed Message:\n5hello".to_vec()
+        );
+        assert_eq!(
+            eip191_message(&[0u8; 32])[..EIP191_PREFIX.len() + 2],
+            b"\x19Ethereum Signed Message:\n32"[..]
+        );
+        assert_eq!(
+            eip191_hash(b"hello world").to_vec(),
+            from_hex("d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68")
+        );
+    }
+}
@@ -0,0 +1,34 @@
+#![deny(missing_docs)]
+
+//! Ethereum signature helpers shared by the Audius Solana programs.
+//!
+//! On-chain, the programs rely on the secp256k1 native program to recover
+//! eth signatures and read the results back through the instructions sysvar.
+//! Clients build those secp256k1 instructions with the `client` feature.
+//! Signed messages may be raw, `personal_sign` (EIP-191) or typed data (EIP-712).
+//! The crate also holds the account creation, closing and resizing routines
+//! and the sysvar and program account checks the programs share.
+
+pub mod account;
+pub mod eip712;
+pub mod error;
+pub mod eth;
+pub mod secp;
+
+#[cfg(any(test, feature = "client"))]
+pub mod client;
+
+pub use account::{
+    assert_clock_sysvar, assert_instructions_sysvar, assert_program, assert_rent_sysvar,
+    assert_system_program, close_account, create_derived_account, is_closed, resize_account,
+    CLOSED_ACCOUNT_DISCRIMINATOR,
+};
+pub use eip712::{eip712_message, Eip712Domain};
+pub use error::SecpError;
+pub use eth::{
+    eip191_message, eth_address_from_pubkey, keccak256, strip_eip191_prefix, EthereumAddress,
+};
+pub use secp::{
+    load_previous_secp_signatures, load_secp_signatures_before, parse_secp_signatures,
+    verify_signer_and_message, SecpSignature, SecpSignatureOffsets,
+};
@@ -0,0 +1,565 @@
+//! Parsing of secp256k1 program instructions
+
+use crate::{
+    error::SecpError,
+    eth::{strip_eip191_prefix, EthereumAddress},
+};
+use solana_program::{account_info::AccountInfo, secp256k1_program, sysvar};
+use std::mem::size_of;
+
+/// Size of a serialized secp256k1 signature, without its recovery id
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+/// Start of the data of a secp instruction checking a single signature,
+/// right after the signature count and its offsets
+pub const DATA_START: usize = 1 + SecpSignatureOffsets::LEN;
+
+/// Offset of the eth address in a secp instruction checking a single inline signature
+pub const ETH_ADDRESS_OFFSET: usize = DATA_START;
+
+/// Offset of the signature in a secp instruction checking a single inline signature
+pub const SIGNATURE_OFFSET: usize = ETH_ADDRESS_OFFSET + size_of::<EthereumAddress>();
+
+/// Offset of the message in a secp instruction checking a single inline signature
+pub const MESSAGE_DATA_OFFSET: usize = SIGNATURE_OFFSET + SIGNATURE_SERIALIZED_SIZE + 1;
+
+/// Offsets of the parts of a signature checked by the secp256k1 program.
+/// Each part is read from the instruction at the matching index in the transaction
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SecpSignatureOffsets {
+    /// Offset of the 64 byte signature and its recovery id
+    pub signature_offset: u16,
+    /// Index of the instruction holding the signature
+    pub signature_instruction_index: u8,
+    /// Offset of the 20 byte eth address
+    pub eth_address_offset: u16,
+    /// Index of the instruction holding the eth address
+    pub eth_address_instruction_index: u8,
+    /// Offset of the signed message
+    pub message_data_offset: u16,
+    /// Size of the signed message
+    pub message_data_size: u16,
+    /// Index of the instruction holding the message
+    pub message_instruction_index: u8,
+}
+
+impl SecpSignatureOffsets {
+    /// Size of serialized offsets
+    pub const LEN: usize = 11;
+
+    /// Unpacks offsets from the little endian layout of the secp256k1 program
+    pub fn unpack(input: &[u8]) -> Result<Self, SecpError> {
+        if input.len() != Self::LEN {
+            return Err(SecpError::MalformedInstruction);
+        }
+        let read_u16 = |offset: usize| u16::from_le_bytes([input[offset], input[offset + 1]]);
+        Ok(Self {
+            signature_offset: read_u16(0),
+            signature_instruction_index: input[2],
+            eth_address_offset: read_u16(3),
+            eth_address_instruction_index: input[5],
+            message_data_offset: read_u16(6),
+            message_data_size: read_u16(8),
+            message_instruction_index: input[10],
+        })
+    }
+
+    /// Packs offsets into the little endian layout of the secp256k1 program
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut output = [0u8; Self::LEN];
+        output[0..2].copy_from_slice(&self.signature_offset.to_le_bytes());
+        output[2] = self.signature_instruction_index;
+        output[3..5].copy_from_slice(&self.eth_address_offset.to_le_bytes());
+        output[5] = self.eth_address_instruction_index;
+        output[6..8].copy_from_slice(&self.message_data_offset.to_le_bytes());
+        output[8..10].copy_from_slice(&self.message_data_size.to_le_bytes());
+        output[10] = self.message_instruction_index;
+        output
+    }
+}
+
+/// Eth address and message of a signature checked by a secp256k1 instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecpSignature {
+    /// Address which signed `message`
+    pub eth_address: EthereumAddress,
+    /// Signed message
+    pub message: Vec<u8>,
+}
+
+impl SecpSignature {
+    /// Message approved by the signer. With `accept_eip191`, a message signed with
+    /// `personal_sign` is returned without its EIP-191 prefix.
+    pub fn signed_message(&self, accept_eip191: bool) -> &[u8] {
+        if accept_eip191 {
+            if let Some(message) = strip_eip191_prefix(&self.message) {
+                return message;
+            }
+        }
+        &self.message
+    }
+
+    /// Checks that this is a signature by `expected_signer` over `expected_message`,
+    /// or over its EIP-191 prefixed form when `accept_eip191` is set
+    pub fn verify(
+        &self,
+        expected_signer: &EthereumAddress,
+        expected_message: &[u8],
+        accept_eip191: bool,
+    ) -> Result<(), SecpError> {
+        if self.eth_address != *expected_signer {
+            return Err(SecpError::WrongSigner);
+        }
+        if self.signed_message(accept_eip191) != expected_message {
+            return Err(SecpError::WrongMessage);
+        }
+        Ok(())
+    }
+}
+
+/// Parses every signature checked by the secp instruction at `secp_instruction_index`,
+/// reading the data of other instructions of the transaction with `load_instruction_data`
+/// when the offsets point outside of the secp instruction itself.
+pub fn parse_signatures<F>(
+    secp_instruction_index: u16,
+    secp_instruction_data: &[u8],
+    mut load_instruction_data: F,
+) -> Result<Vec<SecpSignature>, SecpError>
+where
+    F: FnMut(u8) -> Result<Vec<u8>, SecpError>,
+{
+    let mut load = |instruction_index: u8, offset: u16, size: usize| {
+        let loaded;
+        let data = if u16::from(instruction_index) == secp_instruction_index {
+            secp_instruction_data
+        } else {
+            loaded = load_instruction_data(instruction_index)?;
+            &loaded
+        };
+
+        let offset = offset as usize;
+        data.get(offset..offset + size)
+            .map(|part| part.to_vec())
+            .ok_or(SecpError::MalformedInstruction)
+    };
+
+    let count = *secp_instruction_data
+        .first()
+        .ok_or(SecpError::MalformedInstruction)? as usize;
+
+    let mut signatures = Vec::with_capacity(count);
+    for i in 0..count {
+        let start = 1 + i * SecpSignatureOffsets::LEN;
+        let offsets = secp_instruction_data
+            .get(start..start + SecpSignatureOffsets::LEN)
+            .ok_or(SecpError::MalformedInstruction)?;
+        let offsets = SecpSignatureOffsets::unpack(offsets)?;
+
+        let eth_address = load(
+            offsets.eth_address_instruction_index,
+            offsets.eth_address_offset,
+            size_of::<EthereumAddress>(),
+        )?;
+        let message = load(
+            offsets.message_instruction_index,
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        let mut signature = SecpSignature {
+            eth_address: EthereumAddress::default(),
+            message,
+        };
+        signature.eth_address.copy_from_slice(&eth_address);
+        signatures.push(signature);
+    }
+
+    Ok(signatures)
+}
+
+/// Parses every signature checked by the secp instruction at `secp_instruction_index`,
+/// following its offsets into other instructions of the transaction if needed.
+pub fn parse_secp_signatures(
+    instruction_info: &AccountInfo,
+    secp_instruction_index: u16,
+    secp_instruction_data: &[u8],
+) -> Result<Vec<SecpSignature>, SecpError> {
+    parse_signatures(
+        secp_instruction_index,
+        secp_instruction_data,
+        |instruction_index| {
+            sysvar::instructions::load_instruction_at_checked(
+                instruction_index as usize,
+                instruction_info,
+            )
+            .map(|instruction| instruction.data)
+            .map_err(|_| SecpError::InstructionLoadError)
+        },
+    )
+}
+
+/// Retrieves the signatures checked by every secp instruction before `index`,
+/// skipping instructions of other programs.
+pub fn load_secp_signatures_before(
+    instruction_info: &AccountInfo,
+    index: u16,
+) -> Result<Vec<SecpSignature>, SecpError> {
+    let mut signatures = Vec::new();
+
+    for ind in 0..index {
+        let instruction =
+            sysvar::instructions::load_instruction_at_checked(ind as usize, instruction_info)
+                .map_err(|_| SecpError::InstructionLoadError)?;
+
+        if instruction.program_id == secp256k1_program::id() {
+            signatures.extend(parse_secp_signatures(
+                instruction_info,
+                ind,
+                &instruction.data,
+            )?);
+        }
+    }
+
+    Ok(signatures)
+}
+
+/// Retrieves the signatures checked by the secp instruction right before
+/// the current one. `instruction_info` must be the instructions sysvar.
+pub fn load_previous_secp_signatures(
+    instruction_info: &AccountInfo,
+) -> Result<Vec<SecpSignature>, SecpError> {
+    let index = sysvar::instructions::load_current_index_checked(instruction_info)
+        .map_err(|_| SecpError::InstructionLoadError)?;
+
+    // Instruction can't be first in transaction
+    // because must follow after `new_secp256k1_instruction`
+    if index == 0 {
+        return Err(SecpError::Secp256InstructionMissing);
+    }
+
+    let instruction =
+        sysvar::instructions::load_instruction_at_checked((index - 1) as usize, instruction_info)
+            .map_err(|_| SecpError::InstructionLoadError)?;
+
+    if instruction.program_id != secp256k1_program::id() {
+        return Err(SecpError::Secp256InstructionMissing);
+    }
+
+    parse_secp_signatures(instruction_info, index - 1, &instruction.data)
+}
+
+/// Checks that one of `signatures` is by `expected_signer` over `expected_message`,
+/// or over its EIP-191 prefixed form when `accept_eip191` is set
+pub fn verify_signer_and_message(
+    signatures: &[SecpSignature],
+    expected_signer: &EthereumAddress,
+    expected_message: &[u8],
+    accept_eip191: bool,
+) -> Result<(), SecpError> {
+    let mut result = Err(SecpError::WrongSigner);
+    for signature in signatures {
+        match signature.verify(expected_signer, expected_message, accept_eip191) {
+            Ok(()) => return Ok(()),
+            Err(SecpError::WrongMessage) => result = Err(SecpError::WrongMessage),
+            Err(_) => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::eip191_message;
+    use solana_program::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+
+    fn inline_secp_instruction(
+        eth_address: &EthereumAddress,
+        message: &[u8],
+        instruction_index: u8,
+    ) -> Instruction {
+        let offsets = SecpSignatureOffsets {
+            signature_offset: SIGNATURE_OFFSET as u16,
+            signature_instruction_index: instruction_index,
+            eth_address_offset: ETH_ADDRESS_OFFSET as u16,
+            eth_address_instruction_index: instruction_index,
+            message_data_offset: MESSAGE_DATA_OFFSET as u16,
+            message_data_size: message.len() as u16,
+            message_instruction_index: instruction_index,
+        };
+        let data = [
+            &[1u8][..],
+            &offsets.pack(),
+            eth_address,
+            &[0u8; SIGNATURE_SERIALIZED_SIZE + 1],
+            message,
+        ]
+        .concat();
+        Instruction::new_with_bytes(secp256k1_program::id(), &data, vec![])
+    }
+
+    /// Serializes `instructions` the way the runtime lays out the instructions
+    /// sysvar, followed by room for the current instruction index
+    fn serialize_instructions(instructions: &[Instruction]) -> Vec<u8> {
+        let mut data = (instructions.len() as u16).to_le_bytes().to_vec();
+        let mut serialized = vec![];
+        for instruction in instructions {
+            let offset = 2 + 2 * instructions.len() + serialized.len();
+            data.extend_from_slice(&(offset as u16).to_le_bytes());
+
+            serialized.extend_from_slice(&(instruction.accounts.len() as u16).to_le_bytes());
+            for meta in &instruction.accounts {
+                serialized.push(meta.is_signer as u8 | (meta.is_writable as u8) << 1);
+                serialized.extend_from_slice(meta.pubkey.as_ref());
+            }
+            serialized.extend_from_slice(instruction.program_id.as_ref());
+            serialized.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+            serialized.extend_from_slice(&instruction.data);
+        }
+        data.extend_from_slice(&serialized);
+        data.extend_from_slice(&[0; 2]);
+        data
+    }
+
+    fn with_instructions_sysvar<F: FnOnce(&AccountInfo)>(
+        instructions: &[Instruction],
+        current_index: u16,
+        f: F,
+    ) {
+        let mut data = serialize_instructions(instructions);
+        sysvar::instructions::store_current_index(&mut data, current_index);
+        let key = sysvar::instructions::id();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &key, false, 0);
+        f(&info)
+    }
+
+    fn other_instruction() -> Instruction {
+        let from = Pubkey::new_unique();
+        system_instruction::transfer(&from, &Pubkey::new_unique(), 1)
+    }
+
+    #[test]
+    fn test_offsets_pack_unpack() {
+        let offsets = SecpSignatureOffsets {
+            signature_offset: 345,
+            signature_instruction_index: 43,
+            eth_address_offset: 278,
+            eth_address_instruction_index: 2,
+            message_data_offset: 1024,
+            message_data_size: 77,
+            message_instruction_index: 255,
+        };
+
+        let packed = offsets.pack();
+        assert_eq!(packed[0..2], 345u16.to_le_bytes());
+        assert_eq!(SecpSignatureOffsets::unpack(&packed), Ok(offsets));
+        assert_eq!(
+            SecpSignatureOffsets::unpack(&packed[1..]),
+            Err(SecpError::MalformedInstruction)
+        );
+    }
+
+    #[test]
+    fn test_single_signature_layout() {
+        assert_eq!(ETH_ADDRESS_OFFSET, 12);
+        assert_eq!(MESSAGE_DATA_OFFSET, 97);
+
+        let address = [7u8; 20];
+        let instruction = inline_secp_instruction(&address, b"message", 0);
+        let signatures = parse_signatures(0, &instruction.data, |_| unreachable!()).unwrap();
+
+        assert_eq!(
+            signatures,
+            vec![SecpSignature {
+                eth_address: address,
+                message: b"message".to_vec(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_multiple_signatures_and_external_data() {
+        let message_holder = [b"prefix".as_ref(), b"external message"].concat();
+        let header_size = 1 + 2 * SecpSignatureOffsets::LEN;
+        let first_address = header_size;
+        let signature = first_address + 20;
+        let first_message = signature + SIGNATURE_SERIALIZED_SIZE + 1;
+        let second_address = first_message + 6;
+
+        let first = SecpSignatureOffsets {
+            signature_offset: signature as u16,
+            signature_instruction_index: 1,
+            eth_address_offset: first_address as u16,
+            eth_address_instruction_index: 1,
+            message_data_offset: first_message as u16,
+            message_data_size: 6,
+            message_instruction_index: 1,
+        };
+        // Second signature reads its message from instruction 0
+        let second = SecpSignatureOffsets {
+            eth_address_offset: second_address as u16,
+            message_data_offset: 6,
+            message_data_size: 16,
+            message_instruction_index: 0,
+            ..first
+        };
+        let data = [
+            &[2u8][..],
+            &first.pack(),
+            &second.pack(),
+            &[9u8; 20],
+            &[0u8; SIGNATURE_SERIALIZED_SIZE + 1],
+            b"inline",
+            &[3u8; 20],
+        ]
+        .concat();
+
+        let signatures = parse_signatures(1, &data, |index| {
+            assert_eq!(index, 0);
+            Ok(message_holder.clone())
+        })
+        .unwrap();
+
+        assert_eq!(
+            signatures,
+            vec![
+                SecpSignature {
+                    eth_address: [9u8; 20],
+                    message: b"inline".to_vec(),
+                },
+                SecpSignature {
+                    eth_address: [3u8; 20],
+                    message: b"external message".to_vec(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_instruction() {
+        let instruction = inline_secp_instruction(&[1u8; 20], b"message", 0);
+        let load = |_| Err(SecpError::InstructionLoadError);
+
+        assert_eq!(
+            parse_signatures(0, &[], load),
+            Err(SecpError::MalformedInstruction)
+        );
+        assert_eq!(
+            parse_signatures(0, &instruction.data[..DATA_START - 1], load),
+            Err(SecpError::MalformedInstruction)
+        );
+        assert_eq!(
+            parse_signatures(0, &instruction.data[..instruction.data.len() - 1], load),
+            Err(SecpError::MalformedInstruction)
+        );
+        // Offsets point at another instruction which can't be loaded
+        assert_eq!(
+            parse_signatures(1, &instruction.data, load),
+            Err(SecpError::InstructionLoadError)
+        );
+    }
+
+    #[test]
+    fn test_load_previous_secp_signatures() {
+        let address = [5u8; 20];
+        let instructions = vec![
+            other_instruction(),
+            inline_secp_instruction(&address, b"message", 1),
+            other_instruction(),
+        ];
+
+        with_instructions_sysvar(&instructions, 2, |info| {
+            let signatures = load_previous_secp_signatures(info).unwrap();
+            assert_eq!(signatures.len(), 1);
+            assert_eq!(signatures[0].eth_address, address);
+        });
+        with_instructions_sysvar(&instructions, 0, |info| {
+            assert_eq!(
+                load_previous_secp_signatures(info),
+                Err(SecpError::Secp256InstructionMissing)
+            );
+        });
+        with_instructions_sysvar(&instructions, 1, |info| {
+            assert_eq!(
+                load_previous_secp_signatures(info),
+                Err(SecpError::Secp256InstructionMissing)
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_secp_signatures_before() {
+        let instructions = vec![
+            inline_secp_instruction(&[1u8; 20], b"first", 0),
+            other_instruction(),
+            inline_secp_instruction(&[2u8; 20], b"second", 2),
+            inline_secp_instruction(&[3u8; 20], b"third", 3),
+        ];
+
+        with_instructions_sysvar(&instructions, 3, |info| {
+            let signatures = load_secp_signatures_before(info, 3).unwrap();
+            let addresses: Vec<EthereumAddress> =
+                signatures.iter().map(|s| s.eth_address).collect();
+            assert_eq!(addresses, vec![[1u8; 20], [2u8; 20]]);
+            assert_eq!(signatures[1].message, b"second".to_vec());
+        });
+    }
+
+    #[test]
+    fn test_verify_signer_and_message() {
+        let signatures = vec![
+            SecpSignature {
+                eth_address: [1u8; 20],
+                message: b"first".to_vec(),
+            },
+            SecpSignature {
+                eth_address: [2u8; 20],
+                message: b"second".to_vec(),
+            },
+        ];
+
+        assert_eq!(
+            verify_signer_and_message(&signatures, &[2u8; 20], b"second", false),
+            Ok(())
+        );
+        assert_eq!(
+            verify_signer_and_message(&signatures, &[2u8; 20], b"first", false),
+            Err(SecpError::WrongMessage)
+        );
+        assert_eq!(
+            verify_signer_and_message(&signatures, &[3u8; 20], b"first", false),
+            Err(SecpError::WrongSigner)
+        );
+        assert_eq!(
+            signatures[0].verify(&[1u8; 20], b"second", false),
+            Err(SecpError::WrongMessage)
+        );
+    }
+
+    #[test]
+    fn test_verify_eip191_message() {
+        let signature = SecpSignature {
+            eth_address: [1u8; 20],
+            message: eip191_message(b"message"),
+        };
+
+        assert_eq!(signature.signed_message(true), b"message");
+        assert_eq!(signature.signed_message(false), &signature.message[..]);
+        assert_eq!(signature.verify(&[1u8; 20], b"message", true), Ok(()));
+        assert_eq!(
+            signature.verify(&[1u8; 20], b"message", false),
+            Err(SecpError::WrongMessage)
+        );
+        // The prefixed form is only accepted with the flag, the raw form always is
+        let raw = SecpSignature {
+            eth_address: [1u8; 20],
+            message: b"message".to_vec(),
+        };
+        assert_eq!(raw.verify(&[1u8; 20], b"message", true), Ok(()));
+        assert_eq!(
+            verify_signer_and_message(&[raw, signature], &[1u8; 20], b"other", true),
+            Err(SecpError::WrongMessage)
+        );
+    }
+}
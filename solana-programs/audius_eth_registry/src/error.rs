@@ -1,5 +1,6 @@
 //! Error types
 
+use audius_secp_utils::SecpError;
 use num_derive::FromPrimitive;
 use solana_program::{decode_error::DecodeError, program_error::ProgramError};
 use thiserror::Error;
@@ -40,6 +41,36 @@ pub enum AudiusError {
     /// Signer group owner disabled
     #[error("Signer group owner disabled")]
     SignerGroupOwnerDisabled,
+    /// Account was closed earlier in the transaction
+    #[error("Account closed")]
+    AccountClosed,
+    /// Fewer signatures than the signer group threshold
+    #[error("Not enough signatures")]
+    NotEnoughSignatures,
+    /// Threshold must be at least one
+    #[error("Invalid threshold")]
+    InvalidThreshold,
+    /// The same valid signer account was passed more than once
+    #[error("Repeated valid signer")]
+    RepeatedSigner,
+    /// The same eth address signed more than once
+    #[error("Signature collision")]
+    SignCollision,
+    /// Signer group has no pending owner
+    #[error("No pending owner")]
+    NoPendingOwner,
+    /// Message was already validated by `ValidateSignatureOnce`
+    #[error("Message already used")]
+    MessageAlreadyUsed,
+    /// Threshold is higher than the number of valid signers in the group
+    #[error("Threshold exceeds signer count")]
+    ThresholdExceedsSignerCount,
+    /// Signer group can't count any more valid signers
+    #[error("Too many signers")]
+    TooManySigners,
+    /// Used message account can't be closed before its message expires
+    #[error("Message not expired")]
+    MessageNotExpired,
 }
 impl From<AudiusError> for ProgramError {
     fn from(e: AudiusError) -> Self {
@@ -51,3 +82,14 @@ impl<T> DecodeError<T> for AudiusError {
         "Audius Error"
     }
 }
+impl From<SecpError> for AudiusError {
+    fn from(e: SecpError) -> Self {
+        match e {
+            SecpError::InstructionLoadError => AudiusError::SignatureMissing,
+            SecpError::Secp256InstructionMissing => AudiusError::Secp256InstructionLosing,
+            SecpError::MalformedInstruction | SecpError::WrongSigner | SecpError::WrongMessage => {
+                AudiusError::SignatureVerificationFailed
+            }
+        }
+    }
+}
@@ -1,12 +1,20 @@
 //! Instruction types
 
-use crate::state::SecpSignatureOffsets;
+use crate::processor::{
+    SIGNER_GROUP_SEED_PREFIX, USED_MESSAGE_SEED_PREFIX, VALID_SIGNER_SEED_PREFIX,
+};
+use crate::state::{
+    SecpSignatureOffsets, ADD_SIGNER_MESSAGE_PREFIX, CLEAR_SIGNER_MESSAGE_PREFIX,
+    ENABLE_OWNER_MESSAGE_PREFIX, SET_THRESHOLD_MESSAGE_PREFIX,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::UnixTimestamp,
+    hash::hash,
     instruction::{AccountMeta, Instruction},
     program_error::ProgramError,
     pubkey::Pubkey,
-    sysvar,
+    system_program, sysvar,
 };
 
 /// Signature with message to validate
@@ -19,34 +27,133 @@ pub struct SignatureData {
     pub message: Vec<u8>,
 }
 
+/// Finds the address of the signer group of `owner` created with `seed`,
+/// which is at most 32 bytes long
+pub fn find_signer_group_address(program_id: &Pubkey, owner: &Pubkey, seed: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SIGNER_GROUP_SEED_PREFIX.as_ref(), owner.as_ref(), seed],
+        program_id,
+    )
+}
+
+/// Finds the address of the valid signer of `signer_group` for `eth_address`
+pub fn find_valid_signer_address(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    eth_address: &[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VALID_SIGNER_SEED_PREFIX.as_ref(),
+            signer_group.as_ref(),
+            eth_address.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Finds the address of the account marking `message` signed by `eth_address`
+/// in `signer_group` as used
+pub fn find_used_message_address(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    eth_address: &[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    message: &[u8],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            USED_MESSAGE_SEED_PREFIX.as_ref(),
+            signer_group.as_ref(),
+            eth_address.as_ref(),
+            hash(message).as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Action valid signers approve to add `eth_address` to `signer_group` as `new_valid_signer`
+pub fn add_signer_action(
+    signer_group: &Pubkey,
+    new_valid_signer: &Pubkey,
+    eth_address: &[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+) -> Vec<u8> {
+    [
+        ADD_SIGNER_MESSAGE_PREFIX.as_bytes(),
+        signer_group.as_ref(),
+        new_valid_signer.as_ref(),
+        eth_address.as_ref(),
+    ]
+    .concat()
+}
+
+/// Action valid signers approve to remove `old_valid_signer` from `signer_group`
+pub fn clear_signer_action(signer_group: &Pubkey, old_valid_signer: &Pubkey) -> Vec<u8> {
+    [
+        CLEAR_SIGNER_MESSAGE_PREFIX.as_bytes(),
+        signer_group.as_ref(),
+        old_valid_signer.as_ref(),
+    ]
+    .concat()
+}
+
+/// Action valid signers approve to set the threshold of `signer_group`
+pub fn set_threshold_action(signer_group: &Pubkey, threshold: u8) -> Vec<u8> {
+    [
+        SET_THRESHOLD_MESSAGE_PREFIX.as_bytes(),
+        signer_group.as_ref(),
+        &[threshold],
+    ]
+    .concat()
+}
+
+/// Action valid signers approve to re-enable `owner` of `signer_group`
+pub fn enable_owner_action(signer_group: &Pubkey, owner: &Pubkey) -> Vec<u8> {
+    [
+        ENABLE_OWNER_MESSAGE_PREFIX.as_bytes(),
+        signer_group.as_ref(),
+        owner.as_ref(),
+    ]
+    .concat()
+}
+
+/// Message valid signers sign to approve `action`, followed by the unix
+/// timestamp it was signed at so it expires
+pub fn action_message(action: &[u8], timestamp: UnixTimestamp) -> Vec<u8> {
+    [action, &timestamp.to_le_bytes()].concat()
+}
+
 /// Instructions supported by the Audius program
 #[repr(C)]
 #[derive(Clone, BorshDeserialize, BorshSerialize)]
 pub enum AudiusInstruction {
-    ///   Create new signer group account
+    ///   Initialize a signer group account created at a keypair address
     ///
     ///   0. `[w]` New SignerGroup to create
     ///   1. `[]` SignerGroup's owner
     InitSignerGroup,
-    ///   Create new valid signer account
+    ///   Initialize a valid signer account created at a keypair address
     ///
     ///   0. `[w]` Uninitialized valid signer account
-    ///   1. `[]` Group for Valid Signer to join with
+    ///   1. `[w]` Group for Valid Signer to join with
     ///   2. `[s]` SignerGroup's owner
     InitValidSigner([u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]),
-    ///   Remove valid signer from the group
+    ///   Remove valid signer from the group, closing its account
     ///
     ///   0. `[w]` Initialized valid signer to remove
-    ///   1. `[]` Signer group to remove from
+    ///   1. `[w]` Signer group to remove from
     ///   2. `[s]` SignerGroup's owner
+    ///   3. `[w]` Account to refund the valid signer's rent to
     ClearValidSigner,
-    ///   Validate multiple signatures
-    ///   Remove existing ValidSigner if all 3 are validated
+    ///   Validate multiple signatures of `clear_signer_action`
+    ///   Remove existing ValidSigner if all 3 are validated, closing its account
     ///   0. `[]`  Initialized valid signer 1
     ///   1. `[]`  Initialized valid signer 2
     ///   2. `[]`  Initialized valid signer 3
-    ///   3. `[]`  Signer group signer belongs to
-    ///   0. `[w]` Initialized valid signer to remove
+    ///   3. `[w]` Signer group signer belongs to
+    ///   4. `[w]` Initialized valid signer to remove
+    ///   5. `[]`  Sysvar instructions
+    ///   6. `[]`  Sysvar clock
+    ///   7. `[w]` Account to refund the removed valid signer's rent to
     ValidateMultipleSignaturesClearValidSigner(SignatureData, SignatureData, SignatureData),
     ///   Validate signature issued by valid signer
     ///
@@ -57,12 +164,12 @@ pub enum AudiusInstruction {
     ///   0. `[w]` SignerGroup to disable
     ///   1. `[]` SignerGroup's owner
     DisableSignerGroupOwner,
-    ///   Validate multiple signatures
+    ///   Validate multiple signatures of `add_signer_action`
     ///   Add new ValidSigner if all 3 are validated
     ///   0. `[]`  Initialized valid signer 1
     ///   1. `[]`  Initialized valid signer 2
     ///   2. `[]`  Initialized valid signer 3
-    ///   3. `[]`  Signer group signer belongs to
+    ///   3. `[w]` Signer group signer belongs to
     ///   4. `[w]` Incoming ValidSigner account
     ValidateMultipleSignaturesAddSigner(
         SignatureData,
@@ -70,6 +177,123 @@ pub enum AudiusInstruction {
         SignatureData,
         [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
     ),
+    ///   Set the number of signatures required to change the group
+    ///
+    ///   0. `[w]` SignerGroup to update
+    ///   1. `[s]` SignerGroup's owner
+    SetThreshold(u8),
+    ///   Validate `add_signer_action` signatures of at least `threshold` valid signers
+    ///   Add new ValidSigner if all are validated
+    ///   0. `[w]` Signer group signers belong to
+    ///   1. `[w]` Incoming ValidSigner account
+    ///   2. `[]`  Sysvar instructions
+    ///   3. `[]`  Sysvar clock
+    ///   4. ..4+N `[]` Initialized valid signers, one per signature
+    ValidateQuorumAddSigner(Vec<SignatureData>, [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]),
+    ///   Validate `clear_signer_action` signatures of at least `threshold` valid signers
+    ///   Remove existing ValidSigner if all are validated, closing its account
+    ///   0. `[w]` Signer group signers belong to
+    ///   1. `[w]` Initialized valid signer to remove
+    ///   2. `[]`  Sysvar instructions
+    ///   3. `[]`  Sysvar clock
+    ///   4. `[w]` Account to refund the removed valid signer's rent to
+    ///   5. ..5+N `[]` Initialized valid signers, one per signature
+    ValidateQuorumClearValidSigner(Vec<SignatureData>),
+    ///   Validate `set_threshold_action` signatures of at least `threshold` valid signers
+    ///   Set the group's threshold if all are validated
+    ///   0. `[w]` Signer group signers belong to
+    ///   1. `[]`  Sysvar instructions
+    ///   2. `[]`  Sysvar clock
+    ///   3. ..3+N `[]` Initialized valid signers, one per signature
+    ValidateQuorumSetThreshold(Vec<SignatureData>, u8),
+    ///   Propose a new owner, who takes over the group once it accepts
+    ///
+    ///   0. `[w]` SignerGroup to transfer
+    ///   1. `[s]` SignerGroup's owner
+    ///   2. `[]`  Proposed owner
+    TransferSignerGroupOwnership,
+    ///   Make the pending owner the group's owner
+    ///
+    ///   0. `[w]` SignerGroup to take over
+    ///   1. `[s]` Pending owner
+    AcceptSignerGroupOwnership,
+    ///   Validate `enable_owner_action` signatures of at least `threshold` valid signers
+    ///   Re-enable the group's owner if all are validated
+    ///   0. `[w]` Signer group signers belong to
+    ///   1. `[]`  Sysvar instructions
+    ///   2. `[]`  Sysvar clock
+    ///   3. ..3+N `[]` Initialized valid signers, one per signature
+    EnableSignerGroupOwner(Vec<SignatureData>),
+    ///   Create new signer group account at `find_signer_group_address` of the owner and seed
+    ///
+    ///   0. `[ws]` Funder of the new account
+    ///   1. `[w]`  SignerGroup to create
+    ///   2. `[]`   SignerGroup's owner
+    ///   3. `[]`   Sysvar rent
+    ///   4. `[]`   System program
+    CreateSignerGroup(Vec<u8>),
+    ///   Create new valid signer account at `find_valid_signer_address` of the group
+    ///   and eth address
+    ///
+    ///   0. `[ws]` Funder of the new account
+    ///   1. `[w]`  ValidSigner to create
+    ///   2. `[w]`  Group for Valid Signer to join with
+    ///   3. `[s]`  SignerGroup's owner
+    ///   4. `[]`   Sysvar rent
+    ///   5. `[]`   System program
+    CreateValidSigner([u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]),
+    ///   Validate `add_signer_action` signatures of at least `threshold` valid signers
+    ///   Create new ValidSigner at `find_valid_signer_address` if all are validated
+    ///   0. `[w]` Signer group signers belong to
+    ///   1. `[w]` ValidSigner to create
+    ///   2. `[]`  Sysvar instructions
+    ///   3. `[]`  Sysvar clock
+    ///   4. `[ws]` Funder of the new account
+    ///   5. `[]`  Sysvar rent
+    ///   6. `[]`  System program
+    ///   7. ..7+N `[]` Initialized valid signers, one per signature
+    ValidateQuorumCreateSigner(Vec<SignatureData>, [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]),
+    ///   Move a valid signer at a keypair address to its `find_valid_signer_address`,
+    ///   closing the old account. The signer keeps its group and eth address.
+    ///
+    ///   0. `[ws]` Funder of the new account, refunded the old account's rent
+    ///   1. `[w]`  Initialized valid signer at a keypair address
+    ///   2. `[w]`  ValidSigner to create
+    ///   3. `[]`   Group of the valid signer
+    ///   4. `[s]`  SignerGroup's owner
+    ///   5. `[]`   Sysvar rent
+    ///   6. `[]`   System program
+    MigrateValidSigner,
+    ///   Validate signature issued by valid signer, accepting each message only once.
+    ///   The message must end with a recent unix timestamp, see `action_message`.
+    ///   Creates the `find_used_message_address` account, failing if it already exists.
+    ///
+    ///   0. `[]`  Initialized valid signer
+    ///   1. `[]`  Signer group signer belongs to
+    ///   2. `[]`  Sysvar instructions
+    ///   3. `[ws]` Funder of the used message account
+    ///   4. `[w]` Used message account to create
+    ///   5. `[]`  Sysvar rent
+    ///   6. `[]`  System program
+    ///   7. `[]`  Sysvar clock
+    ValidateSignatureOnce(SignatureData),
+    ///   Grow a legacy signer group to `SignerGroup::LEN`, counting the valid signers
+    ///   passed in. The group keeps the default threshold until it's changed.
+    ///
+    ///   0. `[ws]` Funder of the grown account's rent
+    ///   1. `[w]`  Legacy SignerGroup to migrate
+    ///   2. `[s]`  SignerGroup's owner
+    ///   3. `[]`   Sysvar rent
+    ///   4. `[]`   System program
+    ///   5. ..5+N `[]` Every valid signer of the group
+    MigrateSignerGroup,
+    ///   Close a used message account once its message expired, refunding its
+    ///   funder. Anyone can submit it.
+    ///
+    ///   0. `[w]` UsedMessage to close
+    ///   1. `[w]` Funder of the used message account
+    ///   2. `[]`  Sysvar clock
+    CloseUsedMessage,
 }
 
 /// Creates `InitSignerGroup` instruction
@@ -102,7 +326,7 @@ pub fn init_valid_signer(
 
     let accounts = vec![
         AccountMeta::new(*valid_signer_account, false),
-        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new(*signer_group, false),
         AccountMeta::new_readonly(*groups_owner, true),
     ];
     Ok(Instruction {
@@ -118,11 +342,13 @@ pub fn clear_valid_signer(
     valid_signer_account: &Pubkey,
     signer_group: &Pubkey,
     groups_owner: &Pubkey,
+    refund: &Pubkey,
 ) -> Result<Instruction, ProgramError> {
     let accounts = vec![
         AccountMeta::new(*valid_signer_account, false),
-        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new(*signer_group, false),
         AccountMeta::new_readonly(*groups_owner, true),
+        AccountMeta::new(*refund, false),
     ];
     Ok(Instruction {
         program_id: *program_id,
@@ -139,6 +365,7 @@ pub fn validate_multiple_signatures_clear_valid_signer(
     valid_signer_3: &Pubkey,
     signer_group: &Pubkey,
     old_valid_signer: &Pubkey,
+    refund: &Pubkey,
     signature_data_1: SignatureData,
     signature_data_2: SignatureData,
     signature_data_3: SignatureData,
@@ -153,10 +380,11 @@ pub fn validate_multiple_signatures_clear_valid_signer(
         AccountMeta::new_readonly(*valid_signer_1, false),
         AccountMeta::new_readonly(*valid_signer_2, false),
         AccountMeta::new_readonly(*valid_signer_3, false),
-        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new(*signer_group, false),
         AccountMeta::new(*old_valid_signer, false),
         AccountMeta::new_readonly(sysvar::instructions::id(), false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(*refund, false),
     ];
     Ok(Instruction {
         program_id: *program_id,
@@ -211,7 +439,7 @@ pub fn validate_multiple_signatures_add_signer(
         AccountMeta::new_readonly(*valid_signer_1, false),
         AccountMeta::new_readonly(*valid_signer_2, false),
         AccountMeta::new_readonly(*valid_signer_3, false),
-        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new(*signer_group, false),
         AccountMeta::new(*new_valid_signer, false),
         AccountMeta::new_readonly(sysvar::instructions::id(), false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
@@ -262,3 +490,353 @@ pub fn disable_signer_group_owner(
         data: AudiusInstruction::DisableSignerGroupOwner.try_to_vec()?,
     })
 }
+
+/// Creates `SetThreshold` instruction
+pub fn set_threshold(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    owner: &Pubkey,
+    threshold: u8,
+) -> Result<Instruction, ProgramError> {
+    let accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::SetThreshold(threshold).try_to_vec()?,
+    })
+}
+
+/// Creates `ValidateQuorumAddSigner` instruction
+pub fn validate_quorum_add_signer(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    new_valid_signer: &Pubkey,
+    valid_signers: &[Pubkey],
+    signature_data: Vec<SignatureData>,
+    eth_pubkey: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+) -> Result<Instruction, ProgramError> {
+    let args = AudiusInstruction::ValidateQuorumAddSigner(signature_data, eth_pubkey);
+    let data = args.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new(*new_valid_signer, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    accounts.extend(
+        valid_signers
+            .iter()
+            .map(|valid_signer| AccountMeta::new_readonly(*valid_signer, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates `ValidateQuorumClearValidSigner` instruction
+pub fn validate_quorum_clear_valid_signer(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    old_valid_signer: &Pubkey,
+    refund: &Pubkey,
+    valid_signers: &[Pubkey],
+    signature_data: Vec<SignatureData>,
+) -> Result<Instruction, ProgramError> {
+    let args = AudiusInstruction::ValidateQuorumClearValidSigner(signature_data);
+    let data = args.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new(*old_valid_signer, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(*refund, false),
+    ];
+    accounts.extend(
+        valid_signers
+            .iter()
+            .map(|valid_signer| AccountMeta::new_readonly(*valid_signer, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates `ValidateQuorumSetThreshold` instruction
+pub fn validate_quorum_set_threshold(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    valid_signers: &[Pubkey],
+    signature_data: Vec<SignatureData>,
+    threshold: u8,
+) -> Result<Instruction, ProgramError> {
+    let args = AudiusInstruction::ValidateQuorumSetThreshold(signature_data, threshold);
+    let data = args.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    accounts.extend(
+        valid_signers
+            .iter()
+            .map(|valid_signer| AccountMeta::new_readonly(*valid_signer, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates `TransferSignerGroupOwnership` instruction
+pub fn transfer_signer_group_ownership(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    owner: &Pubkey,
+    proposed_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*proposed_owner, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::TransferSignerGroupOwnership.try_to_vec()?,
+    })
+}
+
+/// Creates `AcceptSignerGroupOwnership` instruction
+pub fn accept_signer_group_ownership(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    pending_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(*pending_owner, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::AcceptSignerGroupOwnership.try_to_vec()?,
+    })
+}
+
+/// Creates `EnableSignerGroupOwner` instruction
+pub fn enable_signer_group_owner(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    valid_signers: &[Pubkey],
+    signature_data: Vec<SignatureData>,
+) -> Result<Instruction, ProgramError> {
+    let args = AudiusInstruction::EnableSignerGroupOwner(signature_data);
+    let data = args.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    accounts.extend(
+        valid_signers
+            .iter()
+            .map(|valid_signer| AccountMeta::new_readonly(*valid_signer, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates `CreateSignerGroup` instruction
+pub fn create_signer_group(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    owner: &Pubkey,
+    seed: &[u8],
+) -> Result<Instruction, ProgramError> {
+    let (signer_group, _) = find_signer_group_address(program_id, owner, seed);
+    let accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(signer_group, false),
+        AccountMeta::new_readonly(*owner, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::CreateSignerGroup(seed.to_vec()).try_to_vec()?,
+    })
+}
+
+/// Creates `CreateValidSigner` instruction
+pub fn create_valid_signer(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    signer_group: &Pubkey,
+    owner: &Pubkey,
+    eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+) -> Result<Instruction, ProgramError> {
+    let (valid_signer, _) = find_valid_signer_address(program_id, signer_group, &eth_address);
+    let accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(valid_signer, false),
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::CreateValidSigner(eth_address).try_to_vec()?,
+    })
+}
+
+/// Creates `ValidateQuorumCreateSigner` instruction
+pub fn validate_quorum_create_signer(
+    program_id: &Pubkey,
+    signer_group: &Pubkey,
+    funder: &Pubkey,
+    valid_signers: &[Pubkey],
+    signature_data: Vec<SignatureData>,
+    eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+) -> Result<Instruction, ProgramError> {
+    let (new_valid_signer, _) = find_valid_signer_address(program_id, signer_group, &eth_address);
+    let args = AudiusInstruction::ValidateQuorumCreateSigner(signature_data, eth_address);
+    let data = args.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new(new_valid_signer, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(*funder, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        valid_signers
+            .iter()
+            .map(|valid_signer| AccountMeta::new_readonly(*valid_signer, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates `MigrateValidSigner` instruction for the valid signer at `old_valid_signer`
+/// of `signer_group` with `eth_address`
+pub fn migrate_valid_signer(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    old_valid_signer: &Pubkey,
+    signer_group: &Pubkey,
+    signer_group_owner: &Pubkey,
+    eth_address: &[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+) -> Result<Instruction, ProgramError> {
+    let (new_valid_signer, _) = find_valid_signer_address(program_id, signer_group, eth_address);
+    let accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(*old_valid_signer, false),
+        AccountMeta::new(new_valid_signer, false),
+        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new_readonly(*signer_group_owner, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::MigrateValidSigner.try_to_vec()?,
+    })
+}
+
+/// Creates `ValidateSignatureOnce` instruction for the valid signer at
+/// `valid_signer_account` with `eth_address`
+pub fn validate_signature_once(
+    program_id: &Pubkey,
+    valid_signer_account: &Pubkey,
+    signer_group: &Pubkey,
+    funder: &Pubkey,
+    eth_address: &[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    signature_data: SignatureData,
+) -> Result<Instruction, ProgramError> {
+    let (used_message, _) = find_used_message_address(
+        program_id,
+        signer_group,
+        eth_address,
+        &signature_data.message,
+    );
+    let accounts = vec![
+        AccountMeta::new_readonly(*valid_signer_account, false),
+        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(used_message, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::ValidateSignatureOnce(signature_data).try_to_vec()?,
+    })
+}
+
+/// Creates `MigrateSignerGroup` instruction for a legacy group with `valid_signers`
+pub fn migrate_signer_group(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    signer_group: &Pubkey,
+    owner: &Pubkey,
+    valid_signers: &[Pubkey],
+) -> Result<Instruction, ProgramError> {
+    let mut accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(*signer_group, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        valid_signers
+            .iter()
+            .map(|valid_signer| AccountMeta::new_readonly(*valid_signer, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::MigrateSignerGroup.try_to_vec()?,
+    })
+}
+
+/// Creates `CloseUsedMessage` instruction refunding `funder`
+pub fn close_used_message(
+    program_id: &Pubkey,
+    used_message: &Pubkey,
+    funder: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let accounts = vec![
+        AccountMeta::new(*used_message, false),
+        AccountMeta::new(*funder, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: AudiusInstruction::CloseUsedMessage.try_to_vec()?,
+    })
+}
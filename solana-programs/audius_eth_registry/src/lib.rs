@@ -6,6 +6,7 @@ pub mod error;
 pub mod instruction;
 pub mod processor;
 pub mod state;
+pub mod utils;
 
 /// Current program version
 pub const PROGRAM_VERSION: u8 = 1;
@@ -1,19 +1,32 @@
 //! Program state processor
 
 use crate::error::AudiusError;
-use crate::instruction::{AudiusInstruction, SignatureData};
-use crate::state::{SecpSignatureOffsets, SignerGroup, ValidSigner};
+use crate::instruction::{
+    add_signer_action, clear_signer_action, enable_owner_action, find_signer_group_address,
+    find_used_message_address, find_valid_signer_address, set_threshold_action,
+    AudiusInstruction, SignatureData,
+};
+use crate::state::{SecpSignature, SecpSignatureOffsets, SignerGroup, UsedMessage, ValidSigner};
+use crate::utils::assert_account_key;
+use audius_secp_utils::{
+    assert_clock_sysvar, assert_instructions_sysvar, assert_rent_sysvar, assert_system_program,
+    close_account, create_derived_account, parse_secp_signatures, resize_account,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use num_traits::FromPrimitive;
 use solana_program::instruction::Instruction;
 use solana_program::decode_error::DecodeError;
 use solana_program::clock::UnixTimestamp;
 use solana_program::program_error::PrintProgramError;
+use std::collections::{BTreeMap, BTreeSet};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    hash::hash,
     msg,
-    pubkey::Pubkey,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+    rent::Rent,
     sysvar,
     sysvar::clock::Clock,
     sysvar::Sysvar,
@@ -25,6 +38,16 @@ use solana_program::{
 // 10 minutes
 const MAX_TIME_DIFF_SECONDS: UnixTimestamp = 600;
 
+// Size of the unix timestamp ending each signed action message
+const TIMESTAMP_SIZE: usize = 8;
+
+/// Signer group program account seed
+pub const SIGNER_GROUP_SEED_PREFIX: &str = "G_";
+/// Valid signer program account seed
+pub const VALID_SIGNER_SEED_PREFIX: &str = "S_";
+/// Used message program account seed
+pub const USED_MESSAGE_SEED_PREFIX: &str = "M_";
+
 /// Program state handler
 pub struct Processor {}
 impl Processor {
@@ -37,52 +60,59 @@ impl Processor {
     /// ValidSigner version indicating signer uninitialization
     pub const VALID_SIGNER_UNINITIALIZED_VERSION: u8 = 0;
 
+    /// UsedMessage version indicating the message was used
+    pub const USED_MESSAGE_VERSION: u8 = 1;
+
     fn validate_eth_signature(
         expected_signer: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
         message: &[u8],
-        secp_instruction_data: Vec<u8>,
+        signature: &SecpSignature,
     ) -> Result<(), AudiusError> {
-        let eth_address_offset = 12;
-        let instruction_signer = secp_instruction_data
-            [eth_address_offset..eth_address_offset + SecpSignatureOffsets::ETH_ADDRESS_SIZE]
-            .to_vec();
-        if instruction_signer != expected_signer {
-            return Err(AudiusError::SignatureVerificationFailed.into());
-        }
-
-        let message_data_offset = 97; // meta (12) + address (20) + signature (65) = 97
-        let instruction_message = secp_instruction_data[message_data_offset..].to_vec();
-        if instruction_message != message {
-            return Err(AudiusError::SignatureVerificationFailed.into());
-        }
-        Ok(())
+        signature
+            .verify(&expected_signer, message, false)
+            .map_err(AudiusError::from)
     }
 
     /// Process [Convert i64 from Vec<u8>] ()
     pub fn int_from_vec(
-        message: &Vec<u8>
+        message: &[u8]
     ) -> i64 {
         let mut intermediate_array = [0u8; 8];
         intermediate_array[0..8].copy_from_slice(message);
         return i64::from_le_bytes(intermediate_array);
     }
 
-    /// Process [validate timestamp messages] ()
-    pub fn validate_timestamp_messages(
+    /// Returns the unix timestamp ending `message` if it's recent
+    fn recent_message_timestamp(
         clock: &sysvar::clock::Clock,
-        message_1: &Vec<u8>,
-        message_2: &Vec<u8>,
-        message_3: &Vec<u8>,
-    ) -> Result<(), AudiusError> {
-        let timestamp_1: i64 = Self::int_from_vec(message_1);
-        let timestamp_2: i64 = Self::int_from_vec(message_2);
-        let timestamp_3: i64 = Self::int_from_vec(message_3);
+        message: &[u8],
+    ) -> Result<UnixTimestamp, AudiusError> {
+        if message.len() < TIMESTAMP_SIZE {
+            return Err(AudiusError::InvalidInstruction);
+        }
+        let timestamp = Self::int_from_vec(&message[message.len() - TIMESTAMP_SIZE..]);
+        if (clock.unix_timestamp - timestamp).abs() > MAX_TIME_DIFF_SECONDS {
+            return Err(AudiusError::InvalidInstruction);
+        }
+        Ok(timestamp)
+    }
 
-        if (clock.unix_timestamp - timestamp_1).abs() > MAX_TIME_DIFF_SECONDS
-            || (clock.unix_timestamp - timestamp_2).abs() > MAX_TIME_DIFF_SECONDS
-            || (clock.unix_timestamp - timestamp_3).abs() > MAX_TIME_DIFF_SECONDS
-        {
-            return Err(AudiusError::InvalidInstruction.into());
+    /// Process [validate action messages] ()
+    /// Each message must be `action` followed by a recent unix timestamp
+    pub fn validate_action_messages(
+        clock: &sysvar::clock::Clock,
+        action: &[u8],
+        signature_data_array: &[&SignatureData],
+    ) -> Result<(), AudiusError> {
+        for signature_data in signature_data_array {
+            let message = &signature_data.message;
+            if message.len() != action.len() + TIMESTAMP_SIZE || !message.starts_with(action) {
+                return Err(AudiusError::InvalidInstruction);
+            }
+            let timestamp: i64 = Self::int_from_vec(&message[action.len()..]);
+            if (clock.unix_timestamp - timestamp).abs() > MAX_TIME_DIFF_SECONDS {
+                return Err(AudiusError::InvalidInstruction.into());
+            }
         }
 
         return std::result::Result::Ok(());
@@ -95,7 +125,8 @@ impl Processor {
     ) -> Result<Vec<Instruction>, AudiusError> {
         let mut v: Vec<Instruction> = Vec::new();
         // Index of current instruction in tx
-        let index = sysvar::instructions::load_current_index(&instruction_info.data.borrow());
+        let index = sysvar::instructions::load_current_index_checked(instruction_info)
+            .map_err(|_| AudiusError::SignatureMissing)?;
         // Indicates no instructions present
         if index == 0 {
             return Err(AudiusError::Secp256InstructionLosing.into());
@@ -103,9 +134,9 @@ impl Processor {
         // Iterate over all instructions and recover SECP instruction
         let mut iterator = 0;
         while iterator < index {
-            let secp_instruction = sysvar::instructions::load_instruction_at(
+            let secp_instruction = sysvar::instructions::load_instruction_at_checked(
                 iterator as usize,
-                &instruction_info.data.borrow(),
+                instruction_info,
             ).map_err(|_| AudiusError::SignatureMissing)?;
 
             if secp_instruction.program_id != secp256k1_program::id() {
@@ -120,13 +151,15 @@ impl Processor {
     }
 
     /// Process [ValidateSignerData]().
-    /// Validates eth signature recovery for each provided ValidSigner
+    /// Validates eth signature recovery for each provided ValidSigner,
+    /// rejecting repeated accounts and eth addresses
     pub fn validate_signer_data(
         instruction_info: &AccountInfo,
         signer_group_info: &AccountInfo,
         valid_signer_accounts: &[&AccountInfo],
         signature_data_array: &[&SignatureData]
-    ) -> Result<(), AudiusError> {
+    ) -> ProgramResult {
+        assert_instructions_sysvar(instruction_info)?;
 
         let instruction_recovery = Self::recover_secp_instructions(&instruction_info);
         if instruction_recovery.is_err() {
@@ -134,15 +167,34 @@ impl Processor {
         }
 
         let recovered_instructions = instruction_recovery?;
-        if recovered_instructions.len() < valid_signer_accounts.len()
-            || recovered_instructions.len() < signature_data_array.len() {
+
+        // Each secp instruction may check several signatures
+        let mut signatures: Vec<SecpSignature> = Vec::new();
+        for (index, secp_instruction) in recovered_instructions.iter().enumerate() {
+            signatures.extend(
+                parse_secp_signatures(instruction_info, index as u16, &secp_instruction.data)
+                    .map_err(AudiusError::from)?,
+            );
+        }
+
+        if signatures.len() < valid_signer_accounts.len()
+            || signatures.len() < signature_data_array.len() {
             return Err(AudiusError::Secp256InstructionLosing.into());
         }
 
-        for i in 0..recovered_instructions.len() {
-            let secp_instruction = &recovered_instructions[i];
-            let valid_signer_info = valid_signer_accounts[i];
-            let signature_data = signature_data_array[i];
+        let mut signer_keys = BTreeSet::new();
+        for valid_signer_info in valid_signer_accounts {
+            if !signer_keys.insert(valid_signer_info.key) {
+                return Err(AudiusError::RepeatedSigner.into());
+            }
+        }
+
+        let mut checkmap = BTreeMap::new();
+        for ((signature, valid_signer_info), signature_data) in signatures
+            .iter()
+            .zip(valid_signer_accounts)
+            .zip(signature_data_array)
+        {
 
             let valid_signer = Box::new(ValidSigner::try_from_slice(
                 &valid_signer_info.data.borrow(),
@@ -161,8 +213,12 @@ impl Processor {
             Self::validate_eth_signature(
                 valid_signer.eth_address,
                 &signature_data.message,
-                secp_instruction.data.clone()
+                signature
             )?;
+
+            if checkmap.insert(signature.eth_address, true).is_some() {
+                return Err(AudiusError::SignCollision.into());
+            }
         }
 
         return std::result::Result::Ok(());
@@ -184,7 +240,7 @@ impl Processor {
             return Err(AudiusError::InvalidInstruction.into());
         }
 
-        let mut signer_group = Box::new(SignerGroup::try_from_slice(
+        let mut signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
@@ -198,9 +254,9 @@ impl Processor {
 
         signer_group.owner_enabled = true;
 
-        signer_group
-            .serialize(&mut *signer_group_info.data.borrow_mut())
-            .map_err(|e| e.into())
+        signer_group.threshold = SignerGroup::DEFAULT_THRESHOLD;
+
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
     }
 
     /// Process [DisableSignerGroupOwner]().
@@ -224,47 +280,41 @@ impl Processor {
             return Err(AudiusError::SignatureMissing.into());
         }
 
-        let mut signer_group = Box::new(SignerGroup::try_from_slice(
+        let mut signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
         if !signer_group.is_initialized() {
             return Err(AudiusError::SignerGroupOwnerDisabled.into());
         }
+
+        signer_group.check_owner(&group_owner_info)?;
+
         signer_group.version = Self::SIGNER_GROUP_VERSION;
 
         signer_group.owner_enabled = false;
-        signer_group
-            .serialize(&mut *signer_group_info.data.borrow_mut())
-            .map_err(|e| e.into())
+        signer_group.pending_owner = Pubkey::default();
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
     }
 
-    /// Process [InitValidSigner]().
-    pub fn process_init_valid_signer(
+    /// Process [TransferSignerGroupOwnership]().
+    pub fn process_transfer_signer_group_ownership(
         _program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+        accounts: &[AccountInfo]
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        // uninitialized valid signer account
-        let valid_signer_info = next_account_info(account_info_iter)?;
         // signer group account
         let signer_group_info = next_account_info(account_info_iter)?;
-        // signer group's owner
-        let signer_groups_owner_info = next_account_info(account_info_iter)?;
+        // signer group owner account
+        let group_owner_info = next_account_info(account_info_iter)?;
+        // proposed owner account
+        let proposed_owner_info = next_account_info(account_info_iter)?;
 
-        // Confirm program ownership of SignerGroup and ValidSigner
-        if valid_signer_info.owner != _program_id
-            || signer_group_info.owner != _program_id {
+        if signer_group_info.owner != _program_id {
             return Err(AudiusError::InvalidInstruction.into());
         }
 
-        // Verify SignerGroupOwner submission of this transaction
-        if !signer_groups_owner_info.is_signer {
-            return Err(AudiusError::SignatureMissing.into());
-        }
-
-        let signer_group = Box::new(SignerGroup::try_from_slice(
+        let mut signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
@@ -277,34 +327,94 @@ impl Processor {
             return Err(AudiusError::SignerGroupOwnerDisabled.into());
         }
 
-        let mut valid_signer = Box::new(ValidSigner::try_from_slice(
-            &valid_signer_info.data.borrow(),
+        signer_group.check_owner(&group_owner_info)?;
+
+        signer_group.pending_owner = *proposed_owner_info.key;
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
+
+    /// Process [AcceptSignerGroupOwnership]().
+    pub fn process_accept_signer_group_ownership(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo]
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // pending owner account
+        let pending_owner_info = next_account_info(account_info_iter)?;
+
+        if signer_group_info.owner != _program_id {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        if !pending_owner_info.is_signer {
+            return Err(AudiusError::SignatureMissing.into());
+        }
+
+        let mut signer_group = Box::new(SignerGroup::unpack(
+            &signer_group_info.data.borrow(),
         )?);
 
-        if valid_signer.is_initialized() {
-            return Err(AudiusError::SignerAlreadyInitialized.into());
+        if !signer_group.is_initialized() {
+            return Err(AudiusError::UninitializedSignerGroup.into());
         }
 
-        signer_group.check_owner(&signer_groups_owner_info)?;
+        if signer_group.pending_owner == Pubkey::default() {
+            return Err(AudiusError::NoPendingOwner.into());
+        }
 
-        // TODO: check if ethereum public key is valid
+        if *pending_owner_info.key != signer_group.pending_owner {
+            return Err(AudiusError::WrongOwner.into());
+        }
 
-        valid_signer.version = Self::VALID_SIGNER_VERSION;
-        valid_signer.signer_group = *signer_group_info.key;
-        valid_signer.eth_address = eth_address;
+        signer_group.owner = signer_group.pending_owner;
+        signer_group.pending_owner = Pubkey::default();
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
 
-        valid_signer
-            .serialize(&mut *valid_signer_info.data.borrow_mut())
-            .map_err(|e| e.into())
+    /// Process [EnableSignerGroupOwner]().
+    pub fn process_enable_signer_group_owner(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: Vec<SignatureData>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // initialized valid signer accounts, one per signature
+        let valid_signer_acct_array: Vec<&AccountInfo> = account_info_iter.collect();
+
+        // The action is bound to the owner being enabled
+        let owner = SignerGroup::unpack(&signer_group_info.data.borrow())?.owner;
+
+        let sig_data_array: Vec<&SignatureData> = signature_data.iter().collect();
+        let mut signer_group = Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &enable_owner_action(signer_group_info.key, &owner),
+        )?;
+
+        signer_group.owner_enabled = true;
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
     }
 
-    /// Process [ClearValidSigner]().
-    pub fn process_clear_valid_signer(
+    /// Process [InitValidSigner]().
+    pub fn process_init_valid_signer(
         _program_id: &Pubkey,
-        accounts: &[AccountInfo]
+        accounts: &[AccountInfo],
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        // initialized valid signer account
+        // uninitialized valid signer account
         let valid_signer_info = next_account_info(account_info_iter)?;
         // signer group account
         let signer_group_info = next_account_info(account_info_iter)?;
@@ -317,12 +427,12 @@ impl Processor {
             return Err(AudiusError::InvalidInstruction.into());
         }
 
-        // Verify owner submission
+        // Verify SignerGroupOwner submission of this transaction
         if !signer_groups_owner_info.is_signer {
             return Err(AudiusError::SignatureMissing.into());
         }
 
-        let signer_group = Box::new(SignerGroup::try_from_slice(
+        let signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
@@ -339,59 +449,183 @@ impl Processor {
             &valid_signer_info.data.borrow(),
         )?);
 
-        if !valid_signer.is_initialized() {
-            return Err(AudiusError::ValidSignerNotInitialized.into());
+        if valid_signer.is_initialized() {
+            return Err(AudiusError::SignerAlreadyInitialized.into());
         }
 
-        if valid_signer.signer_group != *signer_group_info.key {
-            return Err(AudiusError::WrongSignerGroup.into());
+        if valid_signer.is_closed() {
+            return Err(AudiusError::AccountClosed.into());
         }
 
         signer_group.check_owner(&signer_groups_owner_info)?;
 
-        valid_signer.version = Self::VALID_SIGNER_UNINITIALIZED_VERSION;
+        // TODO: check if ethereum public key is valid
+
+        valid_signer.version = Self::VALID_SIGNER_VERSION;
+        valid_signer.signer_group = *signer_group_info.key;
+        valid_signer.eth_address = eth_address;
+
+        valid_signer.serialize(&mut *valid_signer_info.data.borrow_mut())?;
+
+        Self::increment_signer_count(signer_group_info)
+    }
+
+    /// Records a valid signer joining the group, legacy groups don't count their signers
+    fn increment_signer_count(signer_group_info: &AccountInfo) -> ProgramResult {
+        if signer_group_info.data_len() == SignerGroup::LEGACY_LEN {
+            return Ok(());
+        }
+
+        let mut signer_group = SignerGroup::unpack(&signer_group_info.data.borrow())?;
+        signer_group.signer_count = signer_group
+            .signer_count
+            .checked_add(1)
+            .ok_or(AudiusError::TooManySigners)?;
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
+
+    /// Records a valid signer leaving the group. A group that reaches its
+    /// threshold can't lose a signer it needs to keep reaching it
+    fn decrement_signer_count(signer_group_info: &AccountInfo) -> ProgramResult {
+        if signer_group_info.data_len() == SignerGroup::LEGACY_LEN {
+            return Ok(());
+        }
+
+        let mut signer_group = SignerGroup::unpack(&signer_group_info.data.borrow())?;
+        if signer_group.signer_count == signer_group.threshold {
+            return Err(AudiusError::ThresholdExceedsSignerCount.into());
+        }
+        signer_group.signer_count = signer_group.signer_count.saturating_sub(1);
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
+
+    /// Creates the valid signer of `signer_group` for `eth_address` at its derived address
+    fn create_valid_signer_account<'a>(
+        program_id: &Pubkey,
+        funder_info: &AccountInfo<'a>,
+        valid_signer_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        signer_group: &Pubkey,
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    ) -> ProgramResult {
+        assert_rent_sysvar(rent_info)?;
+        assert_system_program(system_program_info)?;
+
+        let (valid_signer_address, bump_seed) =
+            find_valid_signer_address(program_id, signer_group, &eth_address);
+        assert_account_key(valid_signer_info, &valid_signer_address)?;
+
+        let signers_seeds = &[
+            VALID_SIGNER_SEED_PREFIX.as_ref(),
+            signer_group.as_ref(),
+            eth_address.as_ref(),
+            &[bump_seed],
+        ];
+
+        // Fails if the group already has a valid signer for the eth address
+        let rent = Rent::from_account_info(rent_info)?;
+        create_derived_account(
+            funder_info,
+            valid_signer_info,
+            ValidSigner::LEN,
+            program_id,
+            &[signers_seeds],
+            &rent,
+        )?;
 
+        let valid_signer = ValidSigner {
+            version: Self::VALID_SIGNER_VERSION,
+            signer_group: *signer_group,
+            eth_address,
+        };
         valid_signer
             .serialize(&mut *valid_signer_info.data.borrow_mut())
             .map_err(|e| e.into())
     }
 
-    /// Process [ValidateMultipleSignaturesClearValidSigner]().
-    pub fn process_multiple_signatures_clear_valid_signer(
+    /// Process [CreateSignerGroup]().
+    pub fn process_create_signer_group(
         _program_id: &Pubkey,
         accounts: &[AccountInfo],
-        signature_data_1: SignatureData,
-        signature_data_2: SignatureData,
-        signature_data_3: SignatureData,
+        seed: Vec<u8>,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        // initialized valid signer account 1
-        let valid_signer_1_info = next_account_info(account_info_iter)?;
-        // initialized valid signer account 2
-        let valid_signer_2_info = next_account_info(account_info_iter)?;
-        // initialized valid signer account 3
-        let valid_signer_3_info = next_account_info(account_info_iter)?;
+        // account paying for the new signer group
+        let funder_info = next_account_info(account_info_iter)?;
+        // signer group account at the derived address
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // signer group owner account
+        let group_owner_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        assert_rent_sysvar(rent_info)?;
+        assert_system_program(system_program_info)?;
+
+        if seed.len() > MAX_SEED_LEN {
+            return Err(ProgramError::MaxSeedLengthExceeded);
+        }
+
+        let (signer_group_address, bump_seed) =
+            find_signer_group_address(_program_id, group_owner_info.key, &seed);
+        assert_account_key(signer_group_info, &signer_group_address)?;
+
+        let signers_seeds = &[
+            SIGNER_GROUP_SEED_PREFIX.as_ref(),
+            group_owner_info.key.as_ref(),
+            seed.as_slice(),
+            &[bump_seed],
+        ];
+
+        let rent = Rent::from_account_info(rent_info)?;
+        create_derived_account(
+            funder_info,
+            signer_group_info,
+            SignerGroup::LEN,
+            _program_id,
+            &[signers_seeds],
+            &rent,
+        )?;
+
+        let signer_group = SignerGroup {
+            version: Self::SIGNER_GROUP_VERSION,
+            owner: *group_owner_info.key,
+            owner_enabled: true,
+            threshold: SignerGroup::DEFAULT_THRESHOLD,
+            pending_owner: Pubkey::default(),
+            signer_count: 0,
+        };
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
+
+    /// Process [CreateValidSigner]().
+    pub fn process_create_valid_signer(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // account paying for the new valid signer
+        let funder_info = next_account_info(account_info_iter)?;
+        // valid signer account at the derived address
+        let valid_signer_info = next_account_info(account_info_iter)?;
         // signer group account
         let signer_group_info = next_account_info(account_info_iter)?;
-        // incoming valid signer account
-        let old_valid_signer_info = next_account_info(account_info_iter)?;
-        // Sysvar Instruction account info
-        let instruction_info = next_account_info(account_info_iter)?;
+        // signer group's owner
+        let signer_groups_owner_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        // Confirm program ownership of SignerGroup and ValidSigners
-        if valid_signer_1_info.owner != _program_id
-            || valid_signer_2_info.owner != _program_id
-            || valid_signer_3_info.owner != _program_id
-            || old_valid_signer_info.owner != _program_id
-            || signer_group_info.owner != _program_id {
+        if signer_group_info.owner != _program_id {
             return Err(AudiusError::InvalidInstruction.into());
         }
 
-        // clock sysvar account
-        let clock_account_info = next_account_info(account_info_iter)?;
-        let clock = Clock::from_account_info(&clock_account_info)?;
-
-        let signer_group = Box::new(SignerGroup::try_from_slice(
+        let signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
@@ -399,83 +633,65 @@ impl Processor {
             return Err(AudiusError::UninitializedSignerGroup.into());
         }
 
-        let valid_signer_acct_array = [valid_signer_1_info, valid_signer_2_info, valid_signer_3_info];
-        let sig_data_array = [&signature_data_1, &signature_data_2, &signature_data_3];
-        Self::validate_signer_data(
-            &instruction_info,
-            &signer_group_info,
-            &valid_signer_acct_array,
-            &sig_data_array
-        )?;
-
-        // Each signature data message is expected to be a recent unix timestamp
-        // If messages do not adhere to this format, the operation will fail
-        let timestamp_result = Self::validate_timestamp_messages(
-            &clock,
-            &signature_data_1.message,
-            &signature_data_2.message,
-            &signature_data_3.message,
-        );
-
-        if timestamp_result.is_err() {
-            return Err(AudiusError::SignatureVerificationFailed.into());
+        // Reject if owner has been disabled
+        if !signer_group.owner_enabled {
+            return Err(AudiusError::SignerGroupOwnerDisabled.into());
         }
 
-        let mut old_valid_signer = Box::new(ValidSigner::try_from_slice(
-            &old_valid_signer_info.data.borrow(),
-        )?);
-
-        if !old_valid_signer.is_initialized() {
-            return Err(AudiusError::ValidSignerNotInitialized.into());
-        }
+        signer_group.check_owner(&signer_groups_owner_info)?;
 
-        if old_valid_signer.signer_group != *signer_group_info.key {
-            return Err(AudiusError::WrongSignerGroup.into());
-        }
+        Self::create_valid_signer_account(
+            _program_id,
+            funder_info,
+            valid_signer_info,
+            rent_info,
+            system_program_info,
+            signer_group_info.key,
+            eth_address,
+        )?;
 
-        old_valid_signer.version = Self::VALID_SIGNER_UNINITIALIZED_VERSION;
-        old_valid_signer
-            .serialize(&mut *old_valid_signer_info.data.borrow_mut())
-            .map_err(|e| e.into())
+        Self::increment_signer_count(signer_group_info)
     }
 
-    /// Process [ValidateMultipleSignaturesAddSigner]()
-    pub fn process_multiple_signatures_add_signer(
+    /// Process [MigrateValidSigner]().
+    pub fn process_migrate_valid_signer(
         _program_id: &Pubkey,
         accounts: &[AccountInfo],
-        signature_data_1: SignatureData,
-        signature_data_2: SignatureData,
-        signature_data_3: SignatureData,
-        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        // initialized valid signer account 1
-        let valid_signer_1_info = next_account_info(account_info_iter)?;
-        // initialized valid signer account 2
-        let valid_signer_2_info = next_account_info(account_info_iter)?;
-        // initialized valid signer account 3
-        let valid_signer_3_info = next_account_info(account_info_iter)?;
+        // account paying for the new valid signer, refunded the old one's rent
+        let funder_info = next_account_info(account_info_iter)?;
+        // initialized valid signer account at a keypair address
+        let old_valid_signer_info = next_account_info(account_info_iter)?;
+        // valid signer account at the derived address
+        let new_valid_signer_info = next_account_info(account_info_iter)?;
         // signer group account
         let signer_group_info = next_account_info(account_info_iter)?;
-        // incoming valid signer account
-        let new_valid_signer_info = next_account_info(account_info_iter)?;
-        // Sysvar Instruction account info
-        let instruction_info = next_account_info(account_info_iter)?;
+        // signer group's owner
+        let signer_groups_owner_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        // Confirm program ownership of SignerGroup and ValidSigners
-        if valid_signer_1_info.owner != _program_id
-            || valid_signer_2_info.owner != _program_id
-            || valid_signer_3_info.owner != _program_id
-            || new_valid_signer_info.owner != _program_id
+        if old_valid_signer_info.owner != _program_id
             || signer_group_info.owner != _program_id {
             return Err(AudiusError::InvalidInstruction.into());
         }
 
-        // clock sysvar account
-        let clock_account_info = next_account_info(account_info_iter)?;
-        let clock = Clock::from_account_info(&clock_account_info)?;
+        let old_valid_signer = Box::new(ValidSigner::try_from_slice(
+            &old_valid_signer_info.data.borrow(),
+        )?);
+
+        if !old_valid_signer.is_initialized() {
+            return Err(AudiusError::ValidSignerNotInitialized.into());
+        }
+
+        if old_valid_signer.signer_group != *signer_group_info.key {
+            return Err(AudiusError::WrongSignerGroup.into());
+        }
 
-        let signer_group = Box::new(SignerGroup::try_from_slice(
+        let signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
@@ -483,66 +699,569 @@ impl Processor {
             return Err(AudiusError::UninitializedSignerGroup.into());
         }
 
-        // Create and write new valid signer
-        let mut new_valid_signer = Box::new(ValidSigner::try_from_slice(
-            &new_valid_signer_info.data.borrow(),
-        )?);
-
-        if new_valid_signer.is_initialized() {
-            return Err(AudiusError::SignerAlreadyInitialized.into());
+        // Reject if owner has been disabled
+        if !signer_group.owner_enabled {
+            return Err(AudiusError::SignerGroupOwnerDisabled.into());
+        }
+
+        // Only the owner decides who pays for and gets refunded the signer's rent
+        signer_group.check_owner(&signer_groups_owner_info)?;
+
+        Self::create_valid_signer_account(
+            _program_id,
+            funder_info,
+            new_valid_signer_info,
+            rent_info,
+            system_program_info,
+            &old_valid_signer.signer_group,
+            old_valid_signer.eth_address,
+        )?;
+
+        close_account(old_valid_signer_info, funder_info)
+    }
+
+    /// Process [MigrateSignerGroup]().
+    pub fn process_migrate_signer_group(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // account paying for the grown signer group
+        let funder_info = next_account_info(account_info_iter)?;
+        // legacy signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // signer group's owner
+        let signer_groups_owner_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
+        // every valid signer of the group
+        let valid_signer_infos: Vec<&AccountInfo> = account_info_iter.collect();
+
+        assert_rent_sysvar(rent_info)?;
+        assert_system_program(system_program_info)?;
+
+        if signer_group_info.owner != _program_id
+            || valid_signer_infos.iter().any(|info| info.owner != _program_id) {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        if signer_group_info.data_len() != SignerGroup::LEGACY_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut signer_group = Box::new(SignerGroup::unpack(
+            &signer_group_info.data.borrow(),
+        )?);
+
+        if !signer_group.is_initialized() {
+            return Err(AudiusError::UninitializedSignerGroup.into());
+        }
+
+        // Reject if owner has been disabled
+        if !signer_group.owner_enabled {
+            return Err(AudiusError::SignerGroupOwnerDisabled.into());
+        }
+
+        signer_group.check_owner(&signer_groups_owner_info)?;
+
+        // Count each valid signer of the group once
+        let mut counted = BTreeSet::new();
+        for valid_signer_info in &valid_signer_infos {
+            if !counted.insert(valid_signer_info.key) {
+                return Err(AudiusError::RepeatedSigner.into());
+            }
+
+            let valid_signer = ValidSigner::try_from_slice(&valid_signer_info.data.borrow())?;
+            if !valid_signer.is_initialized() {
+                return Err(AudiusError::ValidSignerNotInitialized.into());
+            }
+            if valid_signer.signer_group != *signer_group_info.key {
+                return Err(AudiusError::WrongSignerGroup.into());
+            }
+        }
+        if counted.len() > u8::MAX as usize {
+            return Err(AudiusError::TooManySigners.into());
+        }
+        signer_group.signer_count = counted.len() as u8;
+
+        let rent = Rent::from_account_info(rent_info)?;
+        resize_account(signer_group_info, funder_info, SignerGroup::LEN, &rent)?;
+
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
+
+    /// Process [ClearValidSigner]().
+    pub fn process_clear_valid_signer(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo]
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // initialized valid signer account
+        let valid_signer_info = next_account_info(account_info_iter)?;
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // signer group's owner
+        let signer_groups_owner_info = next_account_info(account_info_iter)?;
+        // account to refund the valid signer's rent to
+        let refund_info = next_account_info(account_info_iter)?;
+
+        // Confirm program ownership of SignerGroup and ValidSigner
+        if valid_signer_info.owner != _program_id
+            || signer_group_info.owner != _program_id {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        // Verify owner submission
+        if !signer_groups_owner_info.is_signer {
+            return Err(AudiusError::SignatureMissing.into());
+        }
+
+        let signer_group = Box::new(SignerGroup::unpack(
+            &signer_group_info.data.borrow(),
+        )?);
+
+        if !signer_group.is_initialized() {
+            return Err(AudiusError::UninitializedSignerGroup.into());
+        }
+
+        // Reject if owner has been disabled
+        if !signer_group.owner_enabled {
+            return Err(AudiusError::SignerGroupOwnerDisabled.into());
+        }
+
+        let valid_signer = Box::new(ValidSigner::try_from_slice(
+            &valid_signer_info.data.borrow(),
+        )?);
+
+        if !valid_signer.is_initialized() {
+            return Err(AudiusError::ValidSignerNotInitialized.into());
+        }
+
+        if valid_signer.signer_group != *signer_group_info.key {
+            return Err(AudiusError::WrongSignerGroup.into());
+        }
+
+        signer_group.check_owner(&signer_groups_owner_info)?;
+
+        Self::decrement_signer_count(signer_group_info)?;
+
+        close_account(valid_signer_info, refund_info)
+    }
+
+    /// Validates that `signature_data_array`, one per account in `valid_signer_accounts`,
+    /// approve `action` recently and are signed by at least `threshold` distinct
+    /// valid signers of the group
+    pub fn validate_quorum(
+        program_id: &Pubkey,
+        signer_group_info: &AccountInfo,
+        instruction_info: &AccountInfo,
+        clock_account_info: &AccountInfo,
+        valid_signer_accounts: &[&AccountInfo],
+        signature_data_array: &[&SignatureData],
+        action: &[u8],
+    ) -> Result<SignerGroup, ProgramError> {
+        // Confirm program ownership of SignerGroup and ValidSigners
+        if signer_group_info.owner != program_id
+            || valid_signer_accounts.iter().any(|info| info.owner != program_id) {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        assert_clock_sysvar(clock_account_info)?;
+        let clock = Clock::from_account_info(clock_account_info)?;
+
+        let signer_group = SignerGroup::unpack(&signer_group_info.data.borrow())?;
+
+        if !signer_group.is_initialized() {
+            return Err(AudiusError::UninitializedSignerGroup.into());
+        }
+
+        if valid_signer_accounts.len() != signature_data_array.len() {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        if signature_data_array.len() < signer_group.threshold as usize {
+            return Err(AudiusError::NotEnoughSignatures.into());
         }
 
-        let valid_signer_acct_array = [valid_signer_1_info, valid_signer_2_info, valid_signer_3_info];
-        let sig_data_array = [&signature_data_1, &signature_data_2, &signature_data_3];
         Self::validate_signer_data(
-            &instruction_info,
-            &signer_group_info,
-            &valid_signer_acct_array,
-            &sig_data_array
+            instruction_info,
+            signer_group_info,
+            valid_signer_accounts,
+            signature_data_array,
         )?;
 
-        // Each signature data message is expected to be a recent unix timestamp
+        // Each signature data message is expected to be the action followed by a
+        // recent unix timestamp
         // If messages do not adhere to this format, the operation will fail
-        let timestamp_result = Self::validate_timestamp_messages(
-            &clock,
-            &signature_data_1.message,
-            &signature_data_2.message,
-            &signature_data_3.message,
-        );
-
-        if timestamp_result.is_err() {
+        if Self::validate_action_messages(&clock, action, signature_data_array).is_err() {
             return Err(AudiusError::SignatureVerificationFailed.into());
         }
 
+        Ok(signer_group)
+    }
+
+    /// Adds a valid signer with `eth_address` to the group once a quorum is validated
+    fn add_signer(
+        program_id: &Pubkey,
+        signer_group_info: &AccountInfo,
+        new_valid_signer_info: &AccountInfo,
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    ) -> ProgramResult {
+        if new_valid_signer_info.owner != program_id {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        // Create and write new valid signer
+        let mut new_valid_signer = Box::new(ValidSigner::try_from_slice(
+            &new_valid_signer_info.data.borrow(),
+        )?);
+
+        if new_valid_signer.is_initialized() {
+            return Err(AudiusError::SignerAlreadyInitialized.into());
+        }
+
+        if new_valid_signer.is_closed() {
+            return Err(AudiusError::AccountClosed.into());
+        }
+
         new_valid_signer.version = Self::VALID_SIGNER_VERSION;
         new_valid_signer.signer_group = *signer_group_info.key;
         new_valid_signer.eth_address = eth_address;
-        new_valid_signer
-            .serialize(&mut *new_valid_signer_info.data.borrow_mut())
-            .map_err(|e| e.into())
+        new_valid_signer.serialize(&mut *new_valid_signer_info.data.borrow_mut())?;
+
+        Self::increment_signer_count(signer_group_info)
     }
 
-    /// Process [ValidateSignature]().
-    pub fn process_validate_signature(
+    /// Closes a valid signer of the group once a quorum is validated
+    fn clear_signer(
+        program_id: &Pubkey,
+        signer_group_info: &AccountInfo,
+        old_valid_signer_info: &AccountInfo,
+        refund_info: &AccountInfo,
+    ) -> ProgramResult {
+        if old_valid_signer_info.owner != program_id {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        let old_valid_signer = Box::new(ValidSigner::try_from_slice(
+            &old_valid_signer_info.data.borrow(),
+        )?);
+
+        if !old_valid_signer.is_initialized() {
+            return Err(AudiusError::ValidSignerNotInitialized.into());
+        }
+
+        if old_valid_signer.signer_group != *signer_group_info.key {
+            return Err(AudiusError::WrongSignerGroup.into());
+        }
+
+        Self::decrement_signer_count(signer_group_info)?;
+
+        close_account(old_valid_signer_info, refund_info)
+    }
+
+    /// Process [ValidateMultipleSignaturesClearValidSigner]().
+    pub fn process_multiple_signatures_clear_valid_signer(
         _program_id: &Pubkey,
         accounts: &[AccountInfo],
-        signature_data: SignatureData,
+        signature_data_1: SignatureData,
+        signature_data_2: SignatureData,
+        signature_data_3: SignatureData,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        // initialized valid signer account
-        let valid_signer_info = next_account_info(account_info_iter)?;
+        // initialized valid signer account 1
+        let valid_signer_1_info = next_account_info(account_info_iter)?;
+        // initialized valid signer account 2
+        let valid_signer_2_info = next_account_info(account_info_iter)?;
+        // initialized valid signer account 3
+        let valid_signer_3_info = next_account_info(account_info_iter)?;
         // signer group account
         let signer_group_info = next_account_info(account_info_iter)?;
+        // valid signer account to remove
+        let old_valid_signer_info = next_account_info(account_info_iter)?;
         // Sysvar Instruction account info
         let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // account to refund the removed valid signer's rent to
+        let refund_info = next_account_info(account_info_iter)?;
 
+        let valid_signer_acct_array = [valid_signer_1_info, valid_signer_2_info, valid_signer_3_info];
+        let sig_data_array = [&signature_data_1, &signature_data_2, &signature_data_3];
+        Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &clear_signer_action(signer_group_info.key, old_valid_signer_info.key),
+        )?;
+
+        Self::clear_signer(_program_id, signer_group_info, old_valid_signer_info, refund_info)
+    }
+
+    /// Process [ValidateMultipleSignaturesAddSigner]()
+    pub fn process_multiple_signatures_add_signer(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data_1: SignatureData,
+        signature_data_2: SignatureData,
+        signature_data_3: SignatureData,
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // initialized valid signer account 1
+        let valid_signer_1_info = next_account_info(account_info_iter)?;
+        // initialized valid signer account 2
+        let valid_signer_2_info = next_account_info(account_info_iter)?;
+        // initialized valid signer account 3
+        let valid_signer_3_info = next_account_info(account_info_iter)?;
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // incoming valid signer account
+        let new_valid_signer_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+
+        let valid_signer_acct_array = [valid_signer_1_info, valid_signer_2_info, valid_signer_3_info];
+        let sig_data_array = [&signature_data_1, &signature_data_2, &signature_data_3];
+        Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &add_signer_action(signer_group_info.key, new_valid_signer_info.key, &eth_address),
+        )?;
+
+        Self::add_signer(_program_id, signer_group_info, new_valid_signer_info, eth_address)
+    }
+
+    /// Process [ValidateQuorumAddSigner]()
+    pub fn process_quorum_add_signer(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: Vec<SignatureData>,
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // incoming valid signer account
+        let new_valid_signer_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // initialized valid signer accounts, one per signature
+        let valid_signer_acct_array: Vec<&AccountInfo> = account_info_iter.collect();
+
+        let sig_data_array: Vec<&SignatureData> = signature_data.iter().collect();
+        Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &add_signer_action(signer_group_info.key, new_valid_signer_info.key, &eth_address),
+        )?;
+
+        Self::add_signer(_program_id, signer_group_info, new_valid_signer_info, eth_address)
+    }
+
+    /// Process [ValidateQuorumCreateSigner]()
+    pub fn process_quorum_create_signer(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: Vec<SignatureData>,
+        eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // valid signer account at the derived address
+        let new_valid_signer_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // account paying for the new valid signer
+        let funder_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
+        // initialized valid signer accounts, one per signature
+        let valid_signer_acct_array: Vec<&AccountInfo> = account_info_iter.collect();
+
+        let sig_data_array: Vec<&SignatureData> = signature_data.iter().collect();
+        Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &add_signer_action(signer_group_info.key, new_valid_signer_info.key, &eth_address),
+        )?;
+
+        Self::create_valid_signer_account(
+            _program_id,
+            funder_info,
+            new_valid_signer_info,
+            rent_info,
+            system_program_info,
+            signer_group_info.key,
+            eth_address,
+        )?;
+
+        Self::increment_signer_count(signer_group_info)
+    }
+
+    /// Process [ValidateQuorumClearValidSigner]()
+    pub fn process_quorum_clear_valid_signer(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: Vec<SignatureData>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // valid signer account to remove
+        let old_valid_signer_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // account to refund the removed valid signer's rent to
+        let refund_info = next_account_info(account_info_iter)?;
+        // initialized valid signer accounts, one per signature
+        let valid_signer_acct_array: Vec<&AccountInfo> = account_info_iter.collect();
+
+        let sig_data_array: Vec<&SignatureData> = signature_data.iter().collect();
+        Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &clear_signer_action(signer_group_info.key, old_valid_signer_info.key),
+        )?;
+
+        Self::clear_signer(_program_id, signer_group_info, old_valid_signer_info, refund_info)
+    }
+
+    /// Process [SetThreshold]().
+    pub fn process_set_threshold(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        threshold: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // signer group's owner
+        let signer_groups_owner_info = next_account_info(account_info_iter)?;
+
+        if signer_group_info.owner != _program_id {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        let mut signer_group = Box::new(SignerGroup::unpack(
+            &signer_group_info.data.borrow(),
+        )?);
+
+        if !signer_group.is_initialized() {
+            return Err(AudiusError::UninitializedSignerGroup.into());
+        }
+
+        // Reject if owner has been disabled
+        if !signer_group.owner_enabled {
+            return Err(AudiusError::SignerGroupOwnerDisabled.into());
+        }
+
+        signer_group.check_owner(&signer_groups_owner_info)?;
+
+        Self::set_threshold(signer_group_info, &mut signer_group, threshold)
+    }
+
+    /// Process [ValidateQuorumSetThreshold]().
+    pub fn process_quorum_set_threshold(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: Vec<SignatureData>,
+        threshold: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // initialized valid signer accounts, one per signature
+        let valid_signer_acct_array: Vec<&AccountInfo> = account_info_iter.collect();
+
+        let sig_data_array: Vec<&SignatureData> = signature_data.iter().collect();
+        let mut signer_group = Self::validate_quorum(
+            _program_id,
+            signer_group_info,
+            instruction_info,
+            clock_account_info,
+            &valid_signer_acct_array,
+            &sig_data_array,
+            &set_threshold_action(signer_group_info.key, threshold),
+        )?;
+
+        Self::set_threshold(signer_group_info, &mut signer_group, threshold)
+    }
+
+    fn set_threshold(
+        signer_group_info: &AccountInfo,
+        signer_group: &mut SignerGroup,
+        threshold: u8,
+    ) -> ProgramResult {
+        if threshold == 0 {
+            return Err(AudiusError::InvalidThreshold.into());
+        }
+
+        // Legacy groups can't store a threshold, whatever their signers
+        if signer_group_info.data_len() == SignerGroup::LEGACY_LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        if threshold > signer_group.signer_count {
+            return Err(AudiusError::ThresholdExceedsSignerCount.into());
+        }
+
+        signer_group.threshold = threshold;
+        signer_group.pack(&mut signer_group_info.data.borrow_mut())
+    }
+
+    /// Checks `signature_data` was signed by the initialized `valid_signer_info`
+    /// of `signer_group_info`, returning the valid signer
+    fn check_signature(
+        program_id: &Pubkey,
+        valid_signer_info: &AccountInfo,
+        signer_group_info: &AccountInfo,
+        instruction_info: &AccountInfo,
+        signature_data: &SignatureData,
+    ) -> Result<ValidSigner, ProgramError> {
         // Confirm program ownership of SignerGroup and ValidSigner
-        if valid_signer_info.owner != _program_id
-            || signer_group_info.owner != _program_id {
+        if valid_signer_info.owner != program_id
+            || signer_group_info.owner != program_id {
             return Err(AudiusError::InvalidInstruction.into());
         }
 
-        let signer_group = Box::new(SignerGroup::try_from_slice(
+        let signer_group = Box::new(SignerGroup::unpack(
             &signer_group_info.data.borrow(),
         )?);
 
@@ -551,7 +1270,7 @@ impl Processor {
         }
 
         let valid_signer_acct_array = [valid_signer_info];
-        let sig_data_array = [&signature_data];
+        let sig_data_array = [signature_data];
         Self::validate_signer_data(
             &instruction_info,
             &signer_group_info,
@@ -559,9 +1278,163 @@ impl Processor {
             &sig_data_array
         )?;
 
+        ValidSigner::try_from_slice(&valid_signer_info.data.borrow()).map_err(|e| e.into())
+    }
+
+    /// Process [ValidateSignature]().
+    pub fn process_validate_signature(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: SignatureData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // initialized valid signer account
+        let valid_signer_info = next_account_info(account_info_iter)?;
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+
+        Self::check_signature(
+            _program_id,
+            valid_signer_info,
+            signer_group_info,
+            instruction_info,
+            &signature_data,
+        )?;
+
         Ok(())
     }
 
+    /// Process [ValidateSignatureOnce]().
+    pub fn process_validate_signature_once(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature_data: SignatureData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // initialized valid signer account
+        let valid_signer_info = next_account_info(account_info_iter)?;
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // Sysvar Instruction account info
+        let instruction_info = next_account_info(account_info_iter)?;
+        // account paying for the used message account
+        let funder_info = next_account_info(account_info_iter)?;
+        // used message account at the derived address
+        let used_message_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+
+        let valid_signer = Self::check_signature(
+            _program_id,
+            valid_signer_info,
+            signer_group_info,
+            instruction_info,
+            &signature_data,
+        )?;
+
+        assert_rent_sysvar(rent_info)?;
+        assert_system_program(system_program_info)?;
+        assert_clock_sysvar(clock_account_info)?;
+
+        // Only recent messages are accepted, so the used message account
+        // can be closed once the message expires
+        let clock = Clock::from_account_info(clock_account_info)?;
+        let timestamp = Self::recent_message_timestamp(&clock, &signature_data.message)?;
+
+        let (used_message_address, bump_seed) = find_used_message_address(
+            _program_id,
+            signer_group_info.key,
+            &valid_signer.eth_address,
+            &signature_data.message,
+        );
+        assert_account_key(used_message_info, &used_message_address)?;
+
+        // The account is only owned by the program once the message has been
+        // used, lamports sent to the address beforehand don't count as a use
+        if used_message_info.owner == _program_id {
+            return Err(AudiusError::MessageAlreadyUsed.into());
+        }
+
+        let message_hash = hash(&signature_data.message);
+        let signers_seeds = &[
+            USED_MESSAGE_SEED_PREFIX.as_ref(),
+            signer_group_info.key.as_ref(),
+            valid_signer.eth_address.as_ref(),
+            message_hash.as_ref(),
+            &[bump_seed],
+        ];
+
+        let rent = Rent::from_account_info(rent_info)?;
+        create_derived_account(
+            funder_info,
+            used_message_info,
+            UsedMessage::LEN,
+            _program_id,
+            &[signers_seeds],
+            &rent,
+        )?;
+
+        UsedMessage {
+            version: Self::USED_MESSAGE_VERSION,
+            funder: *funder_info.key,
+            timestamp,
+        }
+        .serialize(&mut *used_message_info.data.borrow_mut())
+        .map_err(|e| e.into())
+    }
+
+    /// Process [CloseUsedMessage]().
+    pub fn process_close_used_message(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // used message account to close
+        let used_message_info = next_account_info(account_info_iter)?;
+        // account that funded the used message account
+        let funder_info = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+
+        // Confirm program ownership of UsedMessage
+        if used_message_info.owner != _program_id {
+            return Err(AudiusError::InvalidInstruction.into());
+        }
+
+        assert_clock_sysvar(clock_account_info)?;
+
+        // Accounts created before used messages were timestamped are empty
+        if used_message_info.data_len() != UsedMessage::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let used_message = Box::new(UsedMessage::try_from_slice(
+            &used_message_info.data.borrow(),
+        )?);
+
+        if !used_message.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if used_message.funder != *funder_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Once expired the message can't be validated again
+        let clock = Clock::from_account_info(clock_account_info)?;
+        if clock.unix_timestamp - used_message.timestamp <= MAX_TIME_DIFF_SECONDS {
+            return Err(AudiusError::MessageNotExpired.into());
+        }
+
+        close_account(used_message_info, funder_info)
+    }
+
     /// Process an [Instruction]().
     pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
         let instruction = AudiusInstruction::try_from_slice(input)?;
@@ -628,6 +1501,62 @@ impl Processor {
                     eth_pubkey,
                 )
             }
+            AudiusInstruction::SetThreshold(threshold) => {
+                msg!("Instruction: SetThreshold");
+                Self::process_set_threshold(_program_id, accounts, threshold)
+            }
+            AudiusInstruction::ValidateQuorumAddSigner(signatures, eth_pubkey) => {
+                msg!("Instruction: ValidateQuorumAddSigner");
+                Self::process_quorum_add_signer(_program_id, accounts, signatures, eth_pubkey)
+            }
+            AudiusInstruction::ValidateQuorumClearValidSigner(signatures) => {
+                msg!("Instruction: ValidateQuorumClearValidSigner");
+                Self::process_quorum_clear_valid_signer(_program_id, accounts, signatures)
+            }
+            AudiusInstruction::ValidateQuorumSetThreshold(signatures, threshold) => {
+                msg!("Instruction: ValidateQuorumSetThreshold");
+                Self::process_quorum_set_threshold(_program_id, accounts, signatures, threshold)
+            }
+            AudiusInstruction::TransferSignerGroupOwnership => {
+                msg!("Instruction: TransferSignerGroupOwnership");
+                Self::process_transfer_signer_group_ownership(_program_id, accounts)
+            }
+            AudiusInstruction::AcceptSignerGroupOwnership => {
+                msg!("Instruction: AcceptSignerGroupOwnership");
+                Self::process_accept_signer_group_ownership(_program_id, accounts)
+            }
+            AudiusInstruction::EnableSignerGroupOwner(signatures) => {
+                msg!("Instruction: EnableSignerGroupOwner");
+                Self::process_enable_signer_group_owner(_program_id, accounts, signatures)
+            }
+            AudiusInstruction::CreateSignerGroup(seed) => {
+                msg!("Instruction: CreateSignerGroup");
+                Self::process_create_signer_group(_program_id, accounts, seed)
+            }
+            AudiusInstruction::CreateValidSigner(eth_pubkey) => {
+                msg!("Instruction: CreateValidSigner");
+                Self::process_create_valid_signer(_program_id, accounts, eth_pubkey)
+            }
+            AudiusInstruction::ValidateQuorumCreateSigner(signatures, eth_pubkey) => {
+                msg!("Instruction: ValidateQuorumCreateSigner");
+                Self::process_quorum_create_signer(_program_id, accounts, signatures, eth_pubkey)
+            }
+            AudiusInstruction::MigrateValidSigner => {
+                msg!("Instruction: MigrateValidSigner");
+                Self::process_migrate_valid_signer(_program_id, accounts)
+            }
+            AudiusInstruction::ValidateSignatureOnce(signature) => {
+                msg!("Instruction: ValidateSignatureOnce");
+                Self::process_validate_signature_once(_program_id, accounts, signature)
+            }
+            AudiusInstruction::MigrateSignerGroup => {
+                msg!("Instruction: MigrateSignerGroup");
+                Self::process_migrate_signer_group(_program_id, accounts)
+            }
+            AudiusInstruction::CloseUsedMessage => {
+                msg!("Instruction: CloseUsedMessage");
+                Self::process_close_used_message(_program_id, accounts)
+            }
         }
     }
 }
@@ -649,6 +1578,16 @@ impl PrintProgramError for AudiusError {
             AudiusError::SignatureVerificationFailed => msg!("Signature verification failed"),
             AudiusError::Secp256InstructionLosing => msg!("Secp256 instruction losing"),
             AudiusError::SignerGroupOwnerDisabled => msg!("Signer group owner disabled"),
+            AudiusError::AccountClosed => msg!("Account closed"),
+            AudiusError::NotEnoughSignatures => msg!("Not enough signatures"),
+            AudiusError::InvalidThreshold => msg!("Invalid threshold"),
+            AudiusError::RepeatedSigner => msg!("Repeated valid signer"),
+            AudiusError::SignCollision => msg!("Signature collision"),
+            AudiusError::NoPendingOwner => msg!("No pending owner"),
+            AudiusError::MessageAlreadyUsed => msg!("Message already used"),
+            AudiusError::ThresholdExceedsSignerCount => msg!("Threshold exceeds signer count"),
+            AudiusError::TooManySigners => msg!("Too many signers"),
+            AudiusError::MessageNotExpired => msg!("Message not expired"),
         }
     }
 }
@@ -1,10 +1,25 @@
 //! State transition types
 
 use crate::error::AudiusError;
+pub use audius_secp_utils::{SecpSignature, CLOSED_ACCOUNT_DISCRIMINATOR};
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, clock::UnixTimestamp, program_error::ProgramError, pubkey::Pubkey,
+};
 use std::mem::size_of;
 
+/// Add valid signer message prefix
+pub const ADD_SIGNER_MESSAGE_PREFIX: &str = "add_signer";
+
+/// Clear valid signer message prefix
+pub const CLEAR_SIGNER_MESSAGE_PREFIX: &str = "clear_signer";
+
+/// Set threshold message prefix
+pub const SET_THRESHOLD_MESSAGE_PREFIX: &str = "set_threshold";
+
+/// Enable signer group owner message prefix
+pub const ENABLE_OWNER_MESSAGE_PREFIX: &str = "enable_owner";
+
 /// Signer group data
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
@@ -15,6 +30,12 @@ pub struct SignerGroup {
     pub owner: Pubkey,
     /// Boolean indicating whether the signer group owner is active
     pub owner_enabled: bool,
+    /// Number of valid signer signatures required to change the group
+    pub threshold: u8,
+    /// Owner proposed by the current one, default when there is no proposal
+    pub pending_owner: Pubkey,
+    /// Number of valid signers in the group, legacy groups don't count them
+    pub signer_count: u8,
 }
 
 /// Valid signer data
@@ -29,6 +50,18 @@ pub struct ValidSigner {
     pub eth_address: [u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE],
 }
 
+/// Used message data, kept until the message's timestamp expires
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct UsedMessage {
+    /// Used message version
+    pub version: u8,
+    /// Account that paid for the used message account and gets its lamports back
+    pub funder: Pubkey,
+    /// Unix timestamp ending the used message
+    pub timestamp: UnixTimestamp,
+}
+
 /// Secp256k1 signature offsets data
 #[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
 pub struct SecpSignatureOffsets {
@@ -52,6 +85,46 @@ impl SignerGroup {
     /// Length of SignerGroup when serialized
     pub const LEN: usize = size_of::<SignerGroup>();
 
+    /// Length of SignerGroup created before the threshold, pending owner and
+    /// signer count were stored
+    // 1 + 32 + 1
+    pub const LEGACY_LEN: usize = 34;
+
+    /// Threshold of new groups and of groups without a stored threshold
+    pub const DEFAULT_THRESHOLD: u8 = 3;
+
+    /// Unpack SignerGroup of either length
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() == Self::LEGACY_LEN {
+            let mut data = [0; Self::LEN];
+            data[..Self::LEGACY_LEN].copy_from_slice(src);
+            data[Self::LEGACY_LEN] = Self::DEFAULT_THRESHOLD;
+            return Self::try_from_slice(&data).map_err(|_| ProgramError::InvalidAccountData);
+        }
+        Self::try_from_slice(src).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Pack SignerGroup into `dst`, legacy accounts can only keep the default
+    /// threshold, no pending owner and no signer count
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let data = self.try_to_vec()?;
+        if dst.len() == Self::LEGACY_LEN {
+            if self.threshold != Self::DEFAULT_THRESHOLD
+                || self.pending_owner != Pubkey::default()
+                || self.signer_count != 0
+            {
+                return Err(ProgramError::AccountDataTooSmall);
+            }
+            dst.copy_from_slice(&data[..Self::LEGACY_LEN]);
+            return Ok(());
+        }
+        if dst.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        dst.copy_from_slice(&data);
+        Ok(())
+    }
+
     /// Check if SignerGroup is initialized
     pub fn is_initialized(&self) -> bool {
         self.version != 0
@@ -75,7 +148,23 @@ impl ValidSigner {
 
     /// Check if ValidSigner is initialized
     pub fn is_initialized(&self) -> bool {
-        self.version != 0
+        self.version != 0 && !self.is_closed()
+    }
+
+    /// Check if ValidSigner was closed earlier in the transaction
+    pub fn is_closed(&self) -> bool {
+        self.version == CLOSED_ACCOUNT_DISCRIMINATOR
+    }
+}
+
+impl UsedMessage {
+    /// Length of UsedMessage when serialized
+    // 1 + 32 + 8
+    pub const LEN: usize = 41;
+
+    /// Check if UsedMessage is initialized
+    pub fn is_initialized(&self) -> bool {
+        self.version != 0 && self.version != CLOSED_ACCOUNT_DISCRIMINATOR
     }
 }
 
@@ -102,7 +191,10 @@ mod test {
         let signer_group = SignerGroup {
             version: 0,
             owner: Pubkey::new_from_array([1; 32]),
-            owner_enabled: true
+            owner_enabled: true,
+            threshold: 2,
+            pending_owner: Pubkey::new_from_array([2; 32]),
+            signer_count: 4,
         };
 
         let packed = signer_group.try_to_vec().unwrap();
@@ -114,6 +206,52 @@ mod test {
         assert_eq!(signer_group.is_initialized(), false);
     }
 
+    #[test]
+    fn test_legacy_signer_group() {
+        let mut signer_group = SignerGroup {
+            version: 1,
+            owner: Pubkey::new_from_array([1; 32]),
+            owner_enabled: true,
+            threshold: SignerGroup::DEFAULT_THRESHOLD,
+            pending_owner: Pubkey::default(),
+            signer_count: 0,
+        };
+
+        let mut data = [0; SignerGroup::LEGACY_LEN];
+        signer_group.pack(&mut data).unwrap();
+
+        assert_eq!(SignerGroup::unpack(&data).unwrap(), signer_group);
+
+        signer_group.threshold = 2;
+        assert_eq!(
+            signer_group.pack(&mut data),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+
+        signer_group.threshold = SignerGroup::DEFAULT_THRESHOLD;
+        signer_group.pending_owner = Pubkey::new_from_array([2; 32]);
+        assert_eq!(
+            signer_group.pack(&mut data),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+
+        signer_group.pending_owner = Pubkey::default();
+        signer_group.signer_count = 1;
+        assert_eq!(
+            signer_group.pack(&mut data),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_signer_group_len() {
+        assert_eq!(SignerGroup::LEN, 68);
+        assert_eq!(
+            SignerGroup::default().try_to_vec().unwrap().len(),
+            SignerGroup::LEN
+        );
+    }
+
     #[test]
     fn test_valid_signer() {
         let valid_signer = ValidSigner {
@@ -131,6 +269,35 @@ mod test {
         assert_eq!(valid_signer.is_initialized(), true);
     }
 
+    #[test]
+    fn test_closed_valid_signer() {
+        let mut data = [0; ValidSigner::LEN];
+        data[0] = CLOSED_ACCOUNT_DISCRIMINATOR;
+
+        let valid_signer = ValidSigner::try_from_slice(&data).unwrap();
+
+        assert_eq!(valid_signer.is_closed(), true);
+        assert_eq!(valid_signer.is_initialized(), false);
+    }
+
+    #[test]
+    fn test_used_message() {
+        let used_message = UsedMessage {
+            version: 1,
+            funder: Pubkey::new_from_array([1; 32]),
+            timestamp: 1_600_000_000,
+        };
+
+        let packed = used_message.try_to_vec().unwrap();
+        assert_eq!(packed.len(), UsedMessage::LEN);
+
+        let unpacked = UsedMessage::try_from_slice(packed.as_slice()).unwrap();
+
+        assert_eq!(used_message, unpacked);
+
+        assert_eq!(used_message.is_initialized(), true);
+    }
+
     #[test]
     fn test_offsets_pack_unpack() {
         let offsets = SecpSignatureOffsets {
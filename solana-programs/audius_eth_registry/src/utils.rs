@@ -0,0 +1,14 @@
+//! Account key checks
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Assert `account_info`'s key matches `key`
+pub fn assert_account_key(account_info: &AccountInfo, key: &Pubkey) -> ProgramResult {
+    if *account_info.key != *key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
@@ -1,19 +1,24 @@
 #![cfg(feature = "test-bpf")]
 
 use audius_eth_registry::*;
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use rand::rngs::ThreadRng;
 use rand::{thread_rng, Rng};
 use libsecp256k1::{PublicKey, SecretKey};
 use sha3::Digest;
-use solana_program::{hash::Hash, instruction::Instruction, pubkey::Pubkey, system_instruction};
+use solana_program::{
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    secp256k1_program, system_instruction,
+};
 
 use solana_program_test::*;
 use solana_sdk::{
     account::Account,
     secp256k1_instruction,
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
     transport::TransportError,
 };
 
@@ -147,6 +152,68 @@ async fn process_tx_init_valid_signer(
     Ok(())
 }
 
+async fn process_tx_set_threshold(
+    signer_group: &Pubkey,
+    group_owner: &Keypair,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    banks_client: &mut BanksClient,
+    threshold: u8,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::set_threshold(&id(), signer_group, &group_owner.pubkey(), threshold)
+                .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[payer, group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+async fn process_tx_transfer_signer_group_ownership(
+    signer_group: &Pubkey,
+    group_owner: &Keypair,
+    proposed_owner: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    banks_client: &mut BanksClient,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::transfer_signer_group_ownership(
+            &id(),
+            signer_group,
+            &group_owner.pubkey(),
+            proposed_owner,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[payer, group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+async fn process_tx_accept_signer_group_ownership(
+    signer_group: &Pubkey,
+    pending_owner: &Keypair,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    banks_client: &mut BanksClient,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::accept_signer_group_ownership(&id(), signer_group, &pending_owner.pubkey())
+                .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[payer, pending_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 fn construct_eth_address(
     pubkey: &PublicKey,
 ) -> [u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE] {
@@ -156,18 +223,25 @@ fn construct_eth_address(
     addr
 }
 
+/// Signs `message`, the returned secp instruction must be at `instruction_index`
+/// of the transaction since its offsets point into it
 fn construct_signature_data(
     priv_key_raw: &[u8; 32],
     message: &[u8],
+    instruction_index: u8,
 ) -> (instruction::SignatureData, Instruction) {
     let priv_key = SecretKey::parse(priv_key_raw).unwrap();
-    let secp256_program_instruction =
+    let mut secp256_program_instruction =
         secp256k1_instruction::new_secp256k1_instruction(&priv_key, message);
     let start = 1;
     let end = start + state::SecpSignatureOffsets::SIGNATURE_OFFSETS_SERIALIZED_SIZE;
-    let offsets =
+    let mut offsets =
         state::SecpSignatureOffsets::try_from_slice(&secp256_program_instruction.data[start..end])
             .unwrap();
+    offsets.signature_instruction_index = instruction_index;
+    offsets.eth_address_instruction_index = instruction_index;
+    offsets.message_instruction_index = instruction_index;
+    secp256_program_instruction.data[start..end].copy_from_slice(&offsets.try_to_vec().unwrap());
 
     let sig_start = offsets.signature_offset as usize;
     let sig_end = sig_start + state::SecpSignatureOffsets::SECP_SIGNATURE_SIZE;
@@ -181,6 +255,93 @@ fn construct_signature_data(
     return (signature_data, secp256_program_instruction);
 }
 
+/// Signs `message` with every key in a single secp instruction,
+/// which must be at `instruction_index` of the transaction
+fn construct_multiple_signatures_data(
+    priv_keys_raw: &[[u8; 32]],
+    message: &[u8],
+    instruction_index: u8,
+) -> (Vec<instruction::SignatureData>, Instruction) {
+    let offsets_size = state::SecpSignatureOffsets::SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let signature_size = state::SecpSignatureOffsets::ETH_ADDRESS_SIZE
+        + state::SecpSignatureOffsets::SECP_SIGNATURE_SIZE
+        + 1
+        + message.len();
+    let data_start = 1 + priv_keys_raw.len() * offsets_size;
+    let mut data = vec![0u8; data_start + priv_keys_raw.len() * signature_size];
+    data[0] = priv_keys_raw.len() as u8;
+
+    let message_hash =
+        libsecp256k1::Message::parse_slice(&sha3::Keccak256::digest(message)).unwrap();
+    let mut signatures_data = Vec::new();
+    for (i, priv_key_raw) in priv_keys_raw.iter().enumerate() {
+        let priv_key = SecretKey::parse(priv_key_raw).unwrap();
+        let eth_address = construct_eth_address(&PublicKey::from_secret_key(&priv_key));
+        let (signature, recovery_id) = libsecp256k1::sign(&message_hash, &priv_key);
+
+        let eth_address_offset = data_start + i * signature_size;
+        let signature_offset = eth_address_offset + eth_address.len();
+        let recovery_id_offset =
+            signature_offset + state::SecpSignatureOffsets::SECP_SIGNATURE_SIZE;
+        let message_data_offset = recovery_id_offset + 1;
+        data[eth_address_offset..signature_offset].copy_from_slice(&eth_address);
+        data[signature_offset..recovery_id_offset].copy_from_slice(&signature.serialize());
+        data[recovery_id_offset] = recovery_id.serialize();
+        data[message_data_offset..message_data_offset + message.len()].copy_from_slice(message);
+
+        let offsets = state::SecpSignatureOffsets {
+            signature_offset: signature_offset as u16,
+            signature_instruction_index: instruction_index,
+            eth_address_offset: eth_address_offset as u16,
+            eth_address_instruction_index: instruction_index,
+            message_data_offset: message_data_offset as u16,
+            message_data_size: message.len() as u16,
+            message_instruction_index: instruction_index,
+        };
+        let start = 1 + i * offsets_size;
+        data[start..start + offsets_size].copy_from_slice(&offsets.try_to_vec().unwrap());
+
+        signatures_data.push(instruction::SignatureData {
+            recovery_id: recovery_id.serialize(),
+            message: message.to_vec(),
+        });
+    }
+
+    let secp256_program_instruction = Instruction {
+        program_id: secp256k1_program::id(),
+        accounts: vec![],
+        data,
+    };
+    (signatures_data, secp256_program_instruction)
+}
+
+async fn assert_spoofed_account_rejected(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    mut instructions: Vec<Instruction>,
+    position: usize,
+) {
+    // Replace the account with a random one in the last instruction
+    let instruction_index = instructions.len() - 1;
+    instructions[instruction_index].accounts[position].pubkey = Pubkey::new_unique();
+
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.sign(&[payer], recent_blockhash);
+
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            instruction_index as u8,
+            InstructionError::InvalidArgument
+        )
+    );
+}
+
 async fn create_valid_signer(
     rng: &mut ThreadRng,
     banks_client: &mut BanksClient,
@@ -245,6 +406,7 @@ async fn init_signer_group() {
 
     assert!(signer_group_data.is_initialized());
     assert_eq!(signer_group_data.owner, group_owner.pubkey());
+    assert_eq!(signer_group_data.threshold, state::SignerGroup::DEFAULT_THRESHOLD);
 }
 
 #[tokio::test]
@@ -430,12 +592,18 @@ async fn clear_valid_signer() {
     .await
     .unwrap();
 
+    let valid_signer_lamports = get_account(&mut banks_client, &valid_signer.pubkey())
+        .await
+        .lamports;
+    let refund = Pubkey::new_unique();
+
     let mut transaction = Transaction::new_with_payer(
         &[instruction::clear_valid_signer(
             &id(),
             &valid_signer.pubkey(),
             &signer_group.pubkey(),
             &group_owner.pubkey(),
+            &refund,
         )
         .unwrap()],
         Some(&payer.pubkey()),
@@ -443,12 +611,92 @@ async fn clear_valid_signer() {
     transaction.sign(&[&payer, &group_owner], recent_blockhash);
     banks_client.process_transaction(transaction).await.unwrap();
 
-    let valid_signer_account = get_account(&mut banks_client, &valid_signer.pubkey()).await;
+    assert!(banks_client
+        .get_account(valid_signer.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        banks_client.get_balance(refund).await.unwrap(),
+        valid_signer_lamports
+    );
+}
 
-    let valid_signer_data =
-        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
+#[tokio::test]
+async fn clear_and_reinit_valid_signer_in_one_transaction() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let valid_signer = Keypair::new();
+
+    create_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &valid_signer,
+        state::ValidSigner::LEN,
+    )
+    .await
+    .unwrap();
+
+    let eth_address = [1u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE];
+    process_tx_init_valid_signer(
+        &valid_signer.pubkey(),
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        eth_address,
+    )
+    .await
+    .unwrap();
 
-    assert_eq!(valid_signer_data.is_initialized(), false);
+    // The cleared account still exists until the end of the transaction
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::clear_valid_signer(
+                &id(),
+                &valid_signer.pubkey(),
+                &signer_group.pubkey(),
+                &group_owner.pubkey(),
+                &payer.pubkey(),
+            )
+            .unwrap(),
+            instruction::init_valid_signer(
+                &id(),
+                &valid_signer.pubkey(),
+                &signer_group.pubkey(),
+                &group_owner.pubkey(),
+                [2u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::AccountClosed as u32)
+        )
+    );
 }
 
 #[tokio::test]
@@ -508,16 +756,18 @@ async fn validate_3_signatures_clear_valid_signer() {
     )
     .await;
 
-    // Shared timestamp message
-    let message_timestamp = Utc::now().timestamp();
-    let message = message_timestamp.to_le_bytes();
+    // Shared message approving the removal
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signer.pubkey()),
+        Utc::now().timestamp(),
+    );
 
     let (signature_data_1, secp256_program_instruction_1) =
-        construct_signature_data(&key_1, &message);
+        construct_signature_data(&key_1, &message, 0);
     let (signature_data_2, secp256_program_instruction_2) =
-        construct_signature_data(&key_2, &message);
+        construct_signature_data(&key_2, &message, 1);
     let (signature_data_3, secp256_program_instruction_3) =
-        construct_signature_data(&key_3, &message);
+        construct_signature_data(&key_3, &message, 2);
 
     let mut transaction = Transaction::new_with_payer(
         &[
@@ -531,6 +781,7 @@ async fn validate_3_signatures_clear_valid_signer() {
                 &valid_signer_3.pubkey(),
                 &signer_group.pubkey(),
                 &valid_signer.pubkey(),
+                &payer.pubkey(),
                 signature_data_1.clone(),
                 signature_data_2.clone(),
                 signature_data_3.clone(),
@@ -542,12 +793,11 @@ async fn validate_3_signatures_clear_valid_signer() {
     transaction.sign(&[&payer], recent_blockhash);
     banks_client.process_transaction(transaction).await.unwrap();
 
-    let valid_signer_account = get_account(&mut banks_client, &valid_signer.pubkey()).await;
-
-    let valid_signer_data =
-        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
-
-    assert_eq!(valid_signer_data.is_initialized(), false);
+    assert!(banks_client
+        .get_account(valid_signer.pubkey())
+        .await
+        .unwrap()
+        .is_none());
 }
 
 #[tokio::test]
@@ -559,7 +809,7 @@ async fn validate_signature() {
     let eth_address = construct_eth_address(&secp_pubkey);
     let message = [8u8; 30];
 
-    let (signature_data, secp256_program_instruction) = construct_signature_data(&key, &message);
+    let (signature_data, secp256_program_instruction) = construct_signature_data(&key, &message, 0);
     let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
 
     process_tx_init_signer_group(
@@ -743,19 +993,6 @@ async fn validate_3_signatures_add_new_valid_signer() {
     )
     .await;
 
-    // Shared message
-    let message_timestamp = Utc::now().timestamp();
-    // let message_timestamp = (Utc.ymd(2014, 7, 8).and_hms(9, 10, 11)).timestamp(); // `2014-07-08T09:10:11Z`
-
-    let message = message_timestamp.to_le_bytes();
-    // Old timestamp for testing
-    let (signature_data_1, secp256_program_instruction_1) =
-        construct_signature_data(&key_1, &message);
-    let (signature_data_2, secp256_program_instruction_2) =
-        construct_signature_data(&key_2, &message);
-    let (signature_data_3, secp256_program_instruction_3) =
-        construct_signature_data(&key_3, &message);
-
     // Initialize incoming valid signer data
     let new_valid_signer = Keypair::new();
     create_account(
@@ -793,6 +1030,22 @@ async fn validate_3_signatures_add_new_valid_signer() {
     let new_secp_pubkey = PublicKey::from_secret_key(&new_priv_key);
     let new_eth_address = construct_eth_address(&new_secp_pubkey);
 
+    // Shared message approving the new signer
+    let message = instruction::action_message(
+        &instruction::add_signer_action(
+            &signer_group.pubkey(),
+            &new_valid_signer.pubkey(),
+            &new_eth_address,
+        ),
+        Utc::now().timestamp(),
+    );
+    let (signature_data_1, secp256_program_instruction_1) =
+        construct_signature_data(&key_1, &message, 0);
+    let (signature_data_2, secp256_program_instruction_2) =
+        construct_signature_data(&key_2, &message, 1);
+    let (signature_data_3, secp256_program_instruction_3) =
+        construct_signature_data(&key_3, &message, 2);
+
     // Execute multiple transactions
     let mut transaction = Transaction::new_with_payer(
         &[
@@ -825,3 +1078,1770 @@ async fn validate_3_signatures_add_new_valid_signer() {
     assert_eq!(new_valid_signer_data.eth_address, new_eth_address);
     assert_eq!(new_valid_signer_data.signer_group, signer_group.pubkey());
 }
+
+#[tokio::test]
+async fn validate_signature_with_spoofed_sysvar() {
+    let mut rng = thread_rng();
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let (key, _priv_key, _secp_pubkey, _eth_address, valid_signer) = create_valid_signer(
+        &mut rng,
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &group_owner,
+    )
+    .await;
+
+    let (signature_data, secp256_program_instruction) =
+        construct_signature_data(&key, &[8u8; 30], 0);
+
+    // Sysvar instruction account
+    assert_spoofed_account_rejected(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        vec![
+            secp256_program_instruction,
+            instruction::validate_signature(
+                &id(),
+                &valid_signer.pubkey(),
+                &signer_group.pubkey(),
+                signature_data,
+            )
+            .unwrap(),
+        ],
+        2,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn validate_3_signatures_with_spoofed_sysvars() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = thread_rng();
+    let mut keys = Vec::new();
+    let mut valid_signers = Vec::new();
+    for _ in 0..3 {
+        let (key, _priv_key, _secp_pubkey, _eth_address, valid_signer) = create_valid_signer(
+            &mut rng,
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &signer_group.pubkey(),
+            &group_owner,
+        )
+        .await;
+        keys.push(key);
+        valid_signers.push(valid_signer.pubkey());
+    }
+
+    let new_valid_signer = Keypair::new();
+    create_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &new_valid_signer,
+        state::ValidSigner::LEN,
+    )
+    .await
+    .unwrap();
+
+    let eth_address = rng.gen();
+    let message = instruction::action_message(
+        &instruction::add_signer_action(
+            &signer_group.pubkey(),
+            &new_valid_signer.pubkey(),
+            &eth_address,
+        ),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys, &message, 0);
+    let add_signer = vec![
+        secp256_program_instruction,
+        instruction::validate_multiple_signatures_add_signer(
+            &id(),
+            &valid_signers[0],
+            &valid_signers[1],
+            &valid_signers[2],
+            &signer_group.pubkey(),
+            &new_valid_signer.pubkey(),
+            signatures_data[0].clone(),
+            signatures_data[1].clone(),
+            signatures_data[2].clone(),
+            eth_address,
+        )
+        .unwrap(),
+    ];
+
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[2]),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys, &message, 0);
+    let clear_signer = vec![
+        secp256_program_instruction,
+        instruction::validate_multiple_signatures_clear_valid_signer(
+            &id(),
+            &valid_signers[0],
+            &valid_signers[1],
+            &valid_signers[2],
+            &signer_group.pubkey(),
+            &valid_signers[2],
+            &payer.pubkey(),
+            signatures_data[0].clone(),
+            signatures_data[1].clone(),
+            signatures_data[2].clone(),
+        )
+        .unwrap(),
+    ];
+
+    // Sysvar instruction and clock accounts
+    for instructions in [add_signer, clear_signer] {
+        for position in [5, 6] {
+            assert_spoofed_account_rejected(
+                &mut banks_client,
+                &payer,
+                recent_blockhash,
+                instructions.clone(),
+                position,
+            )
+            .await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn validate_3_signatures_in_one_instruction_clear_valid_signer() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = thread_rng();
+    let mut keys = Vec::new();
+    let mut valid_signers = Vec::new();
+    for _ in 0..3 {
+        let (key, _priv_key, _secp_pubkey, _eth_address, valid_signer) = create_valid_signer(
+            &mut rng,
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &signer_group.pubkey(),
+            &group_owner,
+        )
+        .await;
+        keys.push(key);
+        valid_signers.push(valid_signer.pubkey());
+    }
+
+    // signer to remove
+    let (_key, _priv_key, _secp_pubkey, _eth_address, valid_signer) = create_valid_signer(
+        &mut rng,
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &group_owner,
+    )
+    .await;
+
+    // All three signatures are checked by a single secp instruction
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signer.pubkey()),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys, &message, 0);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_multiple_signatures_clear_valid_signer(
+                &id(),
+                &valid_signers[0],
+                &valid_signers[1],
+                &valid_signers[2],
+                &signer_group.pubkey(),
+                &valid_signer.pubkey(),
+                &payer.pubkey(),
+                signatures_data[0].clone(),
+                signatures_data[1].clone(),
+                signatures_data[2].clone(),
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    assert!(banks_client
+        .get_account(valid_signer.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// Creates an initialized signer group with `count` valid signers,
+/// returning their secret keys and accounts
+async fn setup_with_valid_signers(
+    count: usize,
+) -> (BanksClient, Keypair, Hash, Keypair, Keypair, Vec<[u8; 32]>, Vec<Pubkey>) {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = thread_rng();
+    let mut keys = Vec::new();
+    let mut valid_signers = Vec::new();
+    for _ in 0..count {
+        let (key, _priv_key, _secp_pubkey, _eth_address, valid_signer) = create_valid_signer(
+            &mut rng,
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &signer_group.pubkey(),
+            &group_owner,
+        )
+        .await;
+        keys.push(key);
+        valid_signers.push(valid_signer.pubkey());
+    }
+
+    (
+        banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        group_owner,
+        keys,
+        valid_signers,
+    )
+}
+
+#[tokio::test]
+async fn set_threshold() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        group_owner,
+        _keys,
+        _valid_signers,
+    ) = setup_with_valid_signers(5).await;
+
+    process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        5,
+    )
+    .await
+    .unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.threshold, 5);
+    assert_eq!(signer_group_data.signer_count, 5);
+
+    // More signatures than the group has signers
+    let transaction_error = process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        6,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::ThresholdExceedsSignerCount as u32)
+        )
+    );
+
+    let transaction_error = process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        0,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::InvalidThreshold as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn validate_quorum_add_signer_with_lower_threshold() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(2).await;
+
+    process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        2,
+    )
+    .await
+    .unwrap();
+
+    let new_valid_signer = Keypair::new();
+    create_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &new_valid_signer,
+        state::ValidSigner::LEN,
+    )
+    .await
+    .unwrap();
+
+    let eth_address = [7u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE];
+    let message = instruction::action_message(
+        &instruction::add_signer_action(
+            &signer_group.pubkey(),
+            &new_valid_signer.pubkey(),
+            &eth_address,
+        ),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys, &message, 0);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_quorum_add_signer(
+                &id(),
+                &signer_group.pubkey(),
+                &new_valid_signer.pubkey(),
+                &valid_signers,
+                signatures_data,
+                eth_address,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let valid_signer_account = get_account(&mut banks_client, &new_valid_signer.pubkey()).await;
+    let valid_signer_data =
+        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
+    assert!(valid_signer_data.is_initialized());
+    assert_eq!(valid_signer_data.eth_address, eth_address);
+    assert_eq!(valid_signer_data.signer_group, signer_group.pubkey());
+}
+
+#[tokio::test]
+async fn validate_quorum_below_threshold() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        _group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(3).await;
+
+    // Two of the default three signatures
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[2]),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys[..2], &message, 0);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_quorum_clear_valid_signer(
+                &id(),
+                &signer_group.pubkey(),
+                &valid_signers[2],
+                &payer.pubkey(),
+                &valid_signers[..2],
+                signatures_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::NotEnoughSignatures as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn validate_quorum_set_threshold_and_clear_valid_signer() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        _group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(5).await;
+
+    let message = instruction::action_message(
+        &instruction::set_threshold_action(&signer_group.pubkey(), 4),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys[..3], &message, 0);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_quorum_set_threshold(
+                &id(),
+                &signer_group.pubkey(),
+                &valid_signers[..3],
+                signatures_data,
+                4,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.threshold, 4);
+
+    // Fixed three signature instructions no longer reach the threshold
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[4]),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys[..3], &message, 0);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_multiple_signatures_clear_valid_signer(
+                &id(),
+                &valid_signers[0],
+                &valid_signers[1],
+                &valid_signers[2],
+                &signer_group.pubkey(),
+                &valid_signers[4],
+                &payer.pubkey(),
+                signatures_data[0].clone(),
+                signatures_data[1].clone(),
+                signatures_data[2].clone(),
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::NotEnoughSignatures as u32)
+        )
+    );
+
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys[..4], &message, 0);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_quorum_clear_valid_signer(
+                &id(),
+                &signer_group.pubkey(),
+                &valid_signers[4],
+                &payer.pubkey(),
+                &valid_signers[..4],
+                signatures_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    assert!(banks_client
+        .get_account(valid_signers[4])
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// Sends a quorum removal of `old_valid_signer` approved by `keys` on behalf of
+/// `valid_signers`, all signing `message` in a single secp instruction
+#[allow(clippy::too_many_arguments)]
+async fn process_tx_quorum_clear_valid_signer(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    signer_group: &Pubkey,
+    old_valid_signer: &Pubkey,
+    keys: &[[u8; 32]],
+    valid_signers: &[Pubkey],
+    message: &[u8],
+) -> Result<(), TransportError> {
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(keys, message, 0);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_quorum_clear_valid_signer(
+                &id(),
+                signer_group,
+                old_valid_signer,
+                &payer.pubkey(),
+                valid_signers,
+                signatures_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await
+}
+
+#[tokio::test]
+async fn validate_quorum_repeated_valid_signer() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        _group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(2).await;
+
+    // One key-holder signing three times for the same account
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[1]),
+        Utc::now().timestamp(),
+    );
+    let transaction_error = process_tx_quorum_clear_valid_signer(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &valid_signers[1],
+        &[keys[0], keys[0], keys[0]],
+        &[valid_signers[0], valid_signers[0], valid_signers[0]],
+        &message,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::RepeatedSigner as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn validate_quorum_repeated_eth_address() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(3).await;
+
+    // Second account for the eth address of the first valid signer
+    let secp_pubkey = PublicKey::from_secret_key(&SecretKey::parse(&keys[0]).unwrap());
+    let duplicate_valid_signer = Keypair::new();
+    create_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &duplicate_valid_signer,
+        state::ValidSigner::LEN,
+    )
+    .await
+    .unwrap();
+    process_tx_init_valid_signer(
+        &duplicate_valid_signer.pubkey(),
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        construct_eth_address(&secp_pubkey),
+    )
+    .await
+    .unwrap();
+
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[2]),
+        Utc::now().timestamp(),
+    );
+    let transaction_error = process_tx_quorum_clear_valid_signer(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &valid_signers[2],
+        &[keys[0], keys[1], keys[0]],
+        &[
+            valid_signers[0],
+            valid_signers[1],
+            duplicate_valid_signer.pubkey(),
+        ],
+        &message,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::SignCollision as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn validate_quorum_message_bound_to_target() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        _group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(5).await;
+
+    // Signatures approving the removal of one signer can't remove another
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[4]),
+        Utc::now().timestamp(),
+    );
+    let transaction_error = process_tx_quorum_clear_valid_signer(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &valid_signers[3],
+        &keys[..3],
+        &valid_signers[..3],
+        &message,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::SignatureVerificationFailed as u32)
+        )
+    );
+
+    // Nor can a bare timestamp
+    let message = Utc::now().timestamp().to_le_bytes();
+    let transaction_error = process_tx_quorum_clear_valid_signer(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &valid_signers[4],
+        &keys[..3],
+        &valid_signers[..3],
+        &message,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::SignatureVerificationFailed as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn disable_signer_group_owner_by_wrong_owner() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let transaction_error = process_tx_init_disable_signer_group_owner(
+        &signer_group.pubkey(),
+        &Keypair::new(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::WrongOwner as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn transfer_signer_group_ownership() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let new_owner = Keypair::new();
+
+    // Nothing to accept before a transfer
+    let transaction_error = process_tx_accept_signer_group_ownership(
+        &signer_group.pubkey(),
+        &Keypair::new(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::NoPendingOwner as u32)
+        )
+    );
+
+    process_tx_transfer_signer_group_ownership(
+        &signer_group.pubkey(),
+        &group_owner,
+        &new_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    // The current owner keeps the group until the transfer is accepted
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.owner, group_owner.pubkey());
+    assert_eq!(signer_group_data.pending_owner, new_owner.pubkey());
+
+    let transaction_error = process_tx_accept_signer_group_ownership(
+        &signer_group.pubkey(),
+        &Keypair::new(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::WrongOwner as u32)
+        )
+    );
+
+    process_tx_accept_signer_group_ownership(
+        &signer_group.pubkey(),
+        &new_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.owner, new_owner.pubkey());
+    assert_eq!(signer_group_data.pending_owner, Pubkey::default());
+
+    // Only the new owner manages the group
+    let transaction_error = process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        2,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::WrongOwner as u32)
+        )
+    );
+
+    process_tx_init_disable_signer_group_owner(
+        &signer_group.pubkey(),
+        &new_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn enable_signer_group_owner() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(3).await;
+
+    process_tx_init_disable_signer_group_owner(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let message = instruction::action_message(
+        &instruction::enable_owner_action(&signer_group.pubkey(), &group_owner.pubkey()),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys, &message, 0);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::enable_signer_group_owner(
+                &id(),
+                &signer_group.pubkey(),
+                &valid_signers,
+                signatures_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert!(signer_group_data.owner_enabled);
+
+    process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        2,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn create_signer_group_and_valid_signer_at_derived_addresses() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let group_owner = Keypair::new();
+    let seed = b"listens";
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::create_signer_group(&id(), &payer.pubkey(), &group_owner.pubkey(), seed)
+            .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let (signer_group, _) =
+        instruction::find_signer_group_address(&id(), &group_owner.pubkey(), seed);
+    let signer_group_account = get_account(&mut banks_client, &signer_group).await;
+    assert_eq!(signer_group_account.owner, id());
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert!(signer_group_data.is_initialized());
+    assert!(signer_group_data.owner_enabled);
+    assert_eq!(signer_group_data.owner, group_owner.pubkey());
+    assert_eq!(signer_group_data.threshold, state::SignerGroup::DEFAULT_THRESHOLD);
+
+    let eth_address = [7u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE];
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::create_valid_signer(
+            &id(),
+            &payer.pubkey(),
+            &signer_group,
+            &group_owner.pubkey(),
+            eth_address,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let (valid_signer, _) = instruction::find_valid_signer_address(&id(), &signer_group, &eth_address);
+    let valid_signer_account = get_account(&mut banks_client, &valid_signer).await;
+    assert_eq!(valid_signer_account.owner, id());
+    let valid_signer_data =
+        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
+    assert!(valid_signer_data.is_initialized());
+    assert_eq!(valid_signer_data.signer_group, signer_group);
+    assert_eq!(valid_signer_data.eth_address, eth_address);
+
+    // The group can only have one valid signer per eth address
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::transfer(&payer.pubkey(), &group_owner.pubkey(), 10_000_000),
+            instruction::create_valid_signer(
+                &id(),
+                &group_owner.pubkey(),
+                &signer_group,
+                &group_owner.pubkey(),
+                eth_address,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert!(matches!(
+        transaction_error,
+        TransactionError::InstructionError(1, _)
+    ));
+}
+
+#[tokio::test]
+async fn create_signer_group_and_valid_signer_at_prefunded_addresses() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let group_owner = Keypair::new();
+    let seed = b"listens";
+    let eth_address = [7u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE];
+
+    // Anyone can send lamports to the derived addresses beforehand
+    let (signer_group, _) =
+        instruction::find_signer_group_address(&id(), &group_owner.pubkey(), seed);
+    let (valid_signer, _) = instruction::find_valid_signer_address(&id(), &signer_group, &eth_address);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::transfer(&payer.pubkey(), &signer_group, 1),
+            system_instruction::transfer(&payer.pubkey(), &valid_signer, 1),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::create_signer_group(&id(), &payer.pubkey(), &group_owner.pubkey(), seed)
+                .unwrap(),
+            instruction::create_valid_signer(
+                &id(),
+                &payer.pubkey(),
+                &signer_group,
+                &group_owner.pubkey(),
+                eth_address,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let rent = banks_client.get_rent().await.unwrap();
+    let signer_group_account = get_account(&mut banks_client, &signer_group).await;
+    assert_eq!(signer_group_account.owner, id());
+    assert!(rent.is_exempt(signer_group_account.lamports, state::SignerGroup::LEN));
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert!(signer_group_data.is_initialized());
+    assert_eq!(signer_group_data.signer_count, 1);
+
+    let valid_signer_account = get_account(&mut banks_client, &valid_signer).await;
+    assert_eq!(valid_signer_account.owner, id());
+    assert!(rent.is_exempt(valid_signer_account.lamports, state::ValidSigner::LEN));
+    let valid_signer_data =
+        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
+    assert!(valid_signer_data.is_initialized());
+    assert_eq!(valid_signer_data.eth_address, eth_address);
+}
+
+#[tokio::test]
+async fn validate_quorum_create_signer() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        _group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(3).await;
+
+    let eth_address = [7u8; state::SecpSignatureOffsets::ETH_ADDRESS_SIZE];
+    let (new_valid_signer, _) =
+        instruction::find_valid_signer_address(&id(), &signer_group.pubkey(), &eth_address);
+    let message = instruction::action_message(
+        &instruction::add_signer_action(&signer_group.pubkey(), &new_valid_signer, &eth_address),
+        Utc::now().timestamp(),
+    );
+    let (signatures_data, secp256_program_instruction) =
+        construct_multiple_signatures_data(&keys, &message, 0);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_quorum_create_signer(
+                &id(),
+                &signer_group.pubkey(),
+                &payer.pubkey(),
+                &valid_signers,
+                signatures_data,
+                eth_address,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let valid_signer_account = get_account(&mut banks_client, &new_valid_signer).await;
+    let valid_signer_data =
+        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
+    assert!(valid_signer_data.is_initialized());
+    assert_eq!(valid_signer_data.signer_group, signer_group.pubkey());
+    assert_eq!(valid_signer_data.eth_address, eth_address);
+}
+
+#[tokio::test]
+async fn migrate_valid_signer() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = thread_rng();
+    let (key, _priv_key, _secp_pubkey, eth_address, old_valid_signer) = create_valid_signer(
+        &mut rng,
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &group_owner,
+    )
+    .await;
+
+    // Anyone may send lamports to the derived address before the migration
+    let (valid_signer, _) =
+        instruction::find_valid_signer_address(&id(), &signer_group.pubkey(), &eth_address);
+    let funder = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::transfer(&payer.pubkey(), &funder.pubkey(), 10_000_000),
+            system_instruction::transfer(&payer.pubkey(), &valid_signer, 1),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // Only the group's owner may move the signer to its derived address
+    let not_owner = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::migrate_valid_signer(
+            &id(),
+            &funder.pubkey(),
+            &old_valid_signer.pubkey(),
+            &signer_group.pubkey(),
+            &not_owner.pubkey(),
+            &eth_address,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &funder, &not_owner], recent_blockhash);
+    let transaction_error = banks_client.process_transaction(transaction).await;
+    assert_eq!(
+        transaction_error.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::WrongOwner as u32)
+        )
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::migrate_valid_signer(
+            &id(),
+            &funder.pubkey(),
+            &old_valid_signer.pubkey(),
+            &signer_group.pubkey(),
+            &group_owner.pubkey(),
+            &eth_address,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &funder, &group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    assert!(banks_client
+        .get_account(old_valid_signer.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+
+    let valid_signer_account = get_account(&mut banks_client, &valid_signer).await;
+    assert_eq!(valid_signer_account.owner, id());
+    let valid_signer_data =
+        state::ValidSigner::try_from_slice(&valid_signer_account.data.as_slice()).unwrap();
+    assert!(valid_signer_data.is_initialized());
+    assert_eq!(valid_signer_data.signer_group, signer_group.pubkey());
+    assert_eq!(valid_signer_data.eth_address, eth_address);
+
+    // The migrated signer keeps validating signatures
+    let (signature_data, secp256_program_instruction) =
+        construct_signature_data(&key, &[8u8; 30], 0);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_signature(
+                &id(),
+                &valid_signer,
+                &signer_group.pubkey(),
+                signature_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+#[tokio::test]
+async fn validate_signature_once() {
+    let (mut banks_client, payer, recent_blockhash, signer_group, group_owner) = setup().await;
+
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = thread_rng();
+    let (key, _priv_key, _secp_pubkey, eth_address, valid_signer) = create_valid_signer(
+        &mut rng,
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &group_owner,
+    )
+    .await;
+
+    let message = instruction::action_message(&[8u8; 30], Utc::now().timestamp());
+    let (signature_data, secp256_program_instruction) = construct_signature_data(&key, &message, 0);
+    let instructions = [
+        secp256_program_instruction,
+        instruction::validate_signature_once(
+            &id(),
+            &valid_signer.pubkey(),
+            &signer_group.pubkey(),
+            &payer.pubkey(),
+            &eth_address,
+            signature_data.clone(),
+        )
+        .unwrap(),
+    ];
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let (used_message, _) = instruction::find_used_message_address(
+        &id(),
+        &signer_group.pubkey(),
+        &eth_address,
+        &message,
+    );
+    let used_message_account = get_account(&mut banks_client, &used_message).await;
+    assert_eq!(used_message_account.owner, id());
+    let used_message_data =
+        state::UsedMessage::try_from_slice(&used_message_account.data.as_slice()).unwrap();
+    assert_eq!(used_message_data.funder, payer.pubkey());
+    assert_eq!(
+        &used_message_data.timestamp.to_le_bytes(),
+        &message[message.len() - 8..]
+    );
+
+    // Replaying the message through another funder is rejected
+    let relayer = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[system_instruction::transfer(&payer.pubkey(), &relayer.pubkey(), 10_000_000)],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instructions[0].clone(),
+            instruction::validate_signature_once(
+                &id(),
+                &valid_signer.pubkey(),
+                &signer_group.pubkey(),
+                &relayer.pubkey(),
+                &eth_address,
+                signature_data,
+            )
+            .unwrap(),
+        ],
+        Some(&relayer.pubkey()),
+    );
+    transaction.sign(&[&relayer], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::MessageAlreadyUsed as u32)
+        )
+    );
+
+    // The used message account stays until the message expires
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::close_used_message(&id(), &used_message, &payer.pubkey()).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::MessageNotExpired as u32)
+        )
+    );
+
+    // Expired messages are rejected
+    let (signature_data, secp256_program_instruction) = construct_signature_data(
+        &key,
+        &instruction::action_message(&[9u8; 30], Utc::now().timestamp() - 700),
+        0,
+    );
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_signature_once(
+                &id(),
+                &valid_signer.pubkey(),
+                &signer_group.pubkey(),
+                &payer.pubkey(),
+                &eth_address,
+                signature_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error::AudiusError::InvalidInstruction as u32)
+        )
+    );
+
+    // Other messages of the signer are still accepted once, even when anyone
+    // who saw the signed message sent lamports to its used message address
+    let message = instruction::action_message(&[9u8; 30], Utc::now().timestamp());
+    let (used_message, _) = instruction::find_used_message_address(
+        &id(),
+        &signer_group.pubkey(),
+        &eth_address,
+        &message,
+    );
+    let mut transaction = Transaction::new_with_payer(
+        &[system_instruction::transfer(&relayer.pubkey(), &used_message, 1)],
+        Some(&relayer.pubkey()),
+    );
+    transaction.sign(&[&relayer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let (signature_data, secp256_program_instruction) = construct_signature_data(&key, &message, 0);
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::validate_signature_once(
+                &id(),
+                &valid_signer.pubkey(),
+                &signer_group.pubkey(),
+                &payer.pubkey(),
+                &eth_address,
+                signature_data,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let used_message_account = get_account(&mut banks_client, &used_message).await;
+    assert_eq!(used_message_account.owner, id());
+    let used_message_data =
+        state::UsedMessage::try_from_slice(&used_message_account.data.as_slice()).unwrap();
+    assert_eq!(used_message_data.funder, payer.pubkey());
+}
+
+#[tokio::test]
+async fn close_expired_used_message() {
+    let mut program_test = program_test();
+
+    let used_message = Pubkey::new_unique();
+    let funder = Pubkey::new_unique();
+    let lamports = solana_program::rent::Rent::default().minimum_balance(state::UsedMessage::LEN);
+    program_test.add_account(
+        used_message,
+        Account {
+            lamports,
+            data: state::UsedMessage {
+                version: processor::Processor::USED_MESSAGE_VERSION,
+                funder,
+                timestamp: Utc::now().timestamp() - 700,
+            }
+            .try_to_vec()
+            .unwrap(),
+            owner: id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    // Only the funder gets the lamports back
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::close_used_message(&id(), &used_message, &payer.pubkey()).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::close_used_message(&id(), &used_message, &funder).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    assert!(banks_client.get_account(used_message).await.unwrap().is_none());
+    assert_eq!(banks_client.get_balance(funder).await.unwrap(), lamports);
+}
+
+#[tokio::test]
+async fn signer_count_keeps_threshold_reachable() {
+    let (
+        mut banks_client,
+        payer,
+        recent_blockhash,
+        signer_group,
+        group_owner,
+        keys,
+        valid_signers,
+    ) = setup_with_valid_signers(4).await;
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.signer_count, 4);
+
+    let message = instruction::action_message(
+        &instruction::clear_signer_action(&signer_group.pubkey(), &valid_signers[3]),
+        Utc::now().timestamp(),
+    );
+    process_tx_quorum_clear_valid_signer(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &signer_group.pubkey(),
+        &valid_signers[3],
+        &keys[..3],
+        &valid_signers[..3],
+        &message,
+    )
+    .await
+    .unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.signer_count, 3);
+
+    // The group needs every remaining signer to reach its threshold
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::clear_valid_signer(
+            &id(),
+            &valid_signers[2],
+            &signer_group.pubkey(),
+            &group_owner.pubkey(),
+            &payer.pubkey(),
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::ThresholdExceedsSignerCount as u32)
+        )
+    );
+
+    // Lowering the threshold first lets the signer go
+    process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        2,
+    )
+    .await
+    .unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::clear_valid_signer(
+            &id(),
+            &valid_signers[2],
+            &signer_group.pubkey(),
+            &group_owner.pubkey(),
+            &Pubkey::new_unique(),
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.signer_count, 2);
+}
+
+#[tokio::test]
+async fn migrate_legacy_signer_group() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let signer_group = Keypair::new();
+    let group_owner = Keypair::new();
+
+    create_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &signer_group,
+        state::SignerGroup::LEGACY_LEN,
+    )
+    .await
+    .unwrap();
+    process_tx_init_signer_group(
+        &signer_group.pubkey(),
+        &group_owner.pubkey(),
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = thread_rng();
+    let mut valid_signers = Vec::new();
+    for _ in 0..4 {
+        let (_key, _priv_key, _secp_pubkey, _eth_address, valid_signer) = create_valid_signer(
+            &mut rng,
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &signer_group.pubkey(),
+            &group_owner,
+        )
+        .await;
+        valid_signers.push(valid_signer.pubkey());
+    }
+
+    // Legacy groups can't store a threshold
+    let transaction_error = process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        1,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(0, InstructionError::AccountDataTooSmall)
+    );
+
+    // Only the owner migrates the group
+    let not_owner = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::migrate_signer_group(
+            &id(),
+            &payer.pubkey(),
+            &signer_group.pubkey(),
+            &not_owner.pubkey(),
+            &valid_signers,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &not_owner], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::WrongOwner as u32)
+        )
+    );
+
+    // Each valid signer is counted once
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::migrate_signer_group(
+            &id(),
+            &payer.pubkey(),
+            &signer_group.pubkey(),
+            &group_owner.pubkey(),
+            &[valid_signers[0], valid_signers[0]],
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        transaction_error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(error::AudiusError::RepeatedSigner as u32)
+        )
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::migrate_signer_group(
+            &id(),
+            &payer.pubkey(),
+            &signer_group.pubkey(),
+            &group_owner.pubkey(),
+            &valid_signers,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &group_owner], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let signer_group_account = get_account(&mut banks_client, &signer_group.pubkey()).await;
+    assert_eq!(signer_group_account.data.len(), state::SignerGroup::LEN);
+    let signer_group_data =
+        state::SignerGroup::try_from_slice(&signer_group_account.data.as_slice()).unwrap();
+    assert_eq!(signer_group_data.owner, group_owner.pubkey());
+    assert_eq!(signer_group_data.threshold, state::SignerGroup::DEFAULT_THRESHOLD);
+    assert_eq!(signer_group_data.signer_count, 4);
+
+    process_tx_set_threshold(
+        &signer_group.pubkey(),
+        &group_owner,
+        &payer,
+        recent_blockhash,
+        &mut banks_client,
+        2,
+    )
+    .await
+    .unwrap();
+}
@@ -2,9 +2,15 @@ use std::convert::TryInto;
 
 use anyhow::anyhow;
 use anyhow::{bail, Context};
+use audius_secp_utils::{
+    client::{eth_address_from_secret_key, new_secp256k1_instruction_2_0},
+    eip191_message,
+};
 use claimable_tokens::{
-    instruction::{CreateTokenAccount, Transfer},
-    utils::program::{find_address_pair, EthereumAddress},
+    cluster_id,
+    instruction::{transfer_message, transfer_typed_data_message, CreateTokenAccount, Transfer},
+    state::NonceAccount,
+    utils::program::{find_address_pair, find_nonce_address, EthereumAddress},
 };
 use clap::{
     crate_description, crate_name, crate_version, value_t, App, AppSettings, Arg, ArgMatches,
@@ -19,13 +25,8 @@ use solana_clap_utils::{
 use solana_client::{rpc_client::RpcClient, rpc_response::Response};
 use solana_program::instruction::Instruction;
 use solana_sdk::{
-    account::ReadableAccount,
-    commitment_config::CommitmentConfig,
-    program_pack::Pack,
-    pubkey::Pubkey,
-    secp256k1_instruction::{construct_eth_pubkey, new_secp256k1_instruction},
-    signature::Signer,
-    transaction::Transaction,
+    account::ReadableAccount, commitment_config::CommitmentConfig, program_pack::Pack,
+    pubkey::Pubkey, signature::Signer, transaction::Transaction,
 };
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
 use spl_token::state::{Account, Mint};
@@ -81,17 +82,26 @@ fn token_account_initialized(config: &Config, key: &Pubkey) -> bool {
     false
 }
 
+/// Form of the transfer message signed with the ethereum key
+enum MessageFormat {
+    Raw,
+    /// `personal_sign`
+    Eip191,
+    /// `eth_signTypedData` for the cluster with the given id
+    Eip712(u64),
+}
+
 fn transfer(
     config: Config,
     secret_key: libsecp256k1::SecretKey,
     mint: Pubkey,
     recipient: Option<Pubkey>,
-    amount: f64,
+    amount: Option<f64>,
+    format: MessageFormat,
 ) -> anyhow::Result<()> {
     let mut instructions = vec![];
 
-    let eth_pubkey = libsecp256k1::PublicKey::from_secret_key(&secret_key);
-    let eth_address = construct_eth_pubkey(&eth_pubkey);
+    let eth_address = eth_address_from_secret_key(&secret_key);
     let pair = find_address_pair(&claimable_tokens::id(), &mint, eth_address)?;
 
     // If `recipient` token account provided - we will use it,
@@ -137,21 +147,70 @@ fn transfer(
 
     let mint_raw_data = config.rpc_client.get_account_data(&mint)?;
     let mint_data = Mint::unpack(mint_raw_data.as_ref())?;
+    let claim_all = amount.is_none();
+    // Zero amount claims all tokens of the bank
+    let amount = amount.map_or(0, |amount| {
+        spl_token::ui_amount_to_amount(amount, mint_data.decimals)
+    });
+    if amount == 0 && !claim_all {
+        bail!("Amount must be greater than zero, use --all to claim all tokens");
+    }
 
-    let instructions = &[
-        new_secp256k1_instruction(&secret_key, &user_acc.to_bytes()),
-        claimable_tokens::instruction::transfer(
+    // Banks created before nonce accounts were introduced
+    // get their nonce account created on the first transfer
+    let (nonce_acc, _) = find_nonce_address(&claimable_tokens::id(), &mint, eth_address);
+    let nonce = if let Response {
+        value: Some(account),
+        ..
+    } = config
+        .rpc_client
+        .get_account_with_commitment(&nonce_acc, config.rpc_client.commitment())?
+    {
+        NonceAccount::unpack(account.data())?.nonce
+    } else {
+        instructions.push(claimable_tokens::instruction::init(
+            &claimable_tokens::id(),
+            &config.fee_payer.pubkey(),
+            &mint,
+            CreateTokenAccount { eth_address },
+        )?);
+        0
+    };
+
+    let message = match format {
+        MessageFormat::Raw => transfer_message(&claimable_tokens::id(), &user_acc, amount, nonce)?,
+        MessageFormat::Eip191 => eip191_message(&transfer_message(
             &claimable_tokens::id(),
-            &pair.derive.address,
             &user_acc,
-            &pair.base.address,
-            Transfer {
-                eth_address,
-                amount: spl_token::ui_amount_to_amount(amount, mint_data.decimals),
-            },
-        )?,
-    ];
-    let mut tx = Transaction::new_with_payer(instructions, Some(&config.fee_payer.pubkey()));
+            amount,
+            nonce,
+        )?),
+        MessageFormat::Eip712(cluster_id) => transfer_typed_data_message(
+            &claimable_tokens::id(),
+            &user_acc,
+            amount,
+            nonce,
+            cluster_id,
+        ),
+    };
+    // The secp instruction may follow the nonce account initialization
+    instructions.push(new_secp256k1_instruction_2_0(
+        &secret_key,
+        &message,
+        instructions.len() as u8,
+    ));
+    instructions.push(claimable_tokens::instruction::transfer(
+        &claimable_tokens::id(),
+        &pair.derive.address,
+        &user_acc,
+        &nonce_acc,
+        &pair.base.address,
+        Transfer {
+            eth_address,
+            amount,
+        },
+    )?);
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&config.fee_payer.pubkey()));
     let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
     tx.sign(&[config.fee_payer.as_ref()], recent_blockhash);
     let tx_hash = config
@@ -312,14 +371,34 @@ fn main() -> anyhow::Result<()> {
                 Arg::with_name("amount")
                     .value_name("NUMBER")
                     .takes_value(true)
-                    .required(true)
+                    .required_unless("all")
                     .help("Amount to claim"),
+                Arg::with_name("all")
+                    .long("all")
+                    .conflicts_with("amount")
+                    .takes_value(false)
+                    .help("Claim all tokens from your account."),
                 Arg::with_name("recipient")
                     .long("recipient")
                     .validator(is_pubkey)
                     .value_name("SOLANA_ADDRESS")
                     .takes_value(true)
                     .help("Recipient of transfer."),
+                Arg::with_name("eip191")
+                    .long("eip191")
+                    .conflicts_with("eip712")
+                    .takes_value(false)
+                    .help("Sign the message as `personal_sign` does, with the EIP-191 prefix."),
+                Arg::with_name("eip712")
+                    .long("eip712")
+                    .takes_value(false)
+                    .help("Sign the message as EIP-712 typed data, as `eth_signTypedData` does."),
+                Arg::with_name("cluster_id")
+                    .long("cluster-id")
+                    .value_name("NUMBER")
+                    .takes_value(true)
+                    .requires("eip712")
+                    .help("Id of the cluster signed in the EIP-712 domain: 101 mainnet-beta, 102 testnet, 103 devnet. Defaults to mainnet-beta."),
                 ])
                 .help("Transfers some amount of tokens from your account associated with Ethereum address to another account."),
             SubCommand::with_name("balance").args(&[
@@ -389,17 +468,33 @@ fn main() -> anyhow::Result<()> {
 
     match matches.subcommand() {
         ("transfer", Some(args)) => {
-            let (privkey, mint, recipient, amount) = (|| -> anyhow::Result<_> {
+            let (privkey, mint, recipient, amount, format) = (|| -> anyhow::Result<_> {
                 let privkey = eth_seckey_of(args, "private_key")?;
                 let mint = pubkey_of(args, "mint").unwrap();
                 let recipient = pubkey_of(args, "recipient");
-                let amount = value_t!(args.value_of("amount"), f64)?;
-
-                Ok((privkey, mint, recipient, amount))
+                let amount = if args.is_present("all") {
+                    None
+                } else {
+                    Some(value_t!(args.value_of("amount"), f64)?)
+                };
+                let format = if args.is_present("eip712") {
+                    let cluster_id = if args.is_present("cluster_id") {
+                        value_t!(args.value_of("cluster_id"), u64)?
+                    } else {
+                        cluster_id::MAINNET_BETA
+                    };
+                    MessageFormat::Eip712(cluster_id)
+                } else if args.is_present("eip191") {
+                    MessageFormat::Eip191
+                } else {
+                    MessageFormat::Raw
+                };
+
+                Ok((privkey, mint, recipient, amount, format))
             })()
             .context("Preparing parameters for execution command `transfer`")?;
 
-            transfer(config, privkey, mint, recipient, amount)
+            transfer(config, privkey, mint, recipient, amount, format)
                 .context("Failed to execute `transfer` command")?
         }
         ("send-to", Some(args)) => {
@@ -447,12 +542,11 @@ fn main() -> anyhow::Result<()> {
 
 #[test]
 fn test_eth_address_gen_from_secret() {
-    use secp256k1::*;
+    use libsecp256k1::*;
 
     const INPUT_PV: &str = "a53b70c96e7960f1dc295c13fc4c6598a94cd6e98c418e5d4f33146402d13935";
     let private = SecretKey::parse_slice(&hex::decode(INPUT_PV).unwrap().as_slice()).unwrap();
-    let eth_address = secp256k1::PublicKey::from_secret_key(&private);
-    let hashed_eth_pk = construct_eth_pubkey(&eth_address);
+    let hashed_eth_pk = eth_address_from_secret_key(&private);
     assert_eq!(
         "7f14493c18aa7ff329a3cbd8309296f1ab838c21",
         hex::encode(hashed_eth_pk)
@@ -461,7 +555,7 @@ fn test_eth_address_gen_from_secret() {
 
 #[test]
 fn test_parse_eth_pv() {
-    use secp256k1::*;
+    use libsecp256k1::*;
     use std::str;
 
     const INPUT_PV: &str = "09e910621c2e988e9f7f6ffcd7024f54ec1461fa6e86a4b545e9e1fe21c28866";
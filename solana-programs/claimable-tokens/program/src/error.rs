@@ -1,5 +1,6 @@
 //! Error types
 
+use audius_secp_utils::SecpError;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use solana_program::{
@@ -43,6 +44,18 @@ impl PrintProgramError for ClaimableProgramError {
     }
 }
 
+impl From<SecpError> for ClaimableProgramError {
+    fn from(e: SecpError) -> Self {
+        match e {
+            SecpError::InstructionLoadError => ClaimableProgramError::InstructionLoadError,
+            SecpError::Secp256InstructionMissing => ClaimableProgramError::Secp256InstructionLosing,
+            SecpError::MalformedInstruction | SecpError::WrongSigner | SecpError::WrongMessage => {
+                ClaimableProgramError::SignatureVerificationFailed
+            }
+        }
+    }
+}
+
 /// Convert SanitizeError to ClaimableProgramError
 pub fn to_claimable_tokens_error(_e: SanitizeError) -> ClaimableProgramError {
     ClaimableProgramError::InstructionLoadError
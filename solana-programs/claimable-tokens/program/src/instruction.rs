@@ -1,6 +1,9 @@
 //! Instruction types
 
-use crate::utils::program::{find_address_pair, EthereumAddress};
+use crate::utils::program::{find_address_pair, find_nonce_address, EthereumAddress};
+use audius_secp_utils::eip712::{
+    eip712_encode_uint, eip712_hash_struct, eip712_message, Eip712Domain,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -26,6 +29,51 @@ pub struct Transfer {
     pub amount: u64,
 }
 
+/// Message signed with the user's ethereum key to authorize `Transfer`
+#[derive(Clone, BorshDeserialize, BorshSerialize, PartialEq, Debug)]
+pub struct TransferMessage {
+    /// Claimable tokens program id
+    pub program_id: Pubkey,
+    /// Receiver token account
+    pub destination: Pubkey,
+    /// The amount of claiming tokens, 0 claims all tokens
+    pub amount: u64,
+    /// Current nonce of the user bank
+    pub nonce: u64,
+}
+
+impl TransferMessage {
+    /// Whether the message may be signed with `personal_sign` (EIP-191)
+    pub const EIP191: bool = true;
+
+    /// Name of the EIP-712 domain
+    pub const EIP712_NAME: &'static str = "Audius Claimable Tokens";
+    /// Version of the EIP-712 domain
+    pub const EIP712_VERSION: &'static str = "1";
+    /// EIP-712 type of the message, the program id is the domain's verifying contract
+    pub const EIP712_TYPE: &'static str =
+        "Transfer(bytes32 destination,uint64 amount,uint64 nonce)";
+
+    /// EIP-712 typed data form of the message for the cluster with `cluster_id`
+    pub fn eip712_message(&self, cluster_id: u64) -> Vec<u8> {
+        let domain = Eip712Domain {
+            name: Self::EIP712_NAME,
+            version: Self::EIP712_VERSION,
+            chain_id: cluster_id,
+            verifying_contract: self.program_id.to_bytes(),
+        };
+        let transfer = eip712_hash_struct(
+            Self::EIP712_TYPE,
+            &[
+                self.destination.to_bytes(),
+                eip712_encode_uint(self.amount),
+                eip712_encode_uint(self.nonce),
+            ],
+        );
+        eip712_message(&domain.separator(), &transfer)
+    }
+}
+
 /// Instruction definition
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub enum ClaimableProgramInstruction {
@@ -38,6 +86,10 @@ pub enum ClaimableProgramInstruction {
     ///   4. `[r]` Rent id
     ///   5. `[r]` SPL token account id
     ///   6. `[r]` System program id
+    ///   7. `[w]` PDA nonce account to create
+    ///
+    /// If the token account already exists only the nonce account is created,
+    /// so banks opened before nonce accounts were introduced can be migrated
     CreateTokenAccount(CreateTokenAccount),
 
     /// Transfer
@@ -47,6 +99,12 @@ pub enum ClaimableProgramInstruction {
     ///   2. `[r]` Banks token account authority
     ///   3. `[r]` Sysvar instruction id
     ///   4. `[r]` SPL token account id
+    ///   5. `[w]` Banks nonce account
+    ///
+    /// Previous instruction must be `new_secp256k1_instruction` over the
+    /// borsh serialized `TransferMessage` with the current nonce,
+    /// over its EIP-191 prefixed form (`personal_sign`)
+    /// or over its EIP-712 typed data form for `CLUSTER_ID`
     Transfer(Transfer),
 }
 
@@ -58,6 +116,7 @@ pub fn init(
     ethereum_address: CreateTokenAccount,
 ) -> Result<Instruction, ProgramError> {
     let pair = find_address_pair(program_id, mint, ethereum_address.eth_address)?;
+    let (nonce_acc, _) = find_nonce_address(program_id, mint, ethereum_address.eth_address);
 
     let data = ClaimableProgramInstruction::CreateTokenAccount(ethereum_address).try_to_vec()?;
     let accounts = vec![
@@ -68,6 +127,7 @@ pub fn init(
         AccountMeta::new_readonly(sysvar::rent::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(nonce_acc, false),
     ];
     Ok(Instruction {
         program_id: *program_id,
@@ -76,15 +136,50 @@ pub fn init(
     })
 }
 
+/// Create message to be signed for `Transfer` instruction
+pub fn transfer_message(
+    program_id: &Pubkey,
+    users_token_acc: &Pubkey,
+    amount: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, ProgramError> {
+    Ok(TransferMessage {
+        program_id: *program_id,
+        destination: *users_token_acc,
+        amount,
+        nonce,
+    }
+    .try_to_vec()?)
+}
+
+/// Create EIP-712 typed data message to be signed for `Transfer` instruction
+pub fn transfer_typed_data_message(
+    program_id: &Pubkey,
+    users_token_acc: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    cluster_id: u64,
+) -> Vec<u8> {
+    TransferMessage {
+        program_id: *program_id,
+        destination: *users_token_acc,
+        amount,
+        nonce,
+    }
+    .eip712_message(cluster_id)
+}
+
 /// Create `Transfer` instruction
 ///
 /// NOTE: Instruction must followed after `new_secp256k1_instruction`
-/// with params: ethereum private key and user token account public key.
+/// with params: ethereum private key and message from `transfer_message`
+/// or `transfer_typed_data_message`.
 /// Otherwise error message `Secp256 instruction losing` will be issued
 pub fn transfer(
     program_id: &Pubkey,
     banks_token_acc: &Pubkey,
     users_token_acc: &Pubkey,
+    nonce_acc: &Pubkey,
     authority: &Pubkey,
     eth_address: Transfer,
 ) -> Result<Instruction, ProgramError> {
@@ -95,6 +190,7 @@ pub fn transfer(
         AccountMeta::new_readonly(*authority, false),
         AccountMeta::new_readonly(sysvar::instructions::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*nonce_acc, false),
     ];
     Ok(Instruction {
         program_id: *program_id,
@@ -11,6 +11,26 @@ pub mod utils;
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 
+/// Ids of the Solana clusters, signed as the `chainId` of EIP-712 transfer messages
+pub mod cluster_id {
+    /// Mainnet beta
+    pub const MAINNET_BETA: u64 = 101;
+    /// Testnet
+    pub const TESTNET: u64 = 102;
+    /// Devnet
+    pub const DEVNET: u64 = 103;
+}
+
+/// Id of the cluster the program is built for
+#[cfg(not(any(feature = "devnet", feature = "testnet")))]
+pub const CLUSTER_ID: u64 = cluster_id::MAINNET_BETA;
+/// Id of the cluster the program is built for
+#[cfg(all(feature = "testnet", not(feature = "devnet")))]
+pub const CLUSTER_ID: u64 = cluster_id::TESTNET;
+/// Id of the cluster the program is built for
+#[cfg(feature = "devnet")]
+pub const CLUSTER_ID: u64 = cluster_id::DEVNET;
+
 // Export current sdk types for downstream users building with a different sdk version
 pub use solana_program;
 
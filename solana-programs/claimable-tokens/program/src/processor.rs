@@ -1,11 +1,22 @@
 //! Program state processor
 
 use crate::{
-    error::{to_claimable_tokens_error, ClaimableProgramError},
-    instruction::ClaimableProgramInstruction,
-    utils::program::{find_address_pair, EthereumAddress},
+    error::ClaimableProgramError,
+    instruction::{ClaimableProgramInstruction, TransferMessage},
+    state::NonceAccount,
+    utils::{
+        accounts::{
+            assert_instructions_sysvar, assert_rent_sysvar, assert_spl_token_program,
+            assert_system_program,
+        },
+        program::{find_address_pair, find_nonce_address, EthereumAddress, NONCE_ACCOUNT_PREFIX},
+    },
+    CLUSTER_ID,
 };
-use borsh::BorshDeserialize;
+use audius_secp_utils::{
+    create_derived_account, load_previous_secp_signatures, verify_signer_and_message,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::next_account_info,
     account_info::AccountInfo,
@@ -13,13 +24,12 @@ use solana_program::{
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
-    program_pack::Pack,
+    program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
-    secp256k1_program, system_instruction, sysvar,
+    system_instruction,
     sysvar::rent::Rent,
     sysvar::Sysvar,
 };
-use std::mem::size_of;
 
 /// Program state handler.
 pub struct Processor;
@@ -34,11 +44,30 @@ impl Processor {
         base_account_info: &AccountInfo<'a>,
         acc_to_create_info: &AccountInfo<'a>,
         rent_account_info: &AccountInfo<'a>,
+        nonce_account_info: &AccountInfo<'a>,
         rent: &Rent,
         eth_address: EthereumAddress,
     ) -> ProgramResult {
         // check that mint is initialized
         spl_token::state::Mint::unpack(&mint_account_info.data.borrow())?;
+        Self::create_nonce_account(
+            program_id,
+            funder_account_info,
+            nonce_account_info,
+            mint_account_info.key,
+            eth_address,
+            rent,
+        )?;
+
+        // Bank created before nonce accounts were introduced
+        if !acc_to_create_info.data_is_empty() {
+            let pair = find_address_pair(program_id, mint_account_info.key, eth_address)?;
+            if *acc_to_create_info.key != pair.derive.address {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            return Ok(());
+        }
+
         Self::create_account(
             program_id,
             funder_account_info.clone(),
@@ -46,7 +75,7 @@ impl Processor {
             mint_account_info.key,
             base_account_info.clone(),
             eth_address,
-            rent.minimum_balance(spl_token::state::Account::LEN),
+            rent,
             spl_token::state::Account::LEN as u64,
         )?;
 
@@ -58,50 +87,72 @@ impl Processor {
         )
     }
 
-    /// Checks that the user signed message with his ethereum private key
+    /// Checks that the user signed message with his ethereum private key,
+    /// either as is or as its EIP-712 typed data form
     fn check_ethereum_sign(
         instruction_info: &AccountInfo,
         expected_signer: &EthereumAddress,
-        expected_message: &[u8],
+        expected_message: &TransferMessage,
     ) -> ProgramResult {
-        let index = sysvar::instructions::load_current_index(&instruction_info.data.borrow());
-
-        // instruction can't be first in transaction
-        // because must follow after `new_secp256k1_instruction`
-        if index == 0 {
-            return Err(ClaimableProgramError::Secp256InstructionLosing.into());
-        }
+        assert_instructions_sysvar(instruction_info)?;
 
-        // load previous instruction
-        let instruction = sysvar::instructions::load_instruction_at(
-            (index - 1) as usize,
-            &instruction_info.data.borrow(),
+        // The secp instruction may check several signatures, any of them can match
+        let signatures =
+            load_previous_secp_signatures(instruction_info).map_err(ClaimableProgramError::from)?;
+        verify_signer_and_message(
+            &signatures,
+            expected_signer,
+            &expected_message.try_to_vec()?,
+            TransferMessage::EIP191,
         )
-        .map_err(to_claimable_tokens_error)?;
+        .or_else(|_| {
+            verify_signer_and_message(
+                &signatures,
+                expected_signer,
+                &expected_message.eip712_message(CLUSTER_ID),
+                false,
+            )
+        })
+        .map_err(ClaimableProgramError::from)?;
 
-        // is that instruction is `new_secp256k1_instruction`
-        if instruction.program_id != secp256k1_program::id() {
-            return Err(ClaimableProgramError::Secp256InstructionLosing.into());
-        }
-
-        Self::validate_eth_signature(expected_signer, expected_message, instruction.data)
+        Ok(())
     }
 
     /// Transfer user tokens
     /// Operation gated by SECP recovery
+    #[allow(clippy::too_many_arguments)]
     pub fn process_transfer_instruction<'a>(
         program_id: &Pubkey,
         banks_token_account_info: &AccountInfo<'a>,
         destination_account_info: &AccountInfo<'a>,
         authority_account_info: &AccountInfo<'a>,
         instruction_info: &AccountInfo<'a>,
+        nonce_account_info: &AccountInfo<'a>,
         eth_address: EthereumAddress,
         amount: u64,
     ) -> ProgramResult {
+        let source_data =
+            spl_token::state::Account::unpack(&banks_token_account_info.data.borrow())?;
+
+        // Verify nonce account matches the expected PDA
+        let (nonce_address, _) = find_nonce_address(program_id, &source_data.mint, eth_address);
+        if *nonce_account_info.key != nonce_address || nonce_account_info.owner != program_id {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut nonce_account = NonceAccount::unpack_unchecked(&nonce_account_info.data.borrow())?;
+        if !nonce_account.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
         Self::check_ethereum_sign(
             instruction_info,
             &eth_address,
-            &destination_account_info.key.to_bytes(),
+            &TransferMessage {
+                program_id: *program_id,
+                destination: *destination_account_info.key,
+                amount,
+                nonce: nonce_account.nonce,
+            },
         )?;
         Self::token_transfer(
             banks_token_account_info.clone(),
@@ -110,7 +161,14 @@ impl Processor {
             program_id,
             eth_address,
             amount,
-        )
+        )?;
+
+        // Signed message can't be used again
+        nonce_account.nonce = nonce_account
+            .nonce
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        NonceAccount::pack(nonce_account, *nonce_account_info.data.borrow_mut())
     }
 
     /// Processes an instruction
@@ -130,7 +188,11 @@ impl Processor {
                 let base_account_info = next_account_info(account_info_iter)?;
                 let acc_to_create_info = next_account_info(account_info_iter)?;
                 let rent_account_info = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_account_info)?;
                 let rent = &Rent::from_account_info(rent_account_info)?;
+                assert_spl_token_program(next_account_info(account_info_iter)?)?;
+                assert_system_program(next_account_info(account_info_iter)?)?;
+                let nonce_account_info = next_account_info(account_info_iter)?;
 
                 Self::process_init_instruction(
                     program_id,
@@ -139,6 +201,7 @@ impl Processor {
                     base_account_info,
                     acc_to_create_info,
                     rent_account_info,
+                    nonce_account_info,
                     rent,
                     eth_address.eth_address,
                 )
@@ -150,6 +213,8 @@ impl Processor {
                 let destination_account_info = next_account_info(account_info_iter)?;
                 let authority_account_info = next_account_info(account_info_iter)?;
                 let instruction_info = next_account_info(account_info_iter)?;
+                assert_spl_token_program(next_account_info(account_info_iter)?)?;
+                let nonce_account_info = next_account_info(account_info_iter)?;
 
                 Self::process_transfer_instruction(
                     program_id,
@@ -157,6 +222,7 @@ impl Processor {
                     destination_account_info,
                     authority_account_info,
                     instruction_info,
+                    nonce_account_info,
                     instruction.eth_address,
                     instruction.amount,
                 )
@@ -173,7 +239,7 @@ impl Processor {
         mint_key: &Pubkey,
         base: AccountInfo<'a>,
         eth_address: EthereumAddress,
-        required_lamports: u64,
+        rent: &Rent,
         space: u64,
     ) -> ProgramResult {
         // Calculate target bank account PDA
@@ -188,22 +254,89 @@ impl Processor {
 
         // Create user bank account signature and invoke from program
         let signature = &[&mint_key.to_bytes()[..32], &[pair.base.seed]];
+        let required_lamports = rent.minimum_balance(space as usize);
+
+        if account_to_create.lamports() == 0 {
+            return invoke_signed(
+                &system_instruction::create_account_with_seed(
+                    funder.key,
+                    account_to_create.key,
+                    base.key,
+                    pair.derive.seed.as_str(),
+                    required_lamports,
+                    space,
+                    &spl_token::id(),
+                ),
+                &[funder.clone(), account_to_create.clone(), base.clone()],
+                &[signature],
+            );
+        }
 
+        // Lamports sent to the address beforehand would make creation fail,
+        // so the account is funded, allocated and assigned instead
+        let required_lamports = required_lamports.saturating_sub(account_to_create.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(funder.key, account_to_create.key, required_lamports),
+                &[funder.clone(), account_to_create.clone()],
+            )?;
+        }
         invoke_signed(
-            &system_instruction::create_account_with_seed(
-                funder.key,
+            &system_instruction::allocate_with_seed(
                 account_to_create.key,
                 base.key,
                 pair.derive.seed.as_str(),
-                required_lamports,
                 space,
                 &spl_token::id(),
             ),
-            &[funder.clone(), account_to_create.clone(), base.clone()],
+            &[account_to_create.clone(), base.clone()],
+            &[signature],
+        )?;
+        invoke_signed(
+            &system_instruction::assign_with_seed(
+                account_to_create.key,
+                base.key,
+                pair.derive.seed.as_str(),
+                &spl_token::id(),
+            ),
+            &[account_to_create.clone(), base.clone()],
             &[signature],
         )
     }
 
+    /// Create nonce account of the user bank
+    fn create_nonce_account<'a>(
+        program_id: &Pubkey,
+        funder: &AccountInfo<'a>,
+        account_to_create: &AccountInfo<'a>,
+        mint_key: &Pubkey,
+        eth_address: EthereumAddress,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let (nonce_address, bump_seed) = find_nonce_address(program_id, mint_key, eth_address);
+        if *account_to_create.key != nonce_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let signature = &[
+            &mint_key.to_bytes()[..32],
+            NONCE_ACCOUNT_PREFIX.as_ref(),
+            eth_address.as_ref(),
+            &[bump_seed],
+        ];
+
+        create_derived_account(
+            funder,
+            account_to_create,
+            NonceAccount::LEN,
+            program_id,
+            &[signature],
+            rent,
+        )?;
+
+        NonceAccount::pack(NonceAccount::new(), *account_to_create.data.borrow_mut())
+    }
+
     /// Helper to initialize user token account
     fn initialize_token_account<'a>(
         account_to_initialize: AccountInfo<'a>,
@@ -239,6 +372,12 @@ impl Processor {
             return Err(ProgramError::InvalidSeeds);
         }
 
+        // Zero amount claims all tokens of the bank
+        let amount = if amount == 0 {
+            source_data.amount
+        } else {
+            amount
+        };
         if amount == 0 {
             return Err(ProgramError::InsufficientFunds);
         }
@@ -259,29 +398,4 @@ impl Processor {
             signers,
         )
     }
-
-    /// Checks that message inside instruction was signed by expected signer
-    /// and message matches the expected value
-    fn validate_eth_signature(
-        expected_signer: &EthereumAddress,
-        expected_message: &[u8],
-        secp_instruction_data: Vec<u8>,
-    ) -> Result<(), ProgramError> {
-        let eth_address_offset = 12;
-        let instruction_signer = secp_instruction_data
-            [eth_address_offset..eth_address_offset + size_of::<EthereumAddress>()]
-            .to_vec();
-        if instruction_signer != expected_signer {
-            return Err(ClaimableProgramError::SignatureVerificationFailed.into());
-        }
-
-        //NOTE: meta (12) + address (20) + signature (65) = 97
-        let message_data_offset = 97;
-        let instruction_message = secp_instruction_data[message_data_offset..].to_vec();
-        if instruction_message != *expected_message {
-            return Err(ClaimableProgramError::SignatureVerificationFailed.into());
-        }
-
-        Ok(())
-    }
 }
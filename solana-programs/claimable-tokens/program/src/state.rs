@@ -1 +1,64 @@
 //! State transition types
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+};
+
+/// Uninitialized version value, all instances are at least version 1
+pub const UNINITIALIZED_VERSION: u8 = 0;
+
+/// Current version of the nonce account layout
+pub const NONCE_ACCOUNT_VERSION: u8 = 1;
+
+/// Nonce of the user bank, must be signed with every `Transfer`
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct NonceAccount {
+    /// Version
+    pub version: u8,
+    /// Nonce expected in the next transfer message
+    pub nonce: u64,
+}
+
+impl NonceAccount {
+    /// Creates new `NonceAccount`
+    pub fn new() -> Self {
+        Self {
+            version: NONCE_ACCOUNT_VERSION,
+            nonce: 0,
+        }
+    }
+}
+
+impl Default for NonceAccount {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sealed for NonceAccount {}
+impl Pack for NonceAccount {
+    // 1 + 8
+    const LEN: usize = 9;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for NonceAccount {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
@@ -0,0 +1,9 @@
+//! Checks of well-known sysvar and program accounts
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+pub use audius_secp_utils::{assert_instructions_sysvar, assert_rent_sysvar, assert_system_program};
+
+/// Assert `account_info` is the SPL token program
+pub fn assert_spl_token_program(account_info: &AccountInfo) -> ProgramResult {
+    audius_secp_utils::assert_program(account_info, &spl_token::id())
+}
@@ -1,3 +1,4 @@
 //! Utils
 
+pub mod accounts;
 pub mod program;
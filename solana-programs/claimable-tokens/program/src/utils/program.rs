@@ -1,10 +1,11 @@
 #![allow(missing_docs)]
 
 //! Extended functionality for Pubkey
+pub use audius_secp_utils::EthereumAddress;
 use solana_program::{pubkey::Pubkey, pubkey::PubkeyError};
 
-/// Represent Ethereum pubkey bytes
-pub type EthereumAddress = [u8; 20];
+/// Seed prefix of the user bank nonce account
+pub const NONCE_ACCOUNT_PREFIX: &[u8; 2] = b"N_";
 
 /// Base PDA related with some mint
 pub struct Base {
@@ -60,3 +61,20 @@ pub fn find_derived_address(
     let seed = bs58::encode(eth_public_key).into_string();
     Pubkey::create_with_seed(base, seed.as_str(), &spl_token::id()).map(|i| (i, seed))
 }
+
+/// Return nonce account address corresponding to specific mint and
+/// ethereum account and it bump seed
+pub fn find_nonce_address(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    eth_public_key: EthereumAddress,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            &mint.to_bytes()[..32],
+            NONCE_ACCOUNT_PREFIX.as_ref(),
+            eth_public_key.as_ref(),
+        ],
+        program_id,
+    )
+}
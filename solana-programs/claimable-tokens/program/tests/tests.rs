@@ -1,13 +1,18 @@
 #![cfg(feature = "test-bpf")]
 
+use audius_secp_utils::eip191_message;
 use claimable_tokens::error::ClaimableProgramError;
-use claimable_tokens::utils::program::{find_address_pair, EthereumAddress};
+use claimable_tokens::state::NonceAccount;
+use claimable_tokens::utils::program::{find_address_pair, find_nonce_address, EthereumAddress};
 use claimable_tokens::*;
 use libsecp256k1::{PublicKey, SecretKey};
 use rand::prelude::ThreadRng;
 use rand::{thread_rng, Rng};
 use solana_program::instruction::InstructionError;
-use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program::{
+    instruction::Instruction, program_pack::Pack, pubkey::Pubkey, secp256k1_program,
+    system_instruction,
+};
 use solana_program_test::*;
 use solana_sdk::transaction::TransactionError;
 use solana_sdk::{
@@ -219,7 +224,7 @@ async fn prepare_claim(
     mint_authority: Keypair,
     eth_address: EthereumAddress,
     user_token_account: &Keypair,
-) -> (Pubkey, Pubkey, u64) {
+) -> (Pubkey, Pubkey, Pubkey, u64) {
     create_mint(
         program_context,
         &mint_account,
@@ -255,7 +260,14 @@ async fn prepare_claim(
     .await
     .unwrap();
 
-    (pair.base.address, pair.derive.address, tokens_amount)
+    let (nonce_acc, _) = find_nonce_address(&id(), &mint_account.pubkey(), eth_address);
+
+    (
+        pair.base.address,
+        pair.derive.address,
+        nonce_acc,
+        tokens_amount,
+    )
 }
 
 #[tokio::test]
@@ -294,6 +306,73 @@ async fn test_init_instruction() {
         spl_token::state::Account::unpack(&token_account_data.data.as_slice()).unwrap();
 
     assert_eq!(token_account.mint, mint_account.pubkey());
+
+    let (nonce_acc, _) = find_nonce_address(&id(), &mint_account.pubkey(), eth_address);
+    let nonce_account_data = get_account(&mut program_context, &nonce_acc).await;
+    // check that nonce account is initialized
+    let nonce_account = NonceAccount::unpack(&nonce_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(nonce_account.nonce, 0);
+}
+
+#[tokio::test]
+async fn test_init_with_prefunded_accounts() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        _rng,
+        _key,
+        _priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        _user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    create_mint(
+        &mut program_context,
+        &mint_account,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let pair = find_address_pair(&id(), &mint_account.pubkey(), eth_address).unwrap();
+    let (nonce_acc, _) = find_nonce_address(&id(), &mint_account.pubkey(), eth_address);
+
+    // Anyone can send lamports to the addresses before the bank is created
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::transfer(&program_context.payer.pubkey(), &nonce_acc, 1),
+            system_instruction::transfer(&program_context.payer.pubkey(), &pair.derive.address, 1),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    init_user_bank(&mut program_context, &mint_account.pubkey(), eth_address)
+        .await
+        .unwrap();
+
+    let token_account_data = get_account(&mut program_context, &pair.derive.address).await;
+    assert_eq!(token_account_data.owner, spl_token::id());
+    assert!(rent.is_exempt(token_account_data.lamports, spl_token::state::Account::LEN));
+    let token_account =
+        spl_token::state::Account::unpack(&token_account_data.data.as_slice()).unwrap();
+    assert_eq!(token_account.mint, mint_account.pubkey());
+
+    let nonce_account_data = get_account(&mut program_context, &nonce_acc).await;
+    assert_eq!(nonce_account_data.owner, id());
+    assert!(rent.is_exempt(nonce_account_data.lamports, NonceAccount::LEN));
+    let nonce_account = NonceAccount::unpack(&nonce_account_data.data.as_slice()).unwrap();
+    assert_eq!(nonce_account.nonce, 0);
 }
 
 #[tokio::test]
@@ -311,10 +390,7 @@ async fn test_claim_all_instruction() {
         eth_address,
     ) = init_test_variables();
 
-    let message = user_token_account.pubkey().to_bytes();
-    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
-
-    let (base_acc, address_to_create, tokens_amount) = prepare_claim(
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
         &mut program_context,
         mint_account,
         rent,
@@ -333,6 +409,15 @@ async fn test_claim_all_instruction() {
         bank_token_account.amount, address_to_create
     );
 
+    let message = instruction::transfer_message(
+        &id(),
+        &user_token_account.pubkey(),
+        bank_token_account.amount,
+        0,
+    )
+    .unwrap();
+    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
+
     // Transfer ALL tokens
     let mut transaction = Transaction::new_with_payer(
         &[
@@ -341,6 +426,7 @@ async fn test_claim_all_instruction() {
                 &id(),
                 &address_to_create,
                 &user_token_account.pubkey(),
+                &nonce_acc,
                 &base_acc,
                 instruction::Transfer {
                     eth_address,
@@ -388,9 +474,7 @@ async fn test_claim_with_amount_instruction() {
         eth_address,
     ) = init_test_variables();
 
-    let message = user_token_account.pubkey().to_bytes();
-    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
-    let (base_acc, address_to_create, tokens_amount) = prepare_claim(
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
         &mut program_context,
         mint_account,
         rent,
@@ -401,6 +485,11 @@ async fn test_claim_with_amount_instruction() {
     .await;
     let transfer_amount = rand::thread_rng().gen_range(1..tokens_amount);
 
+    let message =
+        instruction::transfer_message(&id(), &user_token_account.pubkey(), transfer_amount, 0)
+            .unwrap();
+    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
+
     let mut transaction = Transaction::new_with_payer(
         &[
             secp256_program_instruction,
@@ -408,6 +497,7 @@ async fn test_claim_with_amount_instruction() {
                 &id(),
                 &address_to_create,
                 &user_token_account.pubkey(),
+                &nonce_acc,
                 &base_acc,
                 instruction::Transfer {
                     eth_address,
@@ -441,7 +531,7 @@ async fn test_claim_with_amount_instruction() {
 }
 
 #[tokio::test]
-async fn test_claim_with_zero_amount_failure() {
+async fn test_claim_with_eip191_message_instruction() {
     let mut program_context = program_test().start_with_context().await;
     let rent = program_context.banks_client.get_rent().await.unwrap();
     let (
@@ -455,9 +545,7 @@ async fn test_claim_with_zero_amount_failure() {
         eth_address,
     ) = init_test_variables();
 
-    let message = user_token_account.pubkey().to_bytes();
-    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
-    let (base_acc, address_to_create, _tokens_amount) = prepare_claim(
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
         &mut program_context,
         mint_account,
         rent,
@@ -466,7 +554,15 @@ async fn test_claim_with_zero_amount_failure() {
         &user_token_account,
     )
     .await;
-    let transfer_amount = 0;
+    let transfer_amount = rand::thread_rng().gen_range(1..tokens_amount);
+
+    // Sign the message the way a wallet's personal_sign would
+    let message =
+        instruction::transfer_message(&id(), &user_token_account.pubkey(), transfer_amount, 0)
+            .unwrap();
+    let secp256_program_instruction =
+        new_secp256k1_instruction(&priv_key, &eip191_message(&message));
+
     let mut transaction = Transaction::new_with_payer(
         &[
             secp256_program_instruction,
@@ -474,6 +570,7 @@ async fn test_claim_with_zero_amount_failure() {
                 &id(),
                 &address_to_create,
                 &user_token_account.pubkey(),
+                &nonce_acc,
                 &base_acc,
                 instruction::Transfer {
                     eth_address,
@@ -484,25 +581,404 @@ async fn test_claim_with_zero_amount_failure() {
         ],
         Some(&program_context.payer.pubkey()),
     );
+
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let bank_token_account_data = get_account(&mut program_context, &address_to_create).await;
+    let bank_token_account =
+        spl_token::state::Account::unpack(&bank_token_account_data.data.as_slice()).unwrap();
+    assert_eq!(bank_token_account.amount, tokens_amount - transfer_amount);
+
+    let user_token_account_data =
+        get_account(&mut program_context, &user_token_account.pubkey()).await;
+    let user_token_account =
+        spl_token::state::Account::unpack(&user_token_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(user_token_account.amount, transfer_amount);
+}
+
+#[tokio::test]
+async fn test_claim_with_eip712_message_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        _rng,
+        _key,
+        priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
+        &mut program_context,
+        mint_account,
+        rent,
+        mint_authority,
+        eth_address,
+        &user_token_account,
+    )
+    .await;
+    let transfer_amount = rand::thread_rng().gen_range(1..tokens_amount);
+
+    // Sign the typed data the way a wallet's eth_signTypedData would
+    let message = instruction::transfer_typed_data_message(
+        &id(),
+        &user_token_account.pubkey(),
+        transfer_amount,
+        0,
+        CLUSTER_ID,
+    );
+    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::transfer(
+                &id(),
+                &address_to_create,
+                &user_token_account.pubkey(),
+                &nonce_acc,
+                &base_acc,
+                instruction::Transfer {
+                    eth_address,
+                    amount: transfer_amount,
+                },
+            )
+            .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let bank_token_account_data = get_account(&mut program_context, &address_to_create).await;
+    let bank_token_account =
+        spl_token::state::Account::unpack(&bank_token_account_data.data.as_slice()).unwrap();
+    assert_eq!(bank_token_account.amount, tokens_amount - transfer_amount);
+
+    let user_token_account_data =
+        get_account(&mut program_context, &user_token_account.pubkey()).await;
+    let user_token_account =
+        spl_token::state::Account::unpack(&user_token_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(user_token_account.amount, transfer_amount);
+}
+
+/// Merges single signature secp instructions into one checking all the signatures,
+/// which must be the first instruction of the transaction
+fn merge_secp256k1_instructions(instructions: &[Instruction]) -> Instruction {
+    const OFFSETS_SIZE: usize = 11;
+    let data_start = 1 + instructions.len() * OFFSETS_SIZE;
+
+    let mut offsets = vec![instructions.len() as u8];
+    let mut signatures = Vec::new();
+    for instruction in instructions {
+        // Move the signature, eth address and message offsets past the preceding signatures
+        let shift = (data_start + signatures.len() - (1 + OFFSETS_SIZE)) as u16;
+        let mut signature_offsets = instruction.data[1..1 + OFFSETS_SIZE].to_vec();
+        for position in [0, 3, 6] {
+            let offset =
+                u16::from_le_bytes([signature_offsets[position], signature_offsets[position + 1]]);
+            signature_offsets[position..position + 2]
+                .copy_from_slice(&(offset + shift).to_le_bytes());
+        }
+        offsets.extend_from_slice(&signature_offsets);
+        signatures.extend_from_slice(&instruction.data[1 + OFFSETS_SIZE..]);
+    }
+
+    Instruction {
+        program_id: secp256k1_program::id(),
+        accounts: vec![],
+        data: [offsets, signatures].concat(),
+    }
+}
+
+#[tokio::test]
+async fn test_claim_with_multiple_signatures_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        mut rng,
+        _key,
+        priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
+        &mut program_context,
+        mint_account,
+        rent,
+        mint_authority,
+        eth_address,
+        &user_token_account,
+    )
+    .await;
+
+    // The user's signature comes after another one in the same secp instruction
+    let message =
+        instruction::transfer_message(&id(), &user_token_account.pubkey(), tokens_amount, 0)
+            .unwrap();
+    let other_key = SecretKey::parse(&rng.gen()).unwrap();
+    let secp256_program_instruction = merge_secp256k1_instructions(&[
+        new_secp256k1_instruction(&other_key, b"unrelated message"),
+        new_secp256k1_instruction(&priv_key, &message),
+    ]);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::transfer(
+                &id(),
+                &address_to_create,
+                &user_token_account.pubkey(),
+                &nonce_acc,
+                &base_acc,
+                instruction::Transfer {
+                    eth_address,
+                    amount: tokens_amount,
+                },
+            )
+            .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let user_token_account_data =
+        get_account(&mut program_context, &user_token_account.pubkey()).await;
+    let user_token_account =
+        spl_token::state::Account::unpack(&user_token_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(user_token_account.amount, tokens_amount);
+}
+
+#[tokio::test]
+async fn test_claim_replay_failure() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        _rng,
+        _key,
+        priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
+        &mut program_context,
+        mint_account,
+        rent,
+        mint_authority,
+        eth_address,
+        &user_token_account,
+    )
+    .await;
+    let transfer_amount = tokens_amount / 4;
+
+    let message =
+        instruction::transfer_message(&id(), &user_token_account.pubkey(), transfer_amount, 0)
+            .unwrap();
+    let instructions = [
+        new_secp256k1_instruction(&priv_key, &message),
+        instruction::transfer(
+            &id(),
+            &address_to_create,
+            &user_token_account.pubkey(),
+            &nonce_acc,
+            &base_acc,
+            instruction::Transfer {
+                eth_address,
+                amount: transfer_amount,
+            },
+        )
+        .unwrap(),
+    ];
+
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&program_context.payer.pubkey()));
     transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let nonce_account_data = get_account(&mut program_context, &nonce_acc).await;
+    let nonce_account = NonceAccount::unpack(&nonce_account_data.data.as_slice()).unwrap();
+    // check that nonce increased after transfer
+    assert_eq!(nonce_account.nonce, 1);
+
+    // Submit the same signed message again
+    program_context.warp_to_slot(5).unwrap();
+    let recent_blockhash = program_context
+        .banks_client
+        .get_recent_blockhash()
+        .await
+        .unwrap();
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&program_context.payer.pubkey()));
+    transaction.sign(&[&program_context.payer], recent_blockhash);
     let tx_result = program_context
         .banks_client
         .process_transaction(transaction)
         .await;
-    assert!(tx_result.is_err());
-    // Confirm a transfer of 0 is not permitted
-    match tx_result {
-        Err(TransportError::TransactionError(TransactionError::InstructionError(
-            _idx,
-            InstructionError::InsufficientFunds,
-        ))) => {
-            println!(
-                "Insufficient funds error found as expected: {:?}",
-                tx_result
-            );
+    assert_custom_error(
+        tx_result,
+        1,
+        ClaimableProgramError::SignatureVerificationFailed,
+    );
+
+    // Sign a larger amount than the one being transferred
+    let message =
+        instruction::transfer_message(&id(), &user_token_account.pubkey(), transfer_amount * 2, 1)
+            .unwrap();
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            new_secp256k1_instruction(&priv_key, &message),
+            instruction::transfer(
+                &id(),
+                &address_to_create,
+                &user_token_account.pubkey(),
+                &nonce_acc,
+                &base_acc,
+                instruction::Transfer {
+                    eth_address,
+                    amount: transfer_amount,
+                },
+            )
+            .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(&[&program_context.payer], recent_blockhash);
+    let tx_result = program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await;
+    assert_custom_error(
+        tx_result,
+        1,
+        ClaimableProgramError::SignatureVerificationFailed,
+    );
+
+    let bank_token_account_data = get_account(&mut program_context, &address_to_create).await;
+    let bank_token_account =
+        spl_token::state::Account::unpack(&bank_token_account_data.data.as_slice()).unwrap();
+    // check that only the first transfer went through
+    assert_eq!(bank_token_account.amount, tokens_amount - transfer_amount);
+}
+
+#[tokio::test]
+async fn test_claim_with_zero_amount_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        _rng,
+        _key,
+        priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
+        &mut program_context,
+        mint_account,
+        rent,
+        mint_authority,
+        eth_address,
+        &user_token_account,
+    )
+    .await;
+
+    // Zero amount claims all tokens without querying the balance
+    for nonce in 0..2 {
+        let message =
+            instruction::transfer_message(&id(), &user_token_account.pubkey(), 0, nonce).unwrap();
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                new_secp256k1_instruction(&priv_key, &message),
+                instruction::transfer(
+                    &id(),
+                    &address_to_create,
+                    &user_token_account.pubkey(),
+                    &nonce_acc,
+                    &base_acc,
+                    instruction::Transfer {
+                        eth_address,
+                        amount: 0,
+                    },
+                )
+                .unwrap(),
+            ],
+            Some(&program_context.payer.pubkey()),
+        );
+        transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+        let tx_result = program_context
+            .banks_client
+            .process_transaction(transaction)
+            .await;
+
+        if nonce == 0 {
+            tx_result.unwrap();
+            continue;
+        }
+
+        // Confirm claiming from an empty bank is not permitted
+        match tx_result {
+            Err(TransportError::TransactionError(TransactionError::InstructionError(
+                _idx,
+                InstructionError::InsufficientFunds,
+            ))) => {
+                println!(
+                    "Insufficient funds error found as expected: {:?}",
+                    tx_result
+                );
+            }
+            _ => panic!("Unexpected error scenario"),
         }
-        _ => panic!("Unexpected error scenario"),
     }
+
+    let bank_token_account_data = get_account(&mut program_context, &address_to_create).await;
+    let bank_token_account =
+        spl_token::state::Account::unpack(&bank_token_account_data.data.as_slice()).unwrap();
+    // check that program sent all the tokens from bank token account to user token account
+    assert_eq!(bank_token_account.amount, 0);
+
+    let user_token_account_data =
+        get_account(&mut program_context, &user_token_account.pubkey()).await;
+    let user_token_account =
+        spl_token::state::Account::unpack(&user_token_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(user_token_account.amount, tokens_amount);
 }
 
 #[tokio::test]
@@ -524,7 +1000,7 @@ async fn test_claim_with_wrong_signature_instruction() {
     let bad_message = [8u8; 30];
     let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &bad_message);
 
-    let (base_acc, address_to_create, tokens_amount) = prepare_claim(
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
         &mut program_context,
         mint_account,
         rent,
@@ -541,6 +1017,82 @@ async fn test_claim_with_wrong_signature_instruction() {
                 &id(),
                 &address_to_create,
                 &user_token_account.pubkey(),
+                &nonce_acc,
+                &base_acc,
+                instruction::Transfer {
+                    eth_address,
+                    amount: 0,
+                },
+            )
+            .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    let tx_result = program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await;
+    assert!(tx_result.is_err());
+
+    let bank_token_account_data = get_account(&mut program_context, &address_to_create).await;
+    let bank_token_account =
+        spl_token::state::Account::unpack(&bank_token_account_data.data.as_slice()).unwrap();
+    // check that bank token accounts balance the same
+    assert_eq!(bank_token_account.amount, tokens_amount);
+
+    let user_token_account_data =
+        get_account(&mut program_context, &user_token_account.pubkey()).await;
+    let user_token_account =
+        spl_token::state::Account::unpack(&user_token_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(user_token_account.amount, 0);
+}
+
+#[tokio::test]
+async fn test_claim_with_eip712_message_for_other_cluster_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        _rng,
+        _key,
+        priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
+        &mut program_context,
+        mint_account,
+        rent,
+        mint_authority,
+        eth_address,
+        &user_token_account,
+    )
+    .await;
+
+    // Typed data signed for a different cluster
+    let bad_message = instruction::transfer_typed_data_message(
+        &id(),
+        &user_token_account.pubkey(),
+        0,
+        0,
+        CLUSTER_ID + 1,
+    );
+    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &bad_message);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            secp256_program_instruction,
+            instruction::transfer(
+                &id(),
+                &address_to_create,
+                &user_token_account.pubkey(),
+                &nonce_acc,
                 &base_acc,
                 instruction::Transfer {
                     eth_address,
@@ -588,10 +1140,10 @@ async fn test_claim_with_wrong_token_account() {
         eth_address,
     ) = init_test_variables();
 
-    let message = user_token_account.pubkey().to_bytes();
+    let message = instruction::transfer_message(&id(), &user_token_account.pubkey(), 0, 0).unwrap();
     let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
 
-    let (base_acc, address_to_create, _) = prepare_claim(
+    let (base_acc, address_to_create, nonce_acc, _) = prepare_claim(
         &mut program_context,
         mint_account,
         rent,
@@ -609,6 +1161,7 @@ async fn test_claim_with_wrong_token_account() {
                 &address_to_create,
                 // use incorrect user token account
                 &Keypair::new().pubkey(),
+                &nonce_acc,
                 &base_acc,
                 instruction::Transfer {
                     eth_address,
@@ -649,7 +1202,7 @@ async fn test_missing_secp_instruction() {
         user_token_account,
         eth_address,
     ) = init_test_variables();
-    let (base_acc, address_to_create, tokens_amount) = prepare_claim(
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
         &mut program_context,
         mint_account,
         rent,
@@ -666,6 +1219,7 @@ async fn test_missing_secp_instruction() {
             &id(),
             &address_to_create,
             &user_token_account.pubkey(),
+            &nonce_acc,
             &base_acc,
             instruction::Transfer {
                 eth_address,
@@ -690,3 +1244,129 @@ async fn test_missing_secp_instruction() {
         ClaimableProgramError::Secp256InstructionLosing,
     );
 }
+
+async fn assert_spoofed_account_rejected(
+    program_context: &mut ProgramTestContext,
+    mut instructions: Vec<solana_program::instruction::Instruction>,
+    position: usize,
+    expected_error: InstructionError,
+) {
+    // Replace the account with a random one in the last instruction
+    let instruction_index = instructions.len() - 1;
+    instructions[instruction_index].accounts[position].pubkey = Pubkey::new_unique();
+
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&program_context.payer.pubkey()));
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    let tx_result = program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await;
+
+    assert_eq!(
+        tx_result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(instruction_index as u8, expected_error)
+    );
+}
+
+#[tokio::test]
+async fn test_init_with_spoofed_accounts() {
+    let mut program_context = program_test().start_with_context().await;
+    let (
+        _rng,
+        _key,
+        _priv_key,
+        _secp_pubkey,
+        mint_account,
+        _mint_authority,
+        _user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let init = instruction::init(
+        &id(),
+        &program_context.payer.pubkey(),
+        &mint_account.pubkey(),
+        instruction::CreateTokenAccount { eth_address },
+    )
+    .unwrap();
+
+    for (position, expected_error) in [
+        // rent sysvar
+        (4, InstructionError::InvalidArgument),
+        // SPL token program
+        (5, InstructionError::IncorrectProgramId),
+        // system program
+        (6, InstructionError::IncorrectProgramId),
+    ] {
+        assert_spoofed_account_rejected(
+            &mut program_context,
+            vec![init.clone()],
+            position,
+            expected_error,
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_claim_with_spoofed_accounts() {
+    let mut program_context = program_test().start_with_context().await;
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let (
+        _rng,
+        _key,
+        priv_key,
+        _secp_pubkey,
+        mint_account,
+        mint_authority,
+        user_token_account,
+        eth_address,
+    ) = init_test_variables();
+
+    let message = instruction::transfer_message(&id(), &user_token_account.pubkey(), 0, 0).unwrap();
+    let secp256_program_instruction = new_secp256k1_instruction(&priv_key, &message);
+
+    let (base_acc, address_to_create, nonce_acc, tokens_amount) = prepare_claim(
+        &mut program_context,
+        mint_account,
+        rent,
+        mint_authority,
+        eth_address,
+        &user_token_account,
+    )
+    .await;
+
+    let transfer = instruction::transfer(
+        &id(),
+        &address_to_create,
+        &user_token_account.pubkey(),
+        &nonce_acc,
+        &base_acc,
+        instruction::Transfer {
+            eth_address,
+            amount: 0,
+        },
+    )
+    .unwrap();
+
+    for (position, expected_error) in [
+        // instructions sysvar
+        (3, InstructionError::InvalidArgument),
+        // SPL token program
+        (4, InstructionError::IncorrectProgramId),
+    ] {
+        assert_spoofed_account_rejected(
+            &mut program_context,
+            vec![secp256_program_instruction.clone(), transfer.clone()],
+            position,
+            expected_error,
+        )
+        .await;
+    }
+
+    let bank_token_account_data = get_account(&mut program_context, &address_to_create).await;
+    let bank_token_account =
+        spl_token::state::Account::unpack(&bank_token_account_data.data.as_slice()).unwrap();
+    assert_eq!(bank_token_account.amount, tokens_amount);
+}
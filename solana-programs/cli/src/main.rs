@@ -1,14 +1,32 @@
 use audius_eth_registry::{
     instruction::{
+        accept_signer_group_ownership,
+        action_message,
+        add_signer_action,
+        clear_signer_action,
         clear_valid_signer,
-        init_signer_group,
-        init_valid_signer,
+        close_used_message,
+        create_signer_group,
+        create_valid_signer,
+        enable_owner_action,
+        enable_signer_group_owner,
+        find_signer_group_address,
+        find_valid_signer_address,
+        migrate_signer_group,
+        migrate_valid_signer,
+        set_threshold,
+        set_threshold_action,
+        transfer_signer_group_ownership,
+        validate_quorum_create_signer,
+        validate_quorum_clear_valid_signer,
+        validate_quorum_set_threshold,
         validate_signature,
         disable_signer_group_owner,
         SignatureData,
     },
-    state::{SecpSignatureOffsets, SignerGroup, ValidSigner},
+    state::{SecpSignatureOffsets, SignerGroup, UsedMessage, ValidSigner},
 };
+use audius_secp_utils::client::new_secp256k1_instruction_2_0;
 use borsh::BorshDeserialize;
 use clap::{
     crate_description, crate_name, crate_version, value_t, value_t_or_exit, App, AppSettings, Arg,
@@ -16,22 +34,28 @@ use clap::{
 };
 use hex::FromHex;
 use libsecp256k1::SecretKey;
+use serde::Deserialize;
 use solana_clap_utils::{
     input_parsers::pubkey_of,
     input_validators::{is_keypair, is_pubkey, is_url},
     keypair::signer_from_path,
 };
 use solana_client::rpc_client::RpcClient;
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::Instruction,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     native_token::lamports_to_sol,
-    secp256k1_instruction,
-    signature::{Keypair, Signer},
-    system_instruction,
+    signature::Signer,
     transaction::Transaction,
 };
-use std::process::exit;
+use std::{
+    process::exit,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 #[allow(dead_code)]
 struct Config {
@@ -45,6 +69,13 @@ struct Config {
 type Error = Box<dyn std::error::Error>;
 type CommandResult = Result<Option<Transaction>, Error>;
 
+/// Valid signer and its Ethereum secret key, read from a csv file
+#[derive(Debug, Deserialize)]
+struct SignerData {
+    valid_signer: String,
+    eth_secret: String,
+}
+
 fn is_hex(s: String) -> Result<(), String> {
     if hex::decode(s).is_err() {
         Err(String::from("Wrong address format"))
@@ -53,6 +84,64 @@ fn is_hex(s: String) -> Result<(), String> {
     }
 }
 
+fn is_csv_file(s: String) -> Result<(), String> {
+    if s.ends_with(".csv") {
+        Ok(())
+    } else {
+        Err(String::from("Receive wrong path to csv file"))
+    }
+}
+
+/// Reads valid signers and their secret keys from `signers_secrets`
+fn read_signers(signers_secrets: &str) -> Result<(Vec<Pubkey>, Vec<SecretKey>), Error> {
+    let mut valid_signers = Vec::new();
+    let mut secrets = Vec::new();
+
+    println!("Reading secrets from: {:?}", signers_secrets);
+    let mut rdr = csv::Reader::from_path(signers_secrets)?;
+    for record in rdr.deserialize() {
+        let signer_data: SignerData = record?;
+        let decoded_secret = <[u8; 32]>::from_hex(signer_data.eth_secret)
+            .expect("Secp256k1 secret key decoding failed");
+
+        valid_signers.push(Pubkey::from_str(&signer_data.valid_signer)?);
+        secrets.push(SecretKey::parse(&decoded_secret)?);
+    }
+
+    Ok((valid_signers, secrets))
+}
+
+/// Signs `action` at the current timestamp with each secret, one secp
+/// instruction per secret at the start of the transaction
+fn sign_action(
+    secrets: &[SecretKey],
+    action: &[u8],
+) -> Result<(Vec<Instruction>, Vec<SignatureData>), Error> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let message = action_message(action, timestamp);
+
+    let mut instructions = Vec::new();
+    let mut signatures_data = Vec::new();
+    for (index, secret) in secrets.iter().enumerate() {
+        let secp256_program_instruction =
+            new_secp256k1_instruction_2_0(secret, &message, index as u8);
+
+        let start = 1;
+        let end = start + SecpSignatureOffsets::SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let offsets =
+            SecpSignatureOffsets::try_from_slice(&secp256_program_instruction.data[start..end])?;
+        let sig_end = offsets.signature_offset as usize + SecpSignatureOffsets::SECP_SIGNATURE_SIZE;
+
+        signatures_data.push(SignatureData {
+            recovery_id: secp256_program_instruction.data[sig_end],
+            message: message.clone(),
+        });
+        instructions.push(secp256_program_instruction);
+    }
+
+    Ok((instructions, signatures_data))
+}
+
 fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
     let balance = config.rpc_client.get_balance(&config.fee_payer.pubkey())?;
     if balance < required_balance {
@@ -68,32 +157,28 @@ fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(),
     }
 }
 
-fn command_create_signer_group(config: &Config) -> CommandResult {
-    let signer_group = Keypair::new();
-    println!(
-        "Creating new signer group account {}",
-        signer_group.pubkey()
+fn command_create_signer_group(config: &Config, seed: String) -> CommandResult {
+    if seed.len() > MAX_SEED_LEN {
+        return Err(format!("Seed is longer than {} bytes", MAX_SEED_LEN).into());
+    }
+
+    let (signer_group, _) = find_signer_group_address(
+        &audius_eth_registry::id(),
+        &config.owner.pubkey(),
+        seed.as_bytes(),
     );
+    println!("Creating new signer group account {}", signer_group);
 
     let signer_group_account_balance = config
         .rpc_client
         .get_minimum_balance_for_rent_exemption(SignerGroup::LEN)?;
     let mut transaction = Transaction::new_with_payer(
-        &[
-            system_instruction::create_account(
-                &config.fee_payer.pubkey(),
-                &signer_group.pubkey(),
-                signer_group_account_balance,
-                SignerGroup::LEN as u64,
-                &audius_eth_registry::id(),
-            ),
-            init_signer_group(
-                &audius_eth_registry::id(),
-                &signer_group.pubkey(),
-                &config.owner.pubkey(),
-            )
-            .unwrap(),
-        ],
+        &[create_signer_group(
+            &audius_eth_registry::id(),
+            &config.fee_payer.pubkey(),
+            &config.owner.pubkey(),
+            seed.as_bytes(),
+        )?],
         Some(&config.fee_payer.pubkey()),
     );
 
@@ -103,10 +188,7 @@ fn command_create_signer_group(config: &Config) -> CommandResult {
         fee_calculator.calculate_fee(&transaction.message()) + signer_group_account_balance,
     )?;
 
-    transaction.sign(
-        &[config.fee_payer.as_ref(), &signer_group],
-        recent_blockhash,
-    );
+    transaction.sign(&[config.fee_payer.as_ref()], recent_blockhash);
     Ok(Some(transaction))
 }
 
@@ -124,7 +206,7 @@ fn command_query_eth_registry(
 
         // Attempt tor ecover data
         let signer_group_data =
-            SignerGroup::try_from_slice(&account.data.as_slice());
+            SignerGroup::unpack(&account.data.as_slice());
 
         if !signer_group_data.is_err() {
             let parsed_data = signer_group_data.unwrap();
@@ -159,13 +241,16 @@ fn command_query_signer_group(
 
     let signer_acc = config.rpc_client.get_account_data(signer_group)?;
     let signer_group_data =
-        SignerGroup::try_from_slice(&signer_acc.as_slice()).unwrap();
+        SignerGroup::unpack(&signer_acc.as_slice()).unwrap();
 
     println!(
         "SignerGroup@{}, {:?}",
         signer_group,
         signer_group_data
     );
+    if signer_group_data.pending_owner != Pubkey::default() {
+        println!("Pending owner: {}", signer_group_data.pending_owner);
+    }
 
     // TODO: Make this not an error
     Err(format!(
@@ -201,41 +286,110 @@ fn command_disable_signer_group_owner(
     Ok(Some(transaction))
 }
 
-fn command_create_valid_signer(
+fn command_transfer_signer_group_ownership(
     config: &Config,
     signer_group: &Pubkey,
-    eth_address: String,
+    new_owner: &Pubkey,
 ) -> CommandResult {
-    let valid_signer = Keypair::new();
-    println!(
-        "Creating new valid signer account {}",
-        valid_signer.pubkey()
+    let mut transaction = Transaction::new_with_payer(
+        &[transfer_signer_group_ownership(
+            &audius_eth_registry::id(),
+            signer_group,
+            &config.owner.pubkey(),
+            new_owner,
+        )?],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(
+        &[config.fee_payer.as_ref(), config.owner.as_ref()],
+        recent_blockhash,
     );
+    Ok(Some(transaction))
+}
+
+fn command_accept_signer_group_ownership(
+    config: &Config,
+    signer_group: &Pubkey,
+) -> CommandResult {
+    let mut transaction = Transaction::new_with_payer(
+        &[accept_signer_group_ownership(
+            &audius_eth_registry::id(),
+            signer_group,
+            &config.owner.pubkey(),
+        )?],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(
+        &[
+            config.fee_payer.as_ref(),
+            config.owner.as_ref() // pending owner key
+        ],
+        recent_blockhash,
+    );
+    Ok(Some(transaction))
+}
 
+fn command_enable_signer_group_owner(
+    config: &Config,
+    signer_group: &Pubkey,
+    signers_secrets: String,
+) -> CommandResult {
+    let signer_group_data = config.rpc_client.get_account_data(signer_group)?;
+    let signer_group_data = SignerGroup::unpack(signer_group_data.as_slice())?;
+
+    let (valid_signers, secrets) = read_signers(&signers_secrets)?;
+    let (mut instructions, signatures_data) = sign_action(
+        &secrets,
+        &enable_owner_action(signer_group, &signer_group_data.owner),
+    )?;
+    instructions.push(enable_signer_group_owner(
+        &audius_eth_registry::id(),
+        signer_group,
+        &valid_signers,
+        signatures_data,
+    )?);
+
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&config.fee_payer.pubkey()));
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(&[config.fee_payer.as_ref()], recent_blockhash);
+    Ok(Some(transaction))
+}
+
+fn command_create_valid_signer(
+    config: &Config,
+    signer_group: &Pubkey,
+    eth_address: String,
+) -> CommandResult {
     let decoded_address = <[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]>::from_hex(eth_address)
         .expect("Ethereum address decoding failed");
 
+    let (valid_signer, _) =
+        find_valid_signer_address(&audius_eth_registry::id(), signer_group, &decoded_address);
+    println!("Creating new valid signer account {}", valid_signer);
+
     let valid_signer_account_balance = config
         .rpc_client
         .get_minimum_balance_for_rent_exemption(ValidSigner::LEN)?;
     let mut transaction = Transaction::new_with_payer(
-        &[
-            system_instruction::create_account(
-                &config.fee_payer.pubkey(),
-                &valid_signer.pubkey(),
-                valid_signer_account_balance,
-                ValidSigner::LEN as u64,
-                &audius_eth_registry::id(),
-            ),
-            init_valid_signer(
-                &audius_eth_registry::id(),
-                &valid_signer.pubkey(),
-                signer_group,
-                &config.owner.pubkey(),
-                decoded_address,
-            )
-            .unwrap(),
-        ],
+        &[create_valid_signer(
+            &audius_eth_registry::id(),
+            &config.fee_payer.pubkey(),
+            signer_group,
+            &config.owner.pubkey(),
+            decoded_address,
+        )?],
         Some(&config.fee_payer.pubkey()),
     );
 
@@ -246,11 +400,7 @@ fn command_create_valid_signer(
     )?;
 
     transaction.sign(
-        &[
-            config.fee_payer.as_ref(),
-            config.owner.as_ref(),
-            &valid_signer,
-        ],
+        &[config.fee_payer.as_ref(), config.owner.as_ref()],
         recent_blockhash,
     );
     Ok(Some(transaction))
@@ -267,6 +417,7 @@ fn command_clear_valid_signer(config: &Config, valid_signer: &Pubkey) -> Command
             valid_signer,
             &valid_signer_data.signer_group,
             &config.owner.pubkey(),
+            &config.fee_payer.pubkey(),
         )
         .unwrap()],
         Some(&config.fee_payer.pubkey()),
@@ -282,6 +433,245 @@ fn command_clear_valid_signer(config: &Config, valid_signer: &Pubkey) -> Command
     Ok(Some(transaction))
 }
 
+fn command_set_threshold(
+    config: &Config,
+    signer_group: &Pubkey,
+    threshold: u8,
+    signers_secrets: Option<String>,
+) -> CommandResult {
+    let mut signers = vec![config.fee_payer.as_ref()];
+    let instructions = match signers_secrets {
+        Some(signers_secrets) => {
+            let (valid_signers, secrets) = read_signers(&signers_secrets)?;
+            let (mut instructions, signatures_data) =
+                sign_action(&secrets, &set_threshold_action(signer_group, threshold))?;
+            instructions.push(validate_quorum_set_threshold(
+                &audius_eth_registry::id(),
+                signer_group,
+                &valid_signers,
+                signatures_data,
+                threshold,
+            )?);
+            instructions
+        }
+        None => {
+            signers.push(config.owner.as_ref());
+            vec![set_threshold(
+                &audius_eth_registry::id(),
+                signer_group,
+                &config.owner.pubkey(),
+                threshold,
+            )?]
+        }
+    };
+
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&config.fee_payer.pubkey()));
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(&signers, recent_blockhash);
+    Ok(Some(transaction))
+}
+
+fn command_add_valid_signer(
+    config: &Config,
+    signer_group: &Pubkey,
+    eth_address: String,
+    signers_secrets: String,
+) -> CommandResult {
+    let decoded_address = <[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]>::from_hex(eth_address)
+        .expect("Ethereum address decoding failed");
+
+    let (valid_signer, _) =
+        find_valid_signer_address(&audius_eth_registry::id(), signer_group, &decoded_address);
+    println!("Creating new valid signer account {}", valid_signer);
+
+    let valid_signer_account_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(ValidSigner::LEN)?;
+
+    let (valid_signers, secrets) = read_signers(&signers_secrets)?;
+    let (mut instructions, signatures_data) = sign_action(
+        &secrets,
+        &add_signer_action(signer_group, &valid_signer, &decoded_address),
+    )?;
+    instructions.push(validate_quorum_create_signer(
+        &audius_eth_registry::id(),
+        signer_group,
+        &config.fee_payer.pubkey(),
+        &valid_signers,
+        signatures_data,
+        decoded_address,
+    )?);
+
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&config.fee_payer.pubkey()));
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(
+        config,
+        fee_calculator.calculate_fee(&transaction.message()) + valid_signer_account_balance,
+    )?;
+
+    transaction.sign(&[config.fee_payer.as_ref()], recent_blockhash);
+    Ok(Some(transaction))
+}
+
+fn command_migrate_valid_signer(config: &Config, valid_signer: &Pubkey) -> CommandResult {
+    // Get valid signer data
+    let valid_signer_data = config.rpc_client.get_account_data(valid_signer)?;
+    let valid_signer_data = ValidSigner::try_from_slice(valid_signer_data.as_slice())?;
+
+    let (new_valid_signer, _) = find_valid_signer_address(
+        &audius_eth_registry::id(),
+        &valid_signer_data.signer_group,
+        &valid_signer_data.eth_address,
+    );
+    println!("Moving valid signer {} to {}", valid_signer, new_valid_signer);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[migrate_valid_signer(
+            &audius_eth_registry::id(),
+            &config.fee_payer.pubkey(),
+            valid_signer,
+            &valid_signer_data.signer_group,
+            &config.owner.pubkey(),
+            &valid_signer_data.eth_address,
+        )?],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(
+        &[config.fee_payer.as_ref(), config.owner.as_ref()],
+        recent_blockhash,
+    );
+    Ok(Some(transaction))
+}
+
+fn command_migrate_signer_group(config: &Config, signer_group: &Pubkey) -> CommandResult {
+    // Every valid signer of the group is counted by the migration
+    let valid_signers: Vec<Pubkey> = config
+        .rpc_client
+        .get_program_accounts(&audius_eth_registry::id())?
+        .into_iter()
+        .filter(|(_, account)| account.data.len() == ValidSigner::LEN)
+        .filter_map(|(address, account)| {
+            ValidSigner::try_from_slice(account.data.as_slice())
+                .ok()
+                .filter(|valid_signer| {
+                    valid_signer.is_initialized() && valid_signer.signer_group == *signer_group
+                })
+                .map(|_| address)
+        })
+        .collect();
+    println!(
+        "Migrating signer group {} with {} valid signers",
+        signer_group,
+        valid_signers.len()
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[migrate_signer_group(
+            &audius_eth_registry::id(),
+            &config.fee_payer.pubkey(),
+            signer_group,
+            &config.owner.pubkey(),
+            &valid_signers,
+        )?],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(
+        &[config.fee_payer.as_ref(), config.owner.as_ref()],
+        recent_blockhash,
+    );
+    Ok(Some(transaction))
+}
+
+fn command_close_used_message(config: &Config, used_message: &Pubkey) -> CommandResult {
+    // The rent goes back to the account that funded the used message
+    let used_message_data = config.rpc_client.get_account_data(used_message)?;
+    let used_message_data = UsedMessage::try_from_slice(used_message_data.as_slice())?;
+    println!(
+        "Closing used message {}, refunding {}",
+        used_message, used_message_data.funder
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[close_used_message(
+            &audius_eth_registry::id(),
+            used_message,
+            &used_message_data.funder,
+        )?],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(&[config.fee_payer.as_ref()], recent_blockhash);
+    Ok(Some(transaction))
+}
+
+fn command_find_valid_signer(
+    config: &Config,
+    signer_group: &Pubkey,
+    eth_address: String,
+) -> CommandResult {
+    let decoded_address = <[u8; SecpSignatureOffsets::ETH_ADDRESS_SIZE]>::from_hex(eth_address)
+        .expect("Ethereum address decoding failed");
+
+    let (valid_signer, _) =
+        find_valid_signer_address(&audius_eth_registry::id(), signer_group, &decoded_address);
+
+    let valid_signer_data = config.rpc_client.get_account_data(&valid_signer)?;
+    let valid_signer_data = ValidSigner::try_from_slice(valid_signer_data.as_slice())?;
+    println!("ValidSigner@{}, {:?}", valid_signer, valid_signer_data);
+
+    Ok(None)
+}
+
+fn command_remove_valid_signer(
+    config: &Config,
+    valid_signer: &Pubkey,
+    signers_secrets: String,
+) -> CommandResult {
+    // Get valid signer data
+    let valid_signer_data = config.rpc_client.get_account_data(valid_signer)?;
+    let valid_signer_data = ValidSigner::try_from_slice(valid_signer_data.as_slice()).unwrap();
+
+    let (valid_signers, secrets) = read_signers(&signers_secrets)?;
+    let (mut instructions, signatures_data) = sign_action(
+        &secrets,
+        &clear_signer_action(&valid_signer_data.signer_group, valid_signer),
+    )?;
+    instructions.push(validate_quorum_clear_valid_signer(
+        &audius_eth_registry::id(),
+        &valid_signer_data.signer_group,
+        valid_signer,
+        &config.fee_payer.pubkey(),
+        &valid_signers,
+        signatures_data,
+    )?);
+
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&config.fee_payer.pubkey()));
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(&transaction.message()))?;
+
+    transaction.sign(&[config.fee_payer.as_ref()], recent_blockhash);
+    Ok(Some(transaction))
+}
+
 fn command_send_message(
     config: &Config,
     valid_signer: &Pubkey,
@@ -297,8 +687,7 @@ fn command_send_message(
     let private_key = SecretKey::parse(&decoded_secret).unwrap();
     let message = message.as_bytes().to_vec();
 
-    let secp256_program_instruction =
-        secp256k1_instruction::new_secp256k1_instruction(&private_key, &message);
+    let secp256_program_instruction = new_secp256k1_instruction_2_0(&private_key, &message, 0);
 
     let start = 1;
     let end = start + SecpSignatureOffsets::SIGNATURE_OFFSETS_SERIALIZED_SIZE;
@@ -397,7 +786,18 @@ fn main() {
                      Defaults to the client keypair.",
                 ),
         )
-        .subcommand(SubCommand::with_name("create-signer-group").about("Create a new signer group"))
+        .subcommand(
+            SubCommand::with_name("create-signer-group")
+                .about("Create a new signer group at the address derived from the owner and seed")
+                .arg(
+                    Arg::with_name("seed")
+                        .index(1)
+                        .value_name("SEED")
+                        .takes_value(true)
+                        .help("Seed telling apart groups of the same owner, at most 32 bytes. \
+                               Defaults to an empty seed."),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("create-valid-signer")
                 .about("Create new valid signer and add to the signer group")
@@ -446,6 +846,63 @@ fn main() {
                         .help("Signer group which will have the owner key disabled"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("transfer-signer-group-ownership")
+                .about("Propose a new signer group owner, who takes over once it accepts")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Signer group to transfer."),
+                )
+                .arg(
+                    Arg::with_name("new_owner")
+                        .index(2)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Proposed owner."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("accept-signer-group-ownership")
+                .about("Accept the ownership of a signer group as its pending owner, given by --owner")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Signer group to take over."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("enable-signer-group-owner")
+                .about("Re-enable the signer group owner with valid signers' signatures")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Signer group which will have the owner key enabled"),
+                )
+                .arg(
+                    Arg::with_name("signers_secrets")
+                        .long("signers-secrets")
+                        .validator(is_csv_file)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("CSV file with valid signers and their Ethereum secret keys."),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("query-eth-registry")
                 .about("Describes all accounts associated with the audius eth registry")
@@ -463,6 +920,150 @@ fn main() {
                         .help("Account of valid signer to be removed."),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("set-threshold")
+                .about("Set the number of valid signer signatures required to change the signer group")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Signer group to update."),
+                )
+                .arg(
+                    Arg::with_name("threshold")
+                        .index(2)
+                        .value_name("NUMBER")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Number of signatures required."),
+                )
+                .arg(
+                    Arg::with_name("signers_secrets")
+                        .long("signers-secrets")
+                        .validator(is_csv_file)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("CSV file with valid signers and their Ethereum secret keys. \
+                               Defaults to signing as the signer group owner."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("add-valid-signer")
+                .about("Add new valid signer to the signer group with valid signers' signatures")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Group for Valid Signer to join with."),
+                )
+                .arg(
+                    Arg::with_name("eth_address")
+                        .index(2)
+                        .validator(is_hex)
+                        .value_name("ETH_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Ethereum address calculated valid signer's private key (without 0x prefix)."),
+                )
+                .arg(
+                    Arg::with_name("signers_secrets")
+                        .long("signers-secrets")
+                        .validator(is_csv_file)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("CSV file with valid signers and their Ethereum secret keys."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-valid-signer")
+                .about("Remove valid signer from the signer group with valid signers' signatures")
+                .arg(
+                    Arg::with_name("valid_signer")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Account of valid signer to be removed."),
+                )
+                .arg(
+                    Arg::with_name("signers_secrets")
+                        .long("signers-secrets")
+                        .validator(is_csv_file)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("CSV file with valid signers and their Ethereum secret keys."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("migrate-valid-signer")
+                .about("Move a valid signer at a keypair address to its derived address")
+                .arg(
+                    Arg::with_name("valid_signer")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Account of valid signer to move."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("migrate-signer-group")
+                .about("Grow a legacy signer group to the current layout, counting its valid signers")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Signer group to migrate."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("close-used-message")
+                .about("Close a used message account whose message expired, refunding its funder")
+                .arg(
+                    Arg::with_name("used_message")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Used message account to close."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("find-valid-signer")
+                .about("Show the valid signer of the signer group for an Ethereum address")
+                .arg(
+                    Arg::with_name("signer_group")
+                        .index(1)
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Group of the valid signer."),
+                )
+                .arg(
+                    Arg::with_name("eth_address")
+                        .index(2)
+                        .validator(is_hex)
+                        .value_name("ETH_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Ethereum address of the valid signer (without 0x prefix)."),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("send-message")
                 .about("Validate signer's signature")
@@ -538,7 +1139,10 @@ fn main() {
     solana_logger::setup_with_default("solana=info");
 
     let _ = match matches.subcommand() {
-        ("create-signer-group", Some(_)) => command_create_signer_group(&config),
+        ("create-signer-group", Some(arg_matches)) => {
+            let seed: String = arg_matches.value_of("seed").unwrap_or_default().to_string();
+            command_create_signer_group(&config, seed)
+        }
         ("query-eth-registry", Some(_)) => command_query_eth_registry(&config),
         ("query-signer-group", Some(arg_matches)) => {
             let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
@@ -548,6 +1152,20 @@ fn main() {
             let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
             command_disable_signer_group_owner(&config, &signer_group)
         }
+        ("transfer-signer-group-ownership", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            let new_owner: Pubkey = pubkey_of(arg_matches, "new_owner").unwrap();
+            command_transfer_signer_group_ownership(&config, &signer_group, &new_owner)
+        }
+        ("accept-signer-group-ownership", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            command_accept_signer_group_ownership(&config, &signer_group)
+        }
+        ("enable-signer-group-owner", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            let signers_secrets: String = value_t_or_exit!(arg_matches, "signers_secrets", String);
+            command_enable_signer_group_owner(&config, &signer_group, signers_secrets)
+        }
         ("create-valid-signer", Some(arg_matches)) => {
             let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
             let eth_address: String = value_t_or_exit!(arg_matches, "eth_address", String);
@@ -557,6 +1175,40 @@ fn main() {
             let valid_signer: Pubkey = pubkey_of(arg_matches, "valid_signer").unwrap();
             command_clear_valid_signer(&config, &valid_signer)
         }
+        ("set-threshold", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            let threshold: u8 = value_t_or_exit!(arg_matches, "threshold", u8);
+            let signers_secrets: Option<String> = value_t!(arg_matches, "signers_secrets", String).ok();
+            command_set_threshold(&config, &signer_group, threshold, signers_secrets)
+        }
+        ("add-valid-signer", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            let eth_address: String = value_t_or_exit!(arg_matches, "eth_address", String);
+            let signers_secrets: String = value_t_or_exit!(arg_matches, "signers_secrets", String);
+            command_add_valid_signer(&config, &signer_group, eth_address, signers_secrets)
+        }
+        ("remove-valid-signer", Some(arg_matches)) => {
+            let valid_signer: Pubkey = pubkey_of(arg_matches, "valid_signer").unwrap();
+            let signers_secrets: String = value_t_or_exit!(arg_matches, "signers_secrets", String);
+            command_remove_valid_signer(&config, &valid_signer, signers_secrets)
+        }
+        ("migrate-valid-signer", Some(arg_matches)) => {
+            let valid_signer: Pubkey = pubkey_of(arg_matches, "valid_signer").unwrap();
+            command_migrate_valid_signer(&config, &valid_signer)
+        }
+        ("migrate-signer-group", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            command_migrate_signer_group(&config, &signer_group)
+        }
+        ("close-used-message", Some(arg_matches)) => {
+            let used_message: Pubkey = pubkey_of(arg_matches, "used_message").unwrap();
+            command_close_used_message(&config, &used_message)
+        }
+        ("find-valid-signer", Some(arg_matches)) => {
+            let signer_group: Pubkey = pubkey_of(arg_matches, "signer_group").unwrap();
+            let eth_address: String = value_t_or_exit!(arg_matches, "eth_address", String);
+            command_find_valid_signer(&config, &signer_group, eth_address)
+        }
         ("send-message", Some(arg_matches)) => {
             let valid_signer: Pubkey = pubkey_of(arg_matches, "valid_signer").unwrap();
             let secret_key: String = value_t_or_exit!(arg_matches, "secret_key", String);
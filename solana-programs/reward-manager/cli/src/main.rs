@@ -11,20 +11,36 @@ use audius_reward_manager::{
         create_sender,
         delete_sender,
         init,
-        evaluate_attestations,
-        submit_attestations,
+        evaluate_attestations_v2,
+        submit_attestations_v2,
         delete_sender_public,
-        change_manager_authority
+        propose_manager,
+        accept_manager,
+        cancel_manager_proposal,
+        update_reward_manager_config,
+        update_reward_manager_config_public,
+        close_legacy_verified_messages,
+        close_verified_messages,
+        set_paused,
+        update_disbursement_limits,
+        update_sender,
+        update_sender_public,
+        update_verified_messages_max_age,
+        migrate_reward_manager
     },
-    processor::SENDER_SEED_PREFIX,
+    processor::{SENDER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX},
     state::{
         RewardManager,
         SenderAccount,
         VerifiedMessages,
         DELETE_SENDER_MESSAGE_PREFIX,
-        ADD_SENDER_MESSAGE_PREFIX
+        ADD_SENDER_MESSAGE_PREFIX,
+        UPDATE_CONFIG_MESSAGE_PREFIX,
+        UPDATE_SENDER_MESSAGE_PREFIX,
+        VERIFIED_MESSAGES_VERSION
     },
-    utils::find_derived_pair
+    utils::{find_derived_pair, hashed_transfer_id_seed, transfer_attestation_domain},
+    PROGRAM_VERSION
 };
 
 use hex::FromHex;
@@ -33,9 +49,15 @@ use solana_clap_utils::{
     input_validators::{is_keypair, is_keypair_or_ask_keyword, is_parsable, is_pubkey, is_url},
     keypair::signer_from_path,
 };
-use solana_client::rpc_client::RpcClient;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+};
 use solana_program::{instruction::Instruction, pubkey::Pubkey};
 use solana_sdk::{
+    bs58,
     commitment_config::CommitmentConfig,
     program_pack::Pack,
     signature::{Keypair, Signer},
@@ -48,7 +70,8 @@ use std::process::exit;
 use std::str::FromStr;
 use utils::Transaction as CustomTransaction;
 use utils::{
-    is_csv_file, is_eth_address, is_hex, new_secp256k1_instruction_2_0, sign_message, SenderData,
+    is_csv_file, is_eth_address, is_hex, new_secp256k1_instruction_2_0, prepare_message,
+    sign_message, SenderData,
 };
 
 #[allow(dead_code)]
@@ -58,6 +81,7 @@ pub struct Config {
     owner: Box<dyn Signer>,
     fee_payer: Box<dyn Signer>,
     commitment_config: CommitmentConfig,
+    eip191: bool,
 }
 
 type Error = Box<dyn std::error::Error>;
@@ -201,6 +225,114 @@ fn command_delete_sender(
     transaction.sign(config, 0)
 }
 
+fn command_update_sender(
+    config: &Config,
+    reward_manager: Pubkey,
+    eth_sender_address: String,
+    new_eth_sender_address: String,
+    operator_eth_address: String,
+) -> CommandResult {
+    let decoded_eth_sender_address =
+        <[u8; 20]>::from_hex(eth_sender_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
+    let decoded_new_eth_sender_address =
+        <[u8; 20]>::from_hex(new_eth_sender_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
+    let decoded_eth_operator_address =
+        <[u8; 20]>::from_hex(operator_eth_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
+
+    let transaction = CustomTransaction {
+        instructions: vec![update_sender(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            &config.fee_payer.pubkey(),
+            decoded_eth_sender_address,
+            decoded_new_eth_sender_address,
+            decoded_eth_operator_address,
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_update_sender_public(
+    config: &Config,
+    reward_manager: Pubkey,
+    eth_sender_address: String,
+    new_eth_sender_address: String,
+    operator_eth_address: String,
+    senders_secrets: String
+) -> CommandResult {
+    let mut instructions = Vec::new();
+
+    let mut senders = Vec::new();
+    let mut secrets = Vec::new();
+    let decoded_eth_sender_address =
+        <[u8; 20]>::from_hex(eth_sender_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
+    let decoded_new_eth_sender_address =
+        <[u8; 20]>::from_hex(new_eth_sender_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
+    let decoded_eth_operator_address =
+        <[u8; 20]>::from_hex(operator_eth_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
+
+    // Signatures cover the sender's current operator
+    let (_, sender, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), decoded_eth_sender_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    let sender_data = config.rpc_client.get_account_data(&sender)?;
+    let sender_account = SenderAccount::unpack(sender_data.as_slice())?;
+
+    let message_to_sign = [
+        UPDATE_SENDER_MESSAGE_PREFIX.as_ref(),
+        reward_manager.as_ref(),
+        decoded_eth_sender_address.as_ref(),
+        sender_account.operator.as_ref(),
+        decoded_new_eth_sender_address.as_ref(),
+        decoded_eth_operator_address.as_ref()
+    ].concat();
+
+    println!("Reading secrets from: {:?}", &senders_secrets);
+    println!("Signing message with senders private keys...");
+
+    let mut rdr = csv::Reader::from_path(&senders_secrets)?;
+    for key in rdr.deserialize() {
+        let deserialized_sender_data: SenderData = key?;
+        let decoded_secret = <[u8; 32]>::from_hex(deserialized_sender_data.eth_secret)
+            .expect(HEX_ETH_SECRET_DECODING_ERROR);
+
+        senders.push(Pubkey::from_str(&deserialized_sender_data.solana_key)?);
+        secrets.push(libsecp256k1::SecretKey::parse(&decoded_secret)?);
+    }
+
+    println!("Senders: {:?}", senders);
+
+    // Append signed update messages
+    instructions.append(
+        &mut sign_message(config, message_to_sign.as_ref(), secrets)
+    );
+
+    // Append public function
+    instructions.push(update_sender_public(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &config.fee_payer.pubkey(),
+        decoded_eth_sender_address,
+        decoded_new_eth_sender_address,
+        decoded_eth_operator_address,
+        &senders
+    )?);
+
+    let transaction = CustomTransaction {
+        instructions,
+        signers: vec![config.fee_payer.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
 fn command_delete_sender_public(
     config: &Config,
     reward_manager: Pubkey,
@@ -238,7 +370,7 @@ fn command_delete_sender_public(
 
     // Append signed delete messages
     instructions.append(
-        &mut sign_message(message_to_sign.as_ref(), secrets)
+        &mut sign_message(config, message_to_sign.as_ref(), secrets)
     );
 
     // Append public function
@@ -258,31 +390,200 @@ fn command_delete_sender_public(
     transaction.sign(config, 0)
 }
 
-fn command_change_reward_manager_authority(
+fn command_propose_manager(
     config: &Config,
     reward_manager: Pubkey,
-    current_authority: Pubkey,
-    new_authority: Pubkey
+    new_manager: Pubkey,
 ) -> CommandResult {
     println!("Using reward manager - {:}", &reward_manager);
-    println!("Current authority input - {:}", &current_authority);
-    println!("New authority - {:}", &new_authority);
+    println!("Proposed manager - {:}", &new_manager);
     let reward_manager_data = config.rpc_client.get_account_data(&reward_manager)?;
     let reward_manager_from_chain = RewardManager::unpack(reward_manager_data.as_slice())?;
-    println!("Current authority from chain - {:}", reward_manager_from_chain.manager);
+    println!("Current manager from chain - {:}", reward_manager_from_chain.manager);
 
-    if reward_manager_from_chain.manager != current_authority {
-        println!("Incorrect parameters, exiting");
+    if reward_manager_from_chain.manager != config.owner.pubkey() {
+        println!("Owner is not the current manager, exiting");
         exit(1);
     }
 
+    let transaction = CustomTransaction {
+        instructions: vec![propose_manager(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            &new_manager,
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_accept_manager(config: &Config, reward_manager: Pubkey) -> CommandResult {
+    let reward_manager_data = config.rpc_client.get_account_data(&reward_manager)?;
+    let reward_manager_from_chain = RewardManager::unpack(reward_manager_data.as_slice())?;
+    println!("Pending manager from chain - {:}", reward_manager_from_chain.pending_manager);
+
+    if reward_manager_from_chain.pending_manager != config.owner.pubkey() {
+        println!("Owner is not the proposed manager, exiting");
+        exit(1);
+    }
+
+    let transaction = CustomTransaction {
+        instructions: vec![accept_manager(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_cancel_manager_proposal(config: &Config, reward_manager: Pubkey) -> CommandResult {
+    let transaction = CustomTransaction {
+        instructions: vec![cancel_manager_proposal(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_set_paused(config: &Config, reward_manager: Pubkey, paused: bool) -> CommandResult {
+    let transaction = CustomTransaction {
+        instructions: vec![set_paused(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            paused,
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_update_disbursement_limits(
+    config: &Config,
+    reward_manager: Pubkey,
+    max_transfer_amount: u64,
+    max_window_amount: u64,
+    window_slots: u64,
+) -> CommandResult {
+    let transaction = CustomTransaction {
+        instructions: vec![update_disbursement_limits(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            max_transfer_amount,
+            max_window_amount,
+            window_slots,
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_update_verified_messages_max_age(
+    config: &Config,
+    reward_manager: Pubkey,
+    max_age: u64,
+) -> CommandResult {
+    let transaction = CustomTransaction {
+        instructions: vec![update_verified_messages_max_age(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            max_age,
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_migrate_reward_manager(config: &Config, reward_manager: Pubkey) -> CommandResult {
+    let transaction = CustomTransaction {
+        instructions: vec![migrate_reward_manager(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            &config.fee_payer.pubkey(),
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_update_reward_manager_config(
+    config: &Config,
+    reward_manager: Pubkey,
+    min_votes: u8,
+) -> CommandResult {
+    let transaction = CustomTransaction {
+        instructions: vec![update_reward_manager_config(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &config.owner.pubkey(),
+            min_votes,
+        )?],
+        signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
+    };
+
+    transaction.sign(config, 0)
+}
+
+fn command_update_reward_manager_config_public(
+    config: &Config,
+    reward_manager: Pubkey,
+    min_votes: u8,
+    senders_secrets: String,
+) -> CommandResult {
     let mut instructions = Vec::new();
 
-    instructions.push(change_manager_authority(
+    let mut senders = Vec::new();
+    let mut secrets = Vec::new();
+
+    // Signatures are only valid for the current config nonce
+    let reward_manager_data = config.rpc_client.get_account_data(&reward_manager)?;
+    let reward_manager_from_chain = RewardManager::unpack(reward_manager_data.as_slice())?;
+
+    let message_to_sign = [
+        UPDATE_CONFIG_MESSAGE_PREFIX.as_ref(),
+        reward_manager.as_ref(),
+        reward_manager_from_chain.config_nonce.to_le_bytes().as_ref(),
+        &[min_votes]
+    ].concat();
+
+    println!("Reading secrets from: {:?}", &senders_secrets);
+    println!("Signing message with senders private keys...");
+
+    let mut rdr = csv::Reader::from_path(&senders_secrets)?;
+    for key in rdr.deserialize() {
+        let deserialized_sender_data: SenderData = key?;
+        let decoded_secret = <[u8; 32]>::from_hex(deserialized_sender_data.eth_secret)
+            .expect(HEX_ETH_SECRET_DECODING_ERROR);
+
+        senders.push(Pubkey::from_str(&deserialized_sender_data.solana_key)?);
+        secrets.push(libsecp256k1::SecretKey::parse(&decoded_secret)?);
+    }
+
+    println!("Senders: {:?}", senders);
+
+    instructions.append(&mut sign_message(config, message_to_sign.as_ref(), secrets));
+
+    instructions.push(update_reward_manager_config_public(
         &audius_reward_manager::id(),
         &reward_manager,
-        &current_authority,
-        &new_authority
+        min_votes,
+        &senders,
     )?);
 
     let transaction = CustomTransaction {
@@ -290,7 +591,106 @@ fn command_change_reward_manager_authority(
         signers: vec![config.fee_payer.as_ref(), config.owner.as_ref()],
     };
 
-    transaction.sign(config, 0)
+    transaction.sign(config, 0)
+}
+
+fn command_close_stale_verified_messages(
+    config: &Config,
+    reward_manager: Pubkey,
+    batch_size: usize,
+    legacy: bool,
+) -> CommandResult {
+    let reward_manager_data = config.rpc_client.get_account_data(&reward_manager)?;
+    let reward_manager_from_chain = RewardManager::unpack(reward_manager_data.as_slice())?;
+    if legacy && reward_manager_from_chain.manager != config.owner.pubkey() {
+        println!("Owner is not the current manager, exiting");
+        exit(1);
+    }
+
+    // Legacy accounts share their version with other account types
+    let version = if legacy { PROGRAM_VERSION } else { VERIFIED_MESSAGES_VERSION };
+    let accounts = config.rpc_client.get_program_accounts_with_config(
+        &audius_reward_manager::id(),
+        RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::Memcmp(Memcmp {
+                    offset: 0,
+                    bytes: MemcmpEncodedBytes::Binary(bs58::encode([version]).into_string()),
+                    encoding: None,
+                }),
+                RpcFilterType::Memcmp(Memcmp {
+                    offset: 1,
+                    bytes: MemcmpEncodedBytes::Binary(reward_manager.to_string()),
+                    encoding: None,
+                }),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(config.commitment_config),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+
+    let current_slot = config.rpc_client.get_slot()?;
+    let mut instructions = Vec::new();
+    for (address, account) in accounts {
+        let verified_messages = match VerifiedMessages::unpack(account.data.as_slice()) {
+            Ok(verified_messages) => verified_messages,
+            Err(_) => continue,
+        };
+        if account.data.len() != verified_messages.packed_len() {
+            continue;
+        }
+        if legacy {
+            println!("Closing legacy verified messages {:}", address);
+            instructions.push(close_legacy_verified_messages(
+                &audius_reward_manager::id(),
+                &address,
+                &reward_manager,
+                &config.owner.pubkey(),
+            )?);
+            continue;
+        }
+        let expires_at = verified_messages
+            .slot
+            .saturating_add(reward_manager_from_chain.verified_messages_max_age);
+        if current_slot < expires_at {
+            continue;
+        }
+        println!("Closing verified messages {:} funded by {:}", address, verified_messages.funder);
+        instructions.push(close_verified_messages(
+            &audius_reward_manager::id(),
+            &address,
+            &reward_manager,
+            &verified_messages.funder,
+        )?);
+    }
+
+    println!("Stale verified messages: {:}", instructions.len());
+
+    let mut signers = vec![config.fee_payer.as_ref()];
+    if legacy {
+        signers.push(config.owner.as_ref());
+    }
+    for batch in instructions.chunks(batch_size.max(1)) {
+        let transaction = CustomTransaction {
+            instructions: batch.to_vec(),
+            signers: signers.clone(),
+        };
+        if let Some(transaction) = transaction.sign(config, 0)? {
+            let signature = config
+                .rpc_client
+                .send_and_confirm_transaction_with_spinner_and_commitment(
+                    &transaction,
+                    config.commitment_config,
+                )?;
+            println!("Signature: {}", signature);
+        }
+    }
+
+    Ok(None)
 }
 
 fn command_add_sender(
@@ -328,7 +728,7 @@ fn command_add_sender(
 
     println!("Senders: {:?}", senders);
 
-    instructions.append(&mut sign_message(message_to_sign.as_ref(), secrets));
+    instructions.append(&mut sign_message(config, message_to_sign.as_ref(), secrets));
 
     instructions.push(create_sender_public(
         &audius_reward_manager::id(),
@@ -373,7 +773,10 @@ fn command_submit_attestations(
     let decoded_recipient_address =
         <[u8; 20]>::from_hex(recipient_eth_address).expect(HEX_ETH_ADDRESS_DECODING_ERROR);
 
+    let domain = transfer_attestation_domain(&audius_reward_manager::id(), &reward_manager_pubkey);
+
     let make_bot_message =  || {[
+            domain.as_ref(),
             decoded_recipient_address.as_ref(),
             b"_".as_ref(),
             amount.to_le_bytes().as_ref(),
@@ -389,6 +792,7 @@ fn command_submit_attestations(
         println!("Signing as normal sender");
         // Sender message
         [
+            domain.as_ref(),
             decoded_recipient_address.as_ref(),
             b"_".as_ref(),
             amount.to_le_bytes().as_ref(),
@@ -408,12 +812,12 @@ fn command_submit_attestations(
     println!("Generated message {:?}", message);
     instructions.push(new_secp256k1_instruction_2_0(
         &libsecp256k1::SecretKey::parse(&decoded_secret)?,
-        &message,
+        &prepare_message(config, &message),
         0
     ));
 
     instructions.push(
-        submit_attestations(
+        submit_attestations_v2(
             &audius_reward_manager::id(),
             &reward_manager_pubkey,
             &signer_pubkey,
@@ -428,10 +832,10 @@ fn command_submit_attestations(
             let bot_decoded_secret = <[u8; 32]>::from_hex(oracle_secret).expect(HEX_ETH_SECRET_DECODING_ERROR);
             let bot_secp = new_secp256k1_instruction_2_0(
                 &libsecp256k1::SecretKey::parse(&bot_decoded_secret)?,
-                &make_bot_message(),
+                &prepare_message(config, &make_bot_message()),
                 2
             );
-            let bot_verify = submit_attestations(
+            let bot_verify = submit_attestations_v2(
                 &audius_reward_manager::id(),
                 &reward_manager_pubkey,
                 &bot_oracle_pubkey.unwrap(),
@@ -512,7 +916,7 @@ fn command_transfer(
         );
     }
 
-    instructions.push(evaluate_attestations(
+    instructions.push(evaluate_attestations_v2(
         &audius_reward_manager::id(),
         &verified_messages_pubkey,
         &reward_manager_pubkey,
@@ -560,6 +964,13 @@ fn main() {
                 .global(true)
                 .help("Show additional information"),
         )
+        .arg(
+            Arg::with_name("eip191")
+                .long("eip191")
+                .takes_value(false)
+                .global(true)
+                .help("Sign messages as `personal_sign` does, with the EIP-191 prefix"),
+        )
         .arg(
             Arg::with_name("json_rpc_url")
                 .long("url")
@@ -646,7 +1057,100 @@ fn main() {
                     .required(true)
                     .help("Ethereum operator address"),
             ))
-        .subcommand(SubCommand::with_name("change-reward-manager-authority").about("Admin method disabling 'manager' authority")
+        .subcommand(SubCommand::with_name("propose-manager").about("Admin method proposing a new 'manager' authority")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            )
+            .arg(
+                Arg::with_name("new-manager")
+                    .long("new-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Proposed manager, takes over once it accepts"),
+            ))
+        .subcommand(SubCommand::with_name("accept-manager").about("Accept the 'manager' authority proposed to the owner")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            ))
+        .subcommand(SubCommand::with_name("cancel-manager-proposal").about("Admin method cancelling the pending manager proposal")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            ))
+        .subcommand(SubCommand::with_name("pause").about("Admin method pausing rewards")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            ))
+        .subcommand(SubCommand::with_name("unpause").about("Admin method unpausing rewards")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            ))
+        .subcommand(SubCommand::with_name("update-disbursement-limits").about("Admin method updating limits on disbursed rewards")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            )
+            .arg(
+                Arg::with_name("max-transfer-amount")
+                    .long("max-transfer-amount")
+                    .validator(is_parsable::<f64>)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .help("Maximum amount of a single transfer [default: no limit]"),
+            )
+            .arg(
+                Arg::with_name("max-window-amount")
+                    .long("max-window-amount")
+                    .validator(is_parsable::<f64>)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .help("Maximum amount disbursed within a window [default: no limit]"),
+            )
+            .arg(
+                Arg::with_name("window-slots")
+                    .long("window-slots")
+                    .validator(is_parsable::<u64>)
+                    .takes_value(true)
+                    .default_value("0")
+                    .help("Length of the disbursement window in slots"),
+            ))
+        .subcommand(SubCommand::with_name("update-reward-manager-config").about("Admin method updating reward manager config")
             .arg(
                 Arg::with_name("reward-manager")
                     .long("reward-manager")
@@ -657,24 +1161,93 @@ fn main() {
                     .help("Reward manager"),
             )
             .arg(
-                Arg::with_name("current-authority")
-                    .long("current-authority")
+                Arg::with_name("min-votes")
+                    .long("min-votes")
+                    .validator(is_parsable::<u8>)
+                    .takes_value(true)
+                    .required(true)
+                    .help("Number of signer votes required for sending rewards."),
+            ))
+        .subcommand(SubCommand::with_name("update-reward-manager-config-public").about("Update reward manager config with signatures")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
                     .validator(is_pubkey)
                     .value_name("ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Current authority"),
+                    .help("Reward manager"),
+            )
+            .arg(
+                Arg::with_name("min-votes")
+                    .long("min-votes")
+                    .validator(is_parsable::<u8>)
+                    .takes_value(true)
+                    .required(true)
+                    .help("Number of signer votes required for sending rewards."),
             )
             .arg(
-                Arg::with_name("new-authority")
-                    .long("new-authority")
+                Arg::with_name("senders-secrets")
+                .long("senders-secrets")
+                .validator(is_csv_file)
+                .value_name("PATH")
+                .takes_value(true)
+                .required(true)
+                .help("CSV file with senders Ethereum secret keys"),
+            ))
+        .subcommand(SubCommand::with_name("close-stale-verified-messages").about("Close expired verified messages, refunding their funders")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
                     .validator(is_pubkey)
                     .value_name("ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Current authority"),
+                    .help("Reward manager"),
             )
+            .arg(
+                Arg::with_name("batch-size")
+                    .long("batch-size")
+                    .validator(is_parsable::<usize>)
+                    .takes_value(true)
+                    .default_value("10")
+                    .help("Number of accounts closed per transaction"),
+            )
+            .arg(
+                Arg::with_name("legacy")
+                    .long("legacy")
+                    .takes_value(false)
+                    .help("Close every legacy verified messages account as the manager, refunding the manager"),
+            ))
+        .subcommand(SubCommand::with_name("update-verified-messages-max-age").about("Admin method updating the age after which verified messages can be closed")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
             )
+            .arg(
+                Arg::with_name("max-age")
+                    .long("max-age")
+                    .validator(is_parsable::<u64>)
+                    .value_name("SLOTS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Slots after which verified messages can be closed"),
+            ))
+        .subcommand(SubCommand::with_name("migrate-reward-manager").about("Admin method moving a legacy reward manager to the current layout")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            ))
         .subcommand(SubCommand::with_name("delete-sender").about("Admin method deleting sender")
             .arg(
                 Arg::with_name("reward-manager")
@@ -694,6 +1267,87 @@ fn main() {
                     .required(true)
                     .help("Ethereum sender address"),
             ))
+        .subcommand(SubCommand::with_name("update-sender").about("Admin method changing the address or the operator of a sender")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            )
+            .arg(
+                Arg::with_name("eth-sender-address")
+                    .long("eth-sender-address")
+                    .validator(is_eth_address)
+                    .value_name("ETH_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Current Ethereum sender address"),
+            )
+            .arg(
+                Arg::with_name("new-eth-sender-address")
+                    .long("new-eth-sender-address")
+                    .validator(is_eth_address)
+                    .value_name("ETH_ADDRESS")
+                    .takes_value(true)
+                    .help("New Ethereum sender address, defaults to the current one"),
+            )
+            .arg(
+                Arg::with_name("eth-operator-address")
+                    .long("eth-operator-address")
+                    .validator(is_eth_address)
+                    .value_name("ETH_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("New Ethereum operator address"),
+            ))
+        .subcommand(SubCommand::with_name("update-sender-public").about("Change the address or the operator of a sender with signatures")
+            .arg(
+                Arg::with_name("reward-manager")
+                    .long("reward-manager")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Reward manager"),
+            )
+            .arg(
+                Arg::with_name("eth-sender-address")
+                    .long("eth-sender-address")
+                    .validator(is_eth_address)
+                    .value_name("ETH_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Current Ethereum sender address"),
+            )
+            .arg(
+                Arg::with_name("new-eth-sender-address")
+                    .long("new-eth-sender-address")
+                    .validator(is_eth_address)
+                    .value_name("ETH_ADDRESS")
+                    .takes_value(true)
+                    .help("New Ethereum sender address, defaults to the current one"),
+            )
+            .arg(
+                Arg::with_name("eth-operator-address")
+                    .long("eth-operator-address")
+                    .validator(is_eth_address)
+                    .value_name("ETH_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("New Ethereum operator address"),
+            )
+            .arg(
+                Arg::with_name("senders-secrets")
+                .long("senders-secrets")
+                .validator(is_csv_file)
+                .value_name("PATH")
+                .takes_value(true)
+                .required(true)
+                .help("CSV file with senders Ethereum secret keys"),
+            ))
         .subcommand(SubCommand::with_name("add-sender").about("Add new sender")
             .arg(
                 Arg::with_name("reward-manager")
@@ -871,8 +1525,7 @@ fn main() {
                     .validator(is_pubkey)
                     .value_name("ADDRESS")
                     .takes_value(true)
-                    .required(true)
-                    .help("Verified messages"),
+                    .help("Verified messages, derived from the transfer ID if omitted"),
             )
             .arg(
                 Arg::with_name("transfer_id")
@@ -943,6 +1596,7 @@ fn main() {
             exit(1);
         });
         let verbose = matches.is_present("verbose");
+        let eip191 = matches.is_present("eip191");
 
         Config {
             rpc_client: RpcClient::new(json_rpc_url),
@@ -950,6 +1604,7 @@ fn main() {
             owner,
             fee_payer,
             commitment_config: CommitmentConfig::confirmed(),
+            eip191,
         }
     };
 
@@ -985,6 +1640,42 @@ fn main() {
                 String::from(eth_sender_address.get(2..).unwrap()),
             )
         }
+        ("update-sender", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let eth_sender_address: String =
+                value_t_or_exit!(arg_matches, "eth-sender-address", String);
+            let new_eth_sender_address: String =
+                value_t!(arg_matches, "new-eth-sender-address", String)
+                    .unwrap_or_else(|_| eth_sender_address.clone());
+            let eth_operator_address: String =
+                value_t_or_exit!(arg_matches, "eth-operator-address", String);
+            command_update_sender(
+                &config,
+                reward_manager,
+                String::from(eth_sender_address.get(2..).unwrap()),
+                String::from(new_eth_sender_address.get(2..).unwrap()),
+                String::from(eth_operator_address.get(2..).unwrap()),
+            )
+        }
+        ("update-sender-public", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let eth_sender_address: String =
+                value_t_or_exit!(arg_matches, "eth-sender-address", String);
+            let new_eth_sender_address: String =
+                value_t!(arg_matches, "new-eth-sender-address", String)
+                    .unwrap_or_else(|_| eth_sender_address.clone());
+            let eth_operator_address: String =
+                value_t_or_exit!(arg_matches, "eth-operator-address", String);
+            let senders_secrets: String = value_t_or_exit!(arg_matches, "senders-secrets", String);
+            command_update_sender_public(
+                &config,
+                reward_manager,
+                String::from(eth_sender_address.get(2..).unwrap()),
+                String::from(new_eth_sender_address.get(2..).unwrap()),
+                String::from(eth_operator_address.get(2..).unwrap()),
+                senders_secrets
+            )
+        }
         ("add-sender", Some(arg_matches)) => {
             let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
             let new_sender: String = value_t_or_exit!(arg_matches, "new-sender", String);
@@ -999,17 +1690,75 @@ fn main() {
                 senders_secrets,
             )
         }
-        ("change-reward-manager-authority", Some(arg_matches)) => {
+        ("propose-manager", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let new_manager: Pubkey = pubkey_of(arg_matches, "new-manager").unwrap();
+            command_propose_manager(&config, reward_manager, new_manager)
+        }
+        ("accept-manager", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            command_accept_manager(&config, reward_manager)
+        }
+        ("cancel-manager-proposal", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            command_cancel_manager_proposal(&config, reward_manager)
+        }
+        ("pause", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            command_set_paused(&config, reward_manager, true)
+        }
+        ("unpause", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            command_set_paused(&config, reward_manager, false)
+        }
+        ("update-disbursement-limits", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let max_transfer_amount = value_t!(arg_matches, "max-transfer-amount", f64)
+                .map(|amount| ui_amount_to_amount(amount, spl_token::native_mint::DECIMALS))
+                .unwrap_or(u64::MAX);
+            let max_window_amount = value_t!(arg_matches, "max-window-amount", f64)
+                .map(|amount| ui_amount_to_amount(amount, spl_token::native_mint::DECIMALS))
+                .unwrap_or(u64::MAX);
+            let window_slots: u64 = value_t_or_exit!(arg_matches, "window-slots", u64);
+            command_update_disbursement_limits(
+                &config,
+                reward_manager,
+                max_transfer_amount,
+                max_window_amount,
+                window_slots,
+            )
+        }
+        ("update-reward-manager-config", Some(arg_matches)) => {
             let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
-            let current_authority: Pubkey = pubkey_of(arg_matches, "current-authority").unwrap();
-            let new_authority: Pubkey = pubkey_of(arg_matches, "new-authority").unwrap();
-            command_change_reward_manager_authority(
+            let min_votes: u8 = value_t_or_exit!(arg_matches, "min-votes", u8);
+            command_update_reward_manager_config(&config, reward_manager, min_votes)
+        }
+        ("update-reward-manager-config-public", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let min_votes: u8 = value_t_or_exit!(arg_matches, "min-votes", u8);
+            let senders_secrets: String = value_t_or_exit!(arg_matches, "senders-secrets", String);
+            command_update_reward_manager_config_public(
                 &config,
                 reward_manager,
-                current_authority,
-                new_authority
+                min_votes,
+                senders_secrets,
             )
         }
+        ("close-stale-verified-messages", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let batch_size: usize = value_t_or_exit!(arg_matches, "batch-size", usize);
+            let legacy = arg_matches.is_present("legacy");
+            command_close_stale_verified_messages(&config, reward_manager, batch_size, legacy)
+        }
+        ("update-verified-messages-max-age", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            let max_age: u64 = value_t_or_exit!(arg_matches, "max-age", u64);
+            command_update_verified_messages_max_age(&config, reward_manager, max_age)
+        }
+        ("migrate-reward-manager", Some(arg_matches)) => {
+            let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
+            command_migrate_reward_manager(&config, reward_manager)
+        }
         ("delete-sender-public", Some(arg_matches)) => {
             let reward_manager: Pubkey = pubkey_of(arg_matches, "reward-manager").unwrap();
             let existing_sender: String = value_t_or_exit!(arg_matches, "existing-sender", String);
@@ -1054,8 +1803,15 @@ fn main() {
         }
         ("transfer", Some(arg_matches)) => {
             let reward_manager: Pubkey = pubkey_of(arg_matches, "reward_manager").unwrap();
-            let verified_messages = pubkey_of(arg_matches, "verified_messages").unwrap();
             let transfer_id: String = value_t_or_exit!(arg_matches, "transfer_id", String);
+            let verified_messages = pubkey_of(arg_matches, "verified_messages").unwrap_or_else(|| {
+                find_derived_pair(
+                    &audius_reward_manager::id(),
+                    &reward_manager,
+                    &hashed_transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, &transfer_id),
+                )
+                .1
+            });
             let recipient_eth_address: String =
                 value_t_or_exit!(arg_matches, "recipient_eth_address", String);
             let amount: f64 = value_t_or_exit!(arg_matches, "amount", f64);
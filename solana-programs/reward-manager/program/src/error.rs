@@ -1,5 +1,6 @@
 //! Error types
 
+use audius_secp_utils::SecpError;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use solana_program::{
@@ -80,6 +81,42 @@ pub enum AudiusProgramError {
     /// Math overflow
     #[error("Math overflow")]
     MathOverflow,
+
+    /// Min votes can't be satisfied by verified messages
+    #[error("Invalid number of min votes")]
+    InvalidMinVotes,
+
+    /// Verified messages account is too recent to be closed
+    #[error("Verified messages not expired")]
+    VerifiedMessagesNotExpired,
+
+    /// Rewards are paused by the manager
+    #[error("Reward manager is paused")]
+    Paused,
+
+    /// No manager was proposed
+    #[error("No pending manager")]
+    NoPendingManager,
+
+    /// Transfer amount is above the per transfer limit
+    #[error("Transfer limit exceeded")]
+    TransferLimitExceeded,
+
+    /// Disbursements within the window would be above the window limit
+    #[error("Window limit exceeded")]
+    WindowLimitExceeded,
+
+    /// Account was closed earlier in the transaction
+    #[error("Account closed")]
+    AccountClosed,
+
+    /// Disbursement limits contradict each other
+    #[error("Invalid disbursement limits")]
+    InvalidDisbursementLimits,
+
+    /// Transfer id doesn't fit into a seed, see `transfer_id_seed`
+    #[error("Transfer id too long")]
+    TransferIdTooLong,
 }
 impl From<AudiusProgramError> for ProgramError {
     fn from(e: AudiusProgramError) -> Self {
@@ -101,6 +138,19 @@ impl PrintProgramError for AudiusProgramError {
     }
 }
 
+impl From<SecpError> for AudiusProgramError {
+    fn from(e: SecpError) -> Self {
+        match e {
+            SecpError::InstructionLoadError => AudiusProgramError::InstructionLoadError,
+            SecpError::Secp256InstructionMissing => AudiusProgramError::Secp256InstructionMissing,
+            SecpError::WrongSigner => AudiusProgramError::WrongSigner,
+            SecpError::MalformedInstruction | SecpError::WrongMessage => {
+                AudiusProgramError::SignatureVerificationFailed
+            }
+        }
+    }
+}
+
 /// Convert SanitizeError to AudiusProgramError
 pub fn to_audius_program_error(_e: SanitizeError) -> AudiusProgramError {
     AudiusProgramError::InstructionLoadError
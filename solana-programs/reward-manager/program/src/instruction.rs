@@ -2,10 +2,13 @@
 
 use crate::{
     processor::{SENDER_SEED_PREFIX, TRANSFER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX},
-    utils::{find_derived_pair, find_program_address, EthereumAddress},
+    utils::{
+        find_derived_pair, find_program_address, transfer_id_seed, EthereumAddress, TransferIdSeed,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::Slot,
     instruction::{AccountMeta, Instruction},
     program_error::ProgramError,
     pubkey::Pubkey,
@@ -37,10 +40,20 @@ pub struct CreateSenderPublicArgs {
     pub operator: EthereumAddress,
 }
 
+/// `UpdateSender` and `UpdateSenderPublic` instruction args
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UpdateSenderArgs {
+    /// New ethereum address, the current one keeps the sender account in place
+    pub eth_address: EthereumAddress,
+    /// New sender operator
+    pub operator: EthereumAddress,
+}
+
 /// Verify `Transfer` instruction args
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct SubmitAttestationsArgs {
-    /// ID generated on backend
+    /// ID generated on backend, fitting into a seed for the legacy instruction
+    /// (see `transfer_id_seed`), of any length for `SubmitAttestationsV2`
     pub id: String,
 }
 
@@ -49,12 +62,52 @@ pub struct SubmitAttestationsArgs {
 pub struct EvaluateAttestationsArgs {
     /// Amount to transfer
     pub amount: u64,
-    /// ID generated on backend
+    /// ID generated on backend, fitting into a seed for the legacy instructions
+    /// (see `transfer_id_seed`), of any length for the `V2` ones
     pub id: String,
     /// Recipient's Eth address
     pub eth_recipient: EthereumAddress,
 }
 
+/// `EvaluateAttestationsBatch` instruction args
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct EvaluateAttestationsBatchArgs {
+    /// Transfers to evaluate, in the order of their account groups
+    pub transfers: Vec<EvaluateAttestationsArgs>,
+}
+
+/// `UpdateRewardManagerConfig` instruction args
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UpdateRewardManagerConfigArgs {
+    /// Number of signer votes required for sending rewards
+    pub min_votes: u8,
+}
+
+/// `SetPaused` instruction args
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetPausedArgs {
+    /// Whether rewards should be paused
+    pub paused: bool,
+}
+
+/// `UpdateDisbursementLimits` instruction args
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UpdateDisbursementLimitsArgs {
+    /// Maximum amount of a single transfer
+    pub max_transfer_amount: u64,
+    /// Maximum amount disbursed within the last `window_slots` slots
+    pub max_window_amount: u64,
+    /// Length of the disbursement window in slots
+    pub window_slots: Slot,
+}
+
+/// `UpdateVerifiedMessagesMaxAge` instruction args
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UpdateVerifiedMessagesMaxAgeArgs {
+    /// Slots after which an unevaluated verified messages account can be closed
+    pub max_age: Slot,
+}
+
 /// Instruction definition
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub enum Instructions {
@@ -69,11 +122,12 @@ pub enum Instructions {
     ///   6. `[]` Rent sysvar
     InitRewardManager(InitRewardManagerArgs),
 
-    ///   Change RewardManager manager account
+    ///   Change RewardManager manager account in one step, signed by both managers.
+    ///   Prefer `ProposeManager` and `AcceptManager`, legacy reward managers only support this
     ///
     ///   0. `[writable]` Reward manager
     ///   1. `[signer]` Current manager
-    ///   2. `[]` New manager
+    ///   2. `[signer]` New manager
     ChangeManagerAccount,
 
     ///   Admin method creating new authorized sender
@@ -117,6 +171,9 @@ pub enum Instructions {
 
     ///   Submit attestations
     ///
+    ///   Existing verified messages accounts too small for `min_votes` plus the
+    ///   bot oracle are grown, with the funder paying the extra rent.
+    ///
     ///   0. `[writable]` Verified messages - New or existing account PDA storing verified messages
     ///   1. `[]` Reward manager
     ///   2. `[]` Reward manager authority
@@ -130,10 +187,10 @@ pub enum Instructions {
     ///   Evaluate attestations, transferring tokens to token recipient
     ///
     ///   0. `[]` Verified messages - New or existing account PDA storing verified messages-
-    ///   1. `[]` Reward manager
+    ///   1. `[writable]` Reward manager - tracks disbursements within the window
     ///   2. `[]` Reward manager authority
     ///   3. `[]` Reward token source
-    ///   4. `[]` Reward token recipient
+    ///   4. `[]` Reward token recipient - claimable-tokens user bank of `eth_recipient`
     ///   5. `[]` Transfer account - the account which represents a successful transfer
     ///   6. `[]` Bot oracle - the ethereum public address of the oracle 
     ///   7. `[]` Payer
@@ -141,6 +198,177 @@ pub enum Instructions {
     ///   9. `[]` Token program id
     ///  10. `[]` System program id
     EvaluateAttestations(EvaluateAttestationsArgs),
+
+    ///   Admin method updating RewardManager config. Every config update bumps
+    ///   the config nonce, so legacy reward managers can't be updated
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Manager account
+    UpdateRewardManagerConfig(UpdateRewardManagerConfigArgs),
+
+    ///   Update RewardManager config with senders attesting as proof. Senders sign
+    ///   the prefix, reward manager, current config nonce and new `min_votes`
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[]` Instruction info
+    ///   2. `[]` Bunch of senders which prove the update
+    UpdateRewardManagerConfigPublic(UpdateRewardManagerConfigArgs),
+
+    ///   Close verified messages older than the reward manager's `verified_messages_max_age`,
+    ///   refunding its funder. Legacy verified messages record neither funder nor slot,
+    ///   they are closed by the manager at any time, refunding the manager
+    ///
+    ///   0. `[writable]` Verified messages
+    ///   1. `[]` Reward manager
+    ///   2. `[writable]` Funder - the account which paid for verified messages,
+    ///      or the signing manager for legacy verified messages
+    CloseVerifiedMessages,
+
+    ///   Pause or unpause rewards, legacy reward managers can't be paused
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Manager account
+    SetPaused(SetPausedArgs),
+
+    ///   Propose a new manager, who takes over once they accept.
+    ///   Legacy reward managers can't store a proposal
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Current manager
+    ///   2. `[]` Proposed manager
+    ProposeManager,
+
+    ///   Accept the manager role proposed by the current manager
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Proposed manager
+    AcceptManager,
+
+    ///   Cancel the pending manager proposal
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Current manager
+    CancelManagerProposal,
+
+    ///   Update limits on disbursed rewards, legacy reward managers can't store limits
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Manager account
+    UpdateDisbursementLimits(UpdateDisbursementLimitsArgs),
+
+    ///   Evaluate attestations for several transfers at once.
+    ///   Fails as a whole if any of the transfers is invalid
+    ///
+    ///   0. `[writable]` Reward manager - tracks disbursements within the window
+    ///   1. `[]` Reward manager authority
+    ///   2. `[writable]` Reward token source
+    ///   3. `[]` Bot oracle
+    ///   4. `[signer]` Payer
+    ///   5. `[]` Sysvar rent
+    ///   6. `[]` Token program id
+    ///   7. `[]` System program id
+    ///   For every transfer:
+    ///   8 + 3 * i. `[writable]` Verified messages
+    ///   9 + 3 * i. `[writable]` Reward token recipient - claimable-tokens user bank of `eth_recipient`
+    ///  10 + 3 * i. `[writable]` Transfer account
+    EvaluateAttestationsBatch(EvaluateAttestationsBatchArgs),
+
+    ///   Evaluate attestations signed in the preceding secp instructions, one per sender
+    ///   and one by the bot oracle, transferring tokens without storing verified messages
+    ///
+    ///   0. `[writable]` Reward manager - tracks disbursements within the window
+    ///   1. `[]` Reward manager authority
+    ///   2. `[writable]` Reward token source
+    ///   3. `[writable]` Reward token recipient - claimable-tokens user bank of `eth_recipient`
+    ///   4. `[writable]` Transfer account - the account which represents a successful transfer
+    ///   5. `[]` Bot oracle
+    ///   6. `[signer]` Payer
+    ///   7. `[]` Instruction info
+    ///   8. `[]` Sysvar rent
+    ///   9. `[]` Token program id
+    ///  10. `[]` System program id
+    ///  11. `[]` Bunch of senders which attest the transfer
+    SubmitAndEvaluate(EvaluateAttestationsArgs),
+
+    ///   Admin method changing the eth address or the operator of a sender.
+    ///   A new eth address moves the sender to the account derived from it
+    ///
+    ///   0. `[]` Reward manager
+    ///   1. `[signer]` Manager account
+    ///   2. `[]` Reward manager authority
+    ///   3. `[signer]` Funder account - pays for the moved sender and gets the old one's rent
+    ///   4. `[writable]` Sender
+    ///   5. `[writable]` Updated sender - the sender itself unless the eth address changes
+    ///   6. `[]` System program id
+    ///   7. `[]` Rent sysvar
+    UpdateSender(UpdateSenderArgs),
+
+    ///   Change the eth address or the operator of a sender with other senders attesting as proof.
+    ///   A new eth address moves the sender to the account derived from it.
+    ///   Senders sign the current eth address and operator along with the new ones
+    ///
+    ///   0. `[]` Reward manager
+    ///   1. `[]` Reward manager authority
+    ///   2. `[signer]` Funder - pays for the moved sender and gets the old one's rent
+    ///   3. `[writable]` Sender
+    ///   4. `[writable]` Updated sender - the sender itself unless the eth address changes
+    ///   5. `[]` Instruction info
+    ///   6. `[]` Rent sysvar
+    ///   7. `[]` System program id
+    ///   8. `[]` Bunch of senders which prove the update
+    UpdateSenderPublic(UpdateSenderArgs),
+
+    ///   Update the age after which verified messages can be closed,
+    ///   legacy reward managers can't store it
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Manager account
+    UpdateVerifiedMessagesMaxAge(UpdateVerifiedMessagesMaxAgeArgs),
+
+    ///   Move a legacy reward manager to the current layout, keeping its
+    ///   token account, manager and min votes. The rest gets the defaults of `init`
+    ///
+    ///   0. `[writable]` Reward manager
+    ///   1. `[signer]` Manager account
+    ///   2. `[writable, signer]` Funder - tops up the rent of the grown account
+    ///   3. `[]` System program id
+    ///   4. `[]` Rent sysvar
+    MigrateRewardManager,
+
+    ///   `SubmitAttestations` for transfer ids of any length, deriving the
+    ///   verified messages from `hashed_transfer_id_seed`
+    ///
+    ///   Same accounts as `SubmitAttestations`
+    SubmitAttestationsV2(SubmitAttestationsArgs),
+
+    ///   `EvaluateAttestations` for transfer ids of any length, deriving the verified
+    ///   messages and the transfer account from `hashed_transfer_id_seed`.
+    ///   Ids that fit into a seed also mark the legacy transfer account, so neither
+    ///   version pays the same transfer twice
+    ///
+    ///   0-10. Same accounts as `EvaluateAttestations`
+    ///  11. `[writable]` Legacy transfer account - derived from `transfer_id_seed`,
+    ///      the transfer account itself for ids that don't fit into a seed
+    EvaluateAttestationsV2(EvaluateAttestationsArgs),
+
+    ///   `EvaluateAttestationsBatch` for transfer ids of any length,
+    ///   deriving the accounts like `EvaluateAttestationsV2`
+    ///
+    ///   0-7. Same accounts as `EvaluateAttestationsBatch`
+    ///   For every transfer:
+    ///   8 + 4 * i. `[writable]` Verified messages
+    ///   9 + 4 * i. `[writable]` Reward token recipient - claimable-tokens user bank of `eth_recipient`
+    ///  10 + 4 * i. `[writable]` Transfer account
+    ///  11 + 4 * i. `[writable]` Legacy transfer account
+    EvaluateAttestationsBatchV2(EvaluateAttestationsBatchArgs),
+
+    ///   `SubmitAndEvaluate` for transfer ids of any length,
+    ///   deriving the accounts like `EvaluateAttestationsV2`
+    ///
+    ///   0-10. Same accounts as `SubmitAndEvaluate`
+    ///  11. `[writable]` Legacy transfer account
+    ///  12. `[]` Bunch of senders which attest the transfer
+    SubmitAndEvaluateV2(EvaluateAttestationsArgs),
 }
 
 /// Create `InitRewardManager` instruction
@@ -185,7 +413,7 @@ pub fn change_manager_authority(
     let accounts = vec![
         AccountMeta::new(*reward_manager, false),
         AccountMeta::new_readonly(*current_authority, true),
-        AccountMeta::new_readonly(*new_authority, false),
+        AccountMeta::new_readonly(*new_authority, true),
     ];
 
     Ok(Instruction {
@@ -355,7 +583,7 @@ where
     })
 }
 
-/// Create `SubmitAttestations` instruction
+/// Create `SubmitAttestations` instruction, `id` must fit into a seed
 pub fn submit_attestations(
     program_id: &Pubkey,
     reward_manager: &Pubkey,
@@ -363,18 +591,55 @@ pub fn submit_attestations(
     funder: &Pubkey,
     id: String,
 ) -> Result<Instruction, ProgramError> {
-    let data =
-        Instructions::SubmitAttestations(SubmitAttestationsArgs { id: id.clone() })
-            .try_to_vec()?;
+    submit_attestations_with_seed(
+        program_id,
+        reward_manager,
+        sender,
+        funder,
+        id,
+        TransferIdSeed::Raw,
+    )
+}
+
+/// Create `SubmitAttestationsV2` instruction
+pub fn submit_attestations_v2(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    sender: &Pubkey,
+    funder: &Pubkey,
+    id: String,
+) -> Result<Instruction, ProgramError> {
+    submit_attestations_with_seed(
+        program_id,
+        reward_manager,
+        sender,
+        funder,
+        id,
+        TransferIdSeed::Hashed,
+    )
+}
 
+fn submit_attestations_with_seed(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    sender: &Pubkey,
+    funder: &Pubkey,
+    id: String,
+    seed: TransferIdSeed,
+) -> Result<Instruction, ProgramError> {
     let (reward_manager_authority, verified_messages, _) = find_derived_pair(
         program_id,
         reward_manager,
-        [VERIFY_TRANSFER_SEED_PREFIX.as_bytes().as_ref(), id.as_ref()]
-            .concat()
-            .as_ref(),
+        &seed.seed(VERIFY_TRANSFER_SEED_PREFIX, &id)?,
     );
 
+    let args = SubmitAttestationsArgs { id };
+    let data = match seed {
+        TransferIdSeed::Raw => Instructions::SubmitAttestations(args),
+        TransferIdSeed::Hashed => Instructions::SubmitAttestationsV2(args),
+    }
+    .try_to_vec()?;
+
     let accounts = vec![
         AccountMeta::new(verified_messages, false),
         AccountMeta::new_readonly(*reward_manager, false),
@@ -393,7 +658,7 @@ pub fn submit_attestations(
     })
 }
 
-/// Create `Evaluate attestation` instruction
+/// Create `Evaluate attestation` instruction, `id` must fit into a seed
 #[allow(clippy::too_many_arguments)]
 pub fn evaluate_attestations(
     program_id: &Pubkey,
@@ -407,24 +672,75 @@ pub fn evaluate_attestations(
     id: String,
     eth_recipient: [u8; 20],
 ) -> Result<Instruction, ProgramError> {
-    let data = Instructions::EvaluateAttestations(EvaluateAttestationsArgs {
-        amount,
-        id: id.clone(),
-        eth_recipient,
-    })
-    .try_to_vec()?;
+    evaluate_attestations_with_seed(
+        program_id,
+        verified_messages,
+        reward_manager,
+        reward_token_source,
+        reward_token_recipient,
+        bot_oracle,
+        payer,
+        EvaluateAttestationsArgs {
+            amount,
+            id,
+            eth_recipient,
+        },
+        TransferIdSeed::Raw,
+    )
+}
+
+/// Create `EvaluateAttestationsV2` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_attestations_v2(
+    program_id: &Pubkey,
+    verified_messages: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    reward_token_recipient: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    amount: u64,
+    id: String,
+    eth_recipient: [u8; 20],
+) -> Result<Instruction, ProgramError> {
+    evaluate_attestations_with_seed(
+        program_id,
+        verified_messages,
+        reward_manager,
+        reward_token_source,
+        reward_token_recipient,
+        bot_oracle,
+        payer,
+        EvaluateAttestationsArgs {
+            amount,
+            id,
+            eth_recipient,
+        },
+        TransferIdSeed::Hashed,
+    )
+}
 
+#[allow(clippy::too_many_arguments)]
+fn evaluate_attestations_with_seed(
+    program_id: &Pubkey,
+    verified_messages: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    reward_token_recipient: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    args: EvaluateAttestationsArgs,
+    seed: TransferIdSeed,
+) -> Result<Instruction, ProgramError> {
     let (reward_manager_authority, derived_address, _) = find_derived_pair(
         program_id,
         reward_manager,
-        [TRANSFER_SEED_PREFIX.as_bytes().as_ref(), id.as_ref()]
-            .concat()
-            .as_ref(),
+        &seed.seed(TRANSFER_SEED_PREFIX, &args.id)?,
     );
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*verified_messages, false),
-        AccountMeta::new_readonly(*reward_manager, false),
+        AccountMeta::new(*reward_manager, false),
         AccountMeta::new_readonly(reward_manager_authority, false),
         AccountMeta::new(*reward_token_source, false),
         AccountMeta::new(*reward_token_recipient, false),
@@ -436,6 +752,628 @@ pub fn evaluate_attestations(
         AccountMeta::new_readonly(system_program::id(), false),
     ];
 
+    let data = match seed {
+        TransferIdSeed::Raw => Instructions::EvaluateAttestations(args),
+        TransferIdSeed::Hashed => {
+            accounts.push(AccountMeta::new(
+                legacy_transfer_account(program_id, reward_manager, &args.id, &derived_address),
+                false,
+            ));
+            Instructions::EvaluateAttestationsV2(args)
+        }
+    }
+    .try_to_vec()?;
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Transfer account the legacy instructions derive for `id`, ids that don't
+/// fit into a seed never had one and use `transfer_account` instead
+fn legacy_transfer_account(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    id: &str,
+    transfer_account: &Pubkey,
+) -> Pubkey {
+    match transfer_id_seed(TRANSFER_SEED_PREFIX, id) {
+        Ok(seed) => find_derived_pair(program_id, reward_manager, &seed).1,
+        Err(_) => *transfer_account,
+    }
+}
+
+/// Create `UpdateRewardManagerConfig` instruction
+pub fn update_reward_manager_config(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+    min_votes: u8,
+) -> Result<Instruction, ProgramError> {
+    let data =
+        Instructions::UpdateRewardManagerConfig(UpdateRewardManagerConfigArgs { min_votes })
+            .try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*manager_account, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `UpdateRewardManagerConfigPublic` instruction
+pub fn update_reward_manager_config_public<'a, I>(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    min_votes: u8,
+    signers: I,
+) -> Result<Instruction, ProgramError>
+where
+    I: IntoIterator<Item = &'a Pubkey>,
+{
+    let data =
+        Instructions::UpdateRewardManagerConfigPublic(UpdateRewardManagerConfigArgs { min_votes })
+            .try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ];
+    let iter = signers
+        .into_iter()
+        .map(|i| AccountMeta::new_readonly(*i, false));
+    accounts.extend(iter);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `CloseVerifiedMessages` instruction
+pub fn close_verified_messages(
+    program_id: &Pubkey,
+    verified_messages: &Pubkey,
+    reward_manager: &Pubkey,
+    funder: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::CloseVerifiedMessages.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*verified_messages, false),
+        AccountMeta::new_readonly(*reward_manager, false),
+        AccountMeta::new(*funder, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `CloseVerifiedMessages` instruction for legacy verified messages,
+/// signed and refunded to the manager
+pub fn close_legacy_verified_messages(
+    program_id: &Pubkey,
+    verified_messages: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::CloseVerifiedMessages.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*verified_messages, false),
+        AccountMeta::new_readonly(*reward_manager, false),
+        AccountMeta::new(*manager_account, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `SetPaused` instruction
+pub fn set_paused(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+    paused: bool,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::SetPaused(SetPausedArgs { paused }).try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*manager_account, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `ProposeManager` instruction
+pub fn propose_manager(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    current_manager: &Pubkey,
+    proposed_manager: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::ProposeManager.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*current_manager, true),
+        AccountMeta::new_readonly(*proposed_manager, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `AcceptManager` instruction
+pub fn accept_manager(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    proposed_manager: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::AcceptManager.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*proposed_manager, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `CancelManagerProposal` instruction
+pub fn cancel_manager_proposal(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    current_manager: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::CancelManagerProposal.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*current_manager, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `UpdateDisbursementLimits` instruction
+pub fn update_disbursement_limits(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+    max_transfer_amount: u64,
+    max_window_amount: u64,
+    window_slots: Slot,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::UpdateDisbursementLimits(UpdateDisbursementLimitsArgs {
+        max_transfer_amount,
+        max_window_amount,
+        window_slots,
+    })
+    .try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*manager_account, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `EvaluateAttestationsBatch` instruction,
+/// each transfer is paired with its reward token recipient.
+/// Every id must fit into a seed
+pub fn evaluate_attestations_batch(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    transfers: Vec<(EvaluateAttestationsArgs, Pubkey)>,
+) -> Result<Instruction, ProgramError> {
+    evaluate_attestations_batch_with_seed(
+        program_id,
+        reward_manager,
+        reward_token_source,
+        bot_oracle,
+        payer,
+        transfers,
+        TransferIdSeed::Raw,
+    )
+}
+
+/// Create `EvaluateAttestationsBatchV2` instruction,
+/// each transfer is paired with its reward token recipient
+pub fn evaluate_attestations_batch_v2(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    transfers: Vec<(EvaluateAttestationsArgs, Pubkey)>,
+) -> Result<Instruction, ProgramError> {
+    evaluate_attestations_batch_with_seed(
+        program_id,
+        reward_manager,
+        reward_token_source,
+        bot_oracle,
+        payer,
+        transfers,
+        TransferIdSeed::Hashed,
+    )
+}
+
+fn evaluate_attestations_batch_with_seed(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    transfers: Vec<(EvaluateAttestationsArgs, Pubkey)>,
+    seed: TransferIdSeed,
+) -> Result<Instruction, ProgramError> {
+    let (reward_manager_authority, _) = find_program_address(program_id, reward_manager);
+
+    let mut accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(reward_manager_authority, false),
+        AccountMeta::new(*reward_token_source, false),
+        AccountMeta::new_readonly(*bot_oracle, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let mut args = Vec::new();
+    for (transfer, reward_token_recipient) in transfers {
+        let (_, verified_messages, _) = find_derived_pair(
+            program_id,
+            reward_manager,
+            &seed.seed(VERIFY_TRANSFER_SEED_PREFIX, &transfer.id)?,
+        );
+        let (_, transfer_account, _) = find_derived_pair(
+            program_id,
+            reward_manager,
+            &seed.seed(TRANSFER_SEED_PREFIX, &transfer.id)?,
+        );
+        accounts.push(AccountMeta::new(verified_messages, false));
+        accounts.push(AccountMeta::new(reward_token_recipient, false));
+        accounts.push(AccountMeta::new(transfer_account, false));
+        if seed == TransferIdSeed::Hashed {
+            let legacy_account = legacy_transfer_account(
+                program_id,
+                reward_manager,
+                &transfer.id,
+                &transfer_account,
+            );
+            accounts.push(AccountMeta::new(legacy_account, false));
+        }
+        args.push(transfer);
+    }
+
+    let args = EvaluateAttestationsBatchArgs { transfers: args };
+    let data = match seed {
+        TransferIdSeed::Raw => Instructions::EvaluateAttestationsBatch(args),
+        TransferIdSeed::Hashed => Instructions::EvaluateAttestationsBatchV2(args),
+    }
+    .try_to_vec()?;
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `SubmitAndEvaluate` instruction, `id` must fit into a seed
+#[allow(clippy::too_many_arguments)]
+pub fn submit_and_evaluate<'a, I>(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    reward_token_recipient: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    senders: I,
+    amount: u64,
+    id: String,
+    eth_recipient: EthereumAddress,
+) -> Result<Instruction, ProgramError>
+where
+    I: IntoIterator<Item = &'a Pubkey>,
+{
+    submit_and_evaluate_with_seed(
+        program_id,
+        reward_manager,
+        reward_token_source,
+        reward_token_recipient,
+        bot_oracle,
+        payer,
+        senders,
+        EvaluateAttestationsArgs {
+            amount,
+            id,
+            eth_recipient,
+        },
+        TransferIdSeed::Raw,
+    )
+}
+
+/// Create `SubmitAndEvaluateV2` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn submit_and_evaluate_v2<'a, I>(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    reward_token_recipient: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    senders: I,
+    amount: u64,
+    id: String,
+    eth_recipient: EthereumAddress,
+) -> Result<Instruction, ProgramError>
+where
+    I: IntoIterator<Item = &'a Pubkey>,
+{
+    submit_and_evaluate_with_seed(
+        program_id,
+        reward_manager,
+        reward_token_source,
+        reward_token_recipient,
+        bot_oracle,
+        payer,
+        senders,
+        EvaluateAttestationsArgs {
+            amount,
+            id,
+            eth_recipient,
+        },
+        TransferIdSeed::Hashed,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn submit_and_evaluate_with_seed<'a, I>(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    reward_token_source: &Pubkey,
+    reward_token_recipient: &Pubkey,
+    bot_oracle: &Pubkey,
+    payer: &Pubkey,
+    senders: I,
+    args: EvaluateAttestationsArgs,
+    seed: TransferIdSeed,
+) -> Result<Instruction, ProgramError>
+where
+    I: IntoIterator<Item = &'a Pubkey>,
+{
+    let (reward_manager_authority, transfer_account, _) = find_derived_pair(
+        program_id,
+        reward_manager,
+        &seed.seed(TRANSFER_SEED_PREFIX, &args.id)?,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(reward_manager_authority, false),
+        AccountMeta::new(*reward_token_source, false),
+        AccountMeta::new(*reward_token_recipient, false),
+        AccountMeta::new(transfer_account, false),
+        AccountMeta::new_readonly(*bot_oracle, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let data = match seed {
+        TransferIdSeed::Raw => Instructions::SubmitAndEvaluate(args),
+        TransferIdSeed::Hashed => {
+            accounts.push(AccountMeta::new(
+                legacy_transfer_account(program_id, reward_manager, &args.id, &transfer_account),
+                false,
+            ));
+            Instructions::SubmitAndEvaluateV2(args)
+        }
+    }
+    .try_to_vec()?;
+
+    let iter = senders
+        .into_iter()
+        .map(|i| AccountMeta::new_readonly(*i, false));
+    accounts.extend(iter);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `UpdateSender` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn update_sender(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+    funder_account: &Pubkey,
+    eth_address: EthereumAddress,
+    new_eth_address: EthereumAddress,
+    new_operator: EthereumAddress,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::UpdateSender(UpdateSenderArgs {
+        eth_address: new_eth_address,
+        operator: new_operator,
+    })
+    .try_to_vec()?;
+
+    let (reward_manager_authority, sender, _) = find_derived_pair(
+        program_id,
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    let (_, updated_sender, _) = find_derived_pair(
+        program_id,
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), new_eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*reward_manager, false),
+        AccountMeta::new_readonly(*manager_account, true),
+        AccountMeta::new_readonly(reward_manager_authority, false),
+        AccountMeta::new(*funder_account, true),
+        AccountMeta::new(sender, false),
+        AccountMeta::new(updated_sender, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `UpdateSenderPublic` instruction
+pub fn update_sender_public<'a, I>(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    funder: &Pubkey,
+    eth_address: EthereumAddress,
+    new_eth_address: EthereumAddress,
+    new_operator: EthereumAddress,
+    signers: I,
+) -> Result<Instruction, ProgramError>
+where
+    I: IntoIterator<Item = &'a Pubkey>,
+{
+    let data = Instructions::UpdateSenderPublic(UpdateSenderArgs {
+        eth_address: new_eth_address,
+        operator: new_operator,
+    })
+    .try_to_vec()?;
+
+    let (reward_manager_authority, sender, _) = find_derived_pair(
+        program_id,
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    let (_, updated_sender, _) = find_derived_pair(
+        program_id,
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), new_eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*reward_manager, false),
+        AccountMeta::new_readonly(reward_manager_authority, false),
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(sender, false),
+        AccountMeta::new(updated_sender, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let iter = signers
+        .into_iter()
+        .map(|i| AccountMeta::new_readonly(*i, false));
+    accounts.extend(iter);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `UpdateVerifiedMessagesMaxAge` instruction
+pub fn update_verified_messages_max_age(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+    max_age: Slot,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::UpdateVerifiedMessagesMaxAge(UpdateVerifiedMessagesMaxAgeArgs {
+        max_age,
+    })
+    .try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*manager_account, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `MigrateRewardManager` instruction
+pub fn migrate_reward_manager(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    manager_account: &Pubkey,
+    funder: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = Instructions::MigrateRewardManager.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*reward_manager, false),
+        AccountMeta::new_readonly(*manager_account, true),
+        AccountMeta::new(*funder, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
     Ok(Instruction {
         program_id: *program_id,
         accounts,
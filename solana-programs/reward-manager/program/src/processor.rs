@@ -4,11 +4,14 @@ use crate::{
     error::AudiusProgramError,
     instruction::{
         CreateSenderPublicArgs, CreateSenderArgs, InitRewardManagerArgs, Instructions, EvaluateAttestationsArgs,
-        SubmitAttestationsArgs,
+        EvaluateAttestationsBatchArgs,
+        SetPausedArgs, SubmitAttestationsArgs, UpdateDisbursementLimitsArgs,
+        UpdateRewardManagerConfigArgs, UpdateSenderArgs, UpdateVerifiedMessagesMaxAgeArgs,
     },
     state::{
-        RewardManager, SenderAccount, VerifiedMessage, VerifiedMessages, ADD_SENDER_MESSAGE_PREFIX,
-        DELETE_SENDER_MESSAGE_PREFIX,
+        RewardManager, SenderAccount, VerifiedMessage, VerifiedMessages, ADD_SENDER_MESSAGE_EIP191,
+        ADD_SENDER_MESSAGE_PREFIX, DELETE_SENDER_MESSAGE_EIP191, DELETE_SENDER_MESSAGE_PREFIX,
+        TRANSFER_MESSAGE_PREFIX, VERIFIED_MESSAGES_VERSION,
     },
     utils::*,
 };
@@ -16,6 +19,7 @@ use borsh::BorshDeserialize;
 use solana_program::{
     account_info::next_account_info,
     account_info::AccountInfo,
+    clock::{Clock, Slot},
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
@@ -38,15 +42,6 @@ pub const TRANSFER_ACC_SPACE: usize = 0;
 pub struct Processor;
 
 impl Processor {
-    /// Transfer all the SOL from source to receiver
-    pub fn transfer_all(source: &AccountInfo, receiver: &AccountInfo) -> Result<(), ProgramError> {
-        let mut from = source.try_borrow_mut_lamports()?;
-        let mut to = receiver.try_borrow_mut_lamports()?;
-        **to += **from;
-        **from = 0;
-        Ok(())
-    }
-
     /// Process init instruction
     /// Initializes the token account and creates a RewardManager account
     /// with `min_votes`, `token_account_info`, and `manager_info`.
@@ -67,6 +62,7 @@ impl Processor {
         let mut reward_manager =
             RewardManager::unpack_unchecked(&reward_manager_info.data.borrow())?;
         assert_uninitialized(&reward_manager)?;
+        assert_valid_min_votes(min_votes)?;
 
         // Find the reward_manager_authority, and test it against
         // `authority_info` to ensure the correct 
@@ -93,7 +89,7 @@ impl Processor {
 
     /// Process change_manager_account instruction.
     /// Changes the `manager` field on the `RewardManager` account,
-    /// provided that the transaction is signed by the current manager.
+    /// provided that the transaction is signed by both the current and the new manager.
     fn process_change_manager_account<'a>(
         reward_manager_info: &AccountInfo<'a>,
         current_manager_info: &AccountInfo<'a>,
@@ -102,7 +98,7 @@ impl Processor {
         // Note: we don't have to assert that we own the `reward_manager` account 
         // as we would normally, because in writing to it the runtime 
         // enforces ownership
-        if !current_manager_info.is_signer {
+        if !current_manager_info.is_signer || !new_manager_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
@@ -110,7 +106,272 @@ impl Processor {
         assert_account_key(current_manager_info, &reward_manager.manager)?;
 
         reward_manager.manager = *new_manager_info.key;
+        reward_manager.pending_manager = Pubkey::default();
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `update_disbursement_limits` instruction.
+    /// Updates limits on disbursed rewards on the `RewardManager` account,
+    /// provided that the transaction is signed by the current manager.
+    fn process_update_disbursement_limits<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        manager_account_info: &AccountInfo<'a>,
+        limits: UpdateDisbursementLimitsArgs,
+    ) -> ProgramResult {
+        if !manager_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(manager_account_info, &reward_manager.manager)?;
+        assert_valid_disbursement_limits(
+            limits.max_transfer_amount,
+            limits.max_window_amount,
+            limits.window_slots,
+        )?;
+
+        reward_manager.max_transfer_amount = limits.max_transfer_amount;
+        reward_manager.max_window_amount = limits.max_window_amount;
+        reward_manager.window_slots = limits.window_slots;
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `propose_manager` instruction.
+    /// Stores `proposed_manager_info` as the `pending_manager`,
+    /// provided that the transaction is signed by the current manager.
+    fn process_propose_manager<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        current_manager_info: &AccountInfo<'a>,
+        proposed_manager_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if !current_manager_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(current_manager_info, &reward_manager.manager)?;
+
+        reward_manager.pending_manager = *proposed_manager_info.key;
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `accept_manager` instruction.
+    /// Makes the `pending_manager` the `manager` of the `RewardManager` account,
+    /// provided that the transaction is signed by the pending manager.
+    fn process_accept_manager<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        proposed_manager_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if !proposed_manager_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        if reward_manager.pending_manager == Pubkey::default() {
+            return Err(AudiusProgramError::NoPendingManager.into());
+        }
+        assert_account_key(proposed_manager_info, &reward_manager.pending_manager)?;
+
+        reward_manager.manager = reward_manager.pending_manager;
+        reward_manager.pending_manager = Pubkey::default();
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `cancel_manager_proposal` instruction.
+    /// Clears the `pending_manager` on the `RewardManager` account,
+    /// provided that the transaction is signed by the current manager.
+    fn process_cancel_manager_proposal<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        current_manager_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if !current_manager_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(current_manager_info, &reward_manager.manager)?;
+        if reward_manager.pending_manager == Pubkey::default() {
+            return Err(AudiusProgramError::NoPendingManager.into());
+        }
+
+        reward_manager.pending_manager = Pubkey::default();
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `update_reward_manager_config` instruction.
+    /// Updates `min_votes` on the `RewardManager` account,
+    /// provided that the transaction is signed by the current manager.
+    fn process_update_reward_manager_config<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        manager_account_info: &AccountInfo<'a>,
+        min_votes: u8,
+    ) -> ProgramResult {
+        if !manager_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(manager_account_info, &reward_manager.manager)?;
+        assert_valid_min_votes(min_votes)?;
+
+        reward_manager.min_votes = min_votes;
+        reward_manager.bump_config_nonce()?;
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `update_reward_manager_config_public` instruction.
+    /// Ensures a quorum of the current senders signed the new config,
+    /// then updates `min_votes` on the `RewardManager` account.
+    fn process_update_reward_manager_config_public<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        instructions_info: &AccountInfo<'a>,
+        signers_info: Vec<&AccountInfo>,
+        min_votes: u8,
+    ) -> ProgramResult {
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+
+        // Verify we have a sufficient amount of signers
+        if signers_info.len() < reward_manager.min_votes.into() {
+            return Err(AudiusProgramError::NotEnoughSigners.into());
+        }
+
+        // Verify signers are as expected
+        validate_secp_update_config(
+            program_id,
+            reward_manager_info.key,
+            instructions_info,
+            signers_info.clone(),
+            signers_info.len(),
+            reward_manager.config_nonce,
+            min_votes,
+        )?;
+        assert_valid_min_votes(min_votes)?;
+
+        reward_manager.min_votes = min_votes;
+        reward_manager.bump_config_nonce()?;
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `set_paused` instruction.
+    /// Toggles `paused` on the `RewardManager` account,
+    /// provided that the transaction is signed by the current manager.
+    fn process_set_paused<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        manager_account_info: &AccountInfo<'a>,
+        paused: bool,
+    ) -> ProgramResult {
+        if !manager_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(manager_account_info, &reward_manager.manager)?;
+
+        reward_manager.paused = paused;
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `update_verified_messages_max_age` instruction.
+    /// Updates the age after which verified messages can be closed,
+    /// provided that the transaction is signed by the current manager.
+    fn process_update_verified_messages_max_age<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        manager_account_info: &AccountInfo<'a>,
+        max_age: Slot,
+    ) -> ProgramResult {
+        if !manager_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(manager_account_info, &reward_manager.manager)?;
+
+        reward_manager.verified_messages_max_age = max_age;
+
+        RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `migrate_reward_manager` instruction.
+    /// Grows a legacy reward manager to `RewardManager::LEN` and rewrites it
+    /// at the current version, provided that the transaction is signed by the manager.
+    fn process_migrate_reward_manager<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        manager_account_info: &AccountInfo<'a>,
+        funder_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if !manager_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(manager_account_info, &reward_manager.manager)?;
+
+        if !reward_manager.is_legacy() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let rent = Rent::from_account_info(rent_info)?;
+        resize_account(reward_manager_info, funder_info, RewardManager::LEN, &rent)?;
 
+        let reward_manager = RewardManager::new(
+            reward_manager.token_account,
+            reward_manager.manager,
+            reward_manager.min_votes,
+        );
         RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
 
         Ok(())
@@ -173,7 +434,7 @@ impl Processor {
     }
 
     /// Process `delete_sender` instruction.
-    /// Deletes a sender by transfering all of it's balance to the `refunder_account`.
+    /// Deletes a sender by closing it and refunding its balance to the `refunder_account`.
     /// Must be signed by the `manager_account_info`.
     fn process_delete_sender<'a>(
         program_id: &Pubkey,
@@ -196,14 +457,14 @@ impl Processor {
         let sender_account = SenderAccount::unpack(&sender_info.data.borrow())?;
         assert_account_key(reward_manager_info, &sender_account.reward_manager)?;
 
-        Self::transfer_all(sender_info, refunder_account_info)?;
+        close_account(sender_info, refunder_account_info)?;
 
         Ok(())
     }
 
     /// Process `delete_sender_public` instruction.
     /// Ensures signers are correct, then deletes the `sender_info`
-    /// by transferring its balance to `refunder_info`.
+    /// by closing it and refunding its balance to `refunder_info`.
     fn process_delete_sender_public<'a>(
         program_id: &Pubkey,
         reward_manager_info: &AccountInfo<'a>,
@@ -232,11 +493,12 @@ impl Processor {
             signers_info.len(),
             sender_account.eth_address,
             DELETE_SENDER_MESSAGE_PREFIX,
+            DELETE_SENDER_MESSAGE_EIP191,
         )?;
 
         assert_account_key(reward_manager_info, &sender_account.reward_manager)?;
 
-        Self::transfer_all(sender_info, refunder_info)?;
+        close_account(sender_info, refunder_info)?;
 
         Ok(())
     }
@@ -257,6 +519,7 @@ impl Processor {
         assert_owned_by(reward_manager_info, program_id)?;
 
         let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_not_paused(&reward_manager)?;
 
         // Verify we have a sufficient amount of signers
         if signers_info.len() < reward_manager.min_votes.into() {
@@ -272,6 +535,7 @@ impl Processor {
             signers_info.len(),
             eth_address,
             ADD_SENDER_MESSAGE_PREFIX,
+            ADD_SENDER_MESSAGE_EIP191,
         )?;
 
         // Ensure `new_sender_info` matches `derived_sender_info`, generated 
@@ -306,39 +570,206 @@ impl Processor {
         Ok(())
     }
 
+    /// Writes the new `eth_address` and `operator` of the sender in `sender_info`.
+    /// A new eth address moves the sender to `updated_sender_info`, the account
+    /// derived from it, and the old account's balance goes to `funder_info`.
     #[allow(clippy::too_many_arguments)]
-    fn process_submit_attestations<'a>(
+    fn update_sender<'a>(
         program_id: &Pubkey,
-        verified_messages_info: &AccountInfo<'a>,
         reward_manager_info: &AccountInfo<'a>,
         authority_info: &AccountInfo<'a>,
         funder_info: &AccountInfo<'a>,
+        sender_info: &AccountInfo<'a>,
+        updated_sender_info: &AccountInfo<'a>,
         rent_info: &AccountInfo<'a>,
+        mut sender_account: SenderAccount,
+        eth_address: EthereumAddress,
+        operator: EthereumAddress,
+    ) -> ProgramResult {
+        // Only the operator changes, the sender stays in place
+        if eth_address == sender_account.eth_address {
+            assert_account_key(updated_sender_info, sender_info.key)?;
+
+            sender_account.operator = operator;
+            SenderAccount::pack(sender_account, *sender_info.data.borrow_mut())?;
+
+            return Ok(());
+        }
+
+        // Derive the new sender address from the new eth_address, and assert it matches `updated_sender_info`
+        let sender_seed = [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()].concat();
+        let (reward_manager_authority, derived_sender_address, bump_seed) =
+            find_derived_pair(program_id, reward_manager_info.key, sender_seed.as_ref());
+
+        assert_account_key(authority_info, &reward_manager_authority)?;
+        assert_account_key(updated_sender_info, &derived_sender_address)?;
+
+        let signers_seeds = &[
+            &reward_manager_authority.to_bytes()[..32],
+            sender_seed.as_slice(),
+            &[bump_seed],
+        ];
+
+        // Create the account, fails if a sender already uses the new eth address
+        let rent = Rent::from_account_info(rent_info)?;
+        create_account(
+            program_id,
+            funder_info.clone(),
+            updated_sender_info.clone(),
+            SenderAccount::LEN,
+            &[signers_seeds],
+            &rent,
+        )?;
+
+        let updated_sender_account = SenderAccount::new(*reward_manager_info.key, eth_address, operator);
+        SenderAccount::pack(updated_sender_account, *updated_sender_info.data.borrow_mut())?;
+
+        // Delete the sender at the old eth address
+        close_account(sender_info, funder_info)?;
+
+        Ok(())
+    }
+
+    /// Process `update_sender` instruction.
+    /// Changes the eth address or the operator of a sender.
+    /// Must be signed by the `manager_account_info`.
+    #[allow(clippy::too_many_arguments)]
+    fn process_update_sender<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        manager_account_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        funder_info: &AccountInfo<'a>,
         sender_info: &AccountInfo<'a>,
-        instruction_info: &AccountInfo<'a>,
-        verify_transfer_data: SubmitAttestationsArgs,
+        updated_sender_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        eth_address: EthereumAddress,
+        operator: EthereumAddress,
     ) -> ProgramResult {
+        if !manager_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
         assert_owned_by(reward_manager_info, program_id)?;
         assert_owned_by(sender_info, program_id)?;
 
-        // Retrieve the sender account, assert that 
-        // the sender's `reward_manager` is this `reward_manager`.
+        let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_account_key(manager_account_info, &reward_manager.manager)?;
+
         let sender_account = SenderAccount::unpack(&sender_info.data.borrow())?;
         assert_account_key(reward_manager_info, &sender_account.reward_manager)?;
 
-        // Derive the verified messages account from the transfer_data and seed prefix,
+        Self::update_sender(
+            program_id,
+            reward_manager_info,
+            authority_info,
+            funder_info,
+            sender_info,
+            updated_sender_info,
+            rent_info,
+            sender_account,
+            eth_address,
+            operator,
+        )
+    }
+
+    /// Process `update_sender_public` instruction.
+    /// Ensures a quorum of senders signed the update,
+    /// then changes the eth address or the operator of a sender.
+    #[allow(clippy::too_many_arguments)]
+    fn process_update_sender_public<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        funder_info: &AccountInfo<'a>,
+        sender_info: &AccountInfo<'a>,
+        updated_sender_info: &AccountInfo<'a>,
+        instructions_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        signers_info: Vec<&AccountInfo>,
+        eth_address: EthereumAddress,
+        operator: EthereumAddress,
+    ) -> ProgramResult {
+        assert_owned_by(reward_manager_info, program_id)?;
+        assert_owned_by(sender_info, program_id)?;
+
+        let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        let sender_account = SenderAccount::unpack(&sender_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &sender_account.reward_manager)?;
+
+        // Verify we have a sufficient amount of signers
+        if signers_info.len() < reward_manager.min_votes.into() {
+            return Err(AudiusProgramError::NotEnoughSigners.into());
+        }
+
+        // Verify signers are as expected
+        validate_secp_update_sender(
+            program_id,
+            reward_manager_info.key,
+            instructions_info,
+            signers_info.clone(),
+            signers_info.len(),
+            sender_account.eth_address,
+            sender_account.operator,
+            eth_address,
+            operator,
+        )?;
+
+        Self::update_sender(
+            program_id,
+            reward_manager_info,
+            authority_info,
+            funder_info,
+            sender_info,
+            updated_sender_info,
+            rent_info,
+            sender_account,
+            eth_address,
+            operator,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_submit_attestations<'a>(
+        program_id: &Pubkey,
+        verified_messages_info: &AccountInfo<'a>,
+        reward_manager_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        funder_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        sender_info: &AccountInfo<'a>,
+        instruction_info: &AccountInfo<'a>,
+        verify_transfer_data: SubmitAttestationsArgs,
+        seed: TransferIdSeed,
+    ) -> ProgramResult {
+        assert_owned_by(reward_manager_info, program_id)?;
+        assert_owned_by(sender_info, program_id)?;
+
+        // Retrieve the sender account, assert that 
+        // the sender's `reward_manager` is this `reward_manager`.
+        let sender_account = SenderAccount::unpack(&sender_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &sender_account.reward_manager)?;
+
+        // Derive the verified messages account from the transfer_data and seed prefix,
         // and ensure that the account matches `verified_messages_info` before proceeding.
-        let verified_messages_account_seed = [
-            VERIFY_TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            verify_transfer_data.id.as_ref(),
-        ].concat();
+        let verified_messages_account_seed =
+            seed.seed(VERIFY_TRANSFER_SEED_PREFIX, &verify_transfer_data.id)?;
         let (reward_manager_authority, derived_verified_messages_account, bump_seed) =
             find_derived_pair(program_id, reward_manager_info.key, verified_messages_account_seed.as_ref());
         assert_account_key(authority_info, &reward_manager_authority)?;
         assert_account_key(verified_messages_info, &derived_verified_messages_account)?;
 
+        // Room for `min_votes` senders and the bot oracle
+        let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_not_paused(&reward_manager)?;
+        let capacity = reward_manager
+            .min_votes
+            .checked_add(1)
+            .ok_or(AudiusProgramError::MathOverflow)?;
+
         // If the verified messages account doesn't exist, create it. Otherwise, 
         // ensure that we own it before proceeding.
+        let rent = Rent::from_account_info(rent_info)?;
         if verified_messages_info.data_len() == 0 && verified_messages_info.lamports() == 0 {
             let signers_seeds = &[
                 &reward_manager_authority.to_bytes()[..32],
@@ -346,17 +777,17 @@ impl Processor {
                 &[bump_seed],
             ];
 
-            let rent = Rent::from_account_info(rent_info)?;
             create_account(
                 program_id,
                 funder_info.clone(),
                 verified_messages_info.clone(),
-                VerifiedMessages::LEN,
+                VerifiedMessages::size(capacity),
                 &[signers_seeds],
                 &rent,
             )?;
         } else {
             assert_owned_by(verified_messages_info, program_id)?;
+            assert_not_closed(verified_messages_info)?;
         }
 
         // Unpack verified messages, initializing it if needed.
@@ -365,25 +796,55 @@ impl Processor {
         if verified_messages.is_initialized() {
             assert_account_key(reward_manager_info, &verified_messages.reward_manager)?;
 
-            // If messages account is full from previous attempt, reset it
-            let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
-            if verified_messages.messages.len() >= (reward_manager.min_votes + 1) as usize {
+            // Grow accounts created before `min_votes` was raised, moving
+            // legacy accounts to the current layout on the way
+            if verified_messages.capacity < capacity {
+                resize_account(
+                    verified_messages_info,
+                    funder_info,
+                    VerifiedMessages::size(capacity),
+                    &rent,
+                )?;
+                if verified_messages.is_legacy() {
+                    verified_messages.version = VERIFIED_MESSAGES_VERSION;
+                    verified_messages.funder = *funder_info.key;
+                    verified_messages.slot = Clock::get()?.slot;
+                    verified_messages.seed = verified_messages_account_seed;
+                }
+                verified_messages.capacity = capacity;
+            }
+
+            // If messages account is full from previous attempt, reset it.
+            // Accounts created for a larger quorum are full once they hold
+            // `min_votes` plus the bot oracle's messages.
+            if verified_messages.messages.len() >= verified_messages.capacity.min(capacity) as usize {
                 verified_messages.messages.clear()
             }
         } else {
-            verified_messages = VerifiedMessages::new(*reward_manager_info.key);
+            verified_messages = VerifiedMessages::new(
+                *reward_manager_info.key,
+                *funder_info.key,
+                Clock::get()?.slot,
+                verified_messages_account_seed,
+                capacity,
+            );
         }
 
         // Check that that previous instruction was a signed vote message,
         // signed by the `sender_account`'s eth address, adding it to the verified_messages
         // account if so.
-        let message = validate_secp_submit_attestation(instruction_info, &sender_account.eth_address)?;
+        let message = validate_secp_submit_attestation(
+            program_id,
+            reward_manager_info.key,
+            instruction_info,
+            &sender_account.eth_address,
+        )?;
 
         verified_messages.add(VerifiedMessage {
             address: sender_account.eth_address,
             message,
             operator: sender_account.operator,
-        });
+        })?;
 
         // Check unique senders & operators
         assert_unique_senders(&verified_messages.messages)?;
@@ -405,7 +866,9 @@ impl Processor {
         bot_oracle_info: &AccountInfo<'a>,
         payer_info: &AccountInfo<'a>,
         rent_info: &AccountInfo<'a>,
+        legacy_transfer_account_info: Option<&AccountInfo<'a>>,
         transfer_data: EvaluateAttestationsArgs,
+        seed: TransferIdSeed,
     ) -> ProgramResult {
         let rent = &Rent::from_account_info(rent_info)?;
 
@@ -413,21 +876,208 @@ impl Processor {
         assert_owned_by(reward_manager_info, program_id)?;
         assert_owned_by(bot_oracle_info, program_id)?;
 
-        let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_not_paused(&reward_manager)?;
+
+        let bot_oracle = SenderAccount::unpack(&bot_oracle_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &bot_oracle.reward_manager)?;
+
+        assert_account_key(reward_token_source_info, &reward_manager.token_account)?;
+        let reward_token_source =
+            spl_token::state::Account::unpack(&reward_token_source_info.data.borrow())?;
+
+        Self::evaluate_transfer(
+            program_id,
+            &mut reward_manager,
+            reward_manager_info,
+            reward_manager_authority_info,
+            reward_token_source_info,
+            &reward_token_source.mint,
+            &bot_oracle,
+            payer_info,
+            rent,
+            Clock::get()?.slot,
+            verified_messages_info,
+            reward_token_recipient_info,
+            transfer_account_info,
+            legacy_transfer_account_info,
+            transfer_data,
+            seed,
+        )?;
+
+        // Legacy accounts don't track disbursements
+        if !reward_manager.is_legacy() {
+            RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    /// Process `evaluate_attestations_batch` instruction.
+    /// Evaluates every transfer like `evaluate_attestations`, sharing
+    /// the reward manager, token source and bot oracle between them.
+    #[allow(clippy::too_many_arguments)]
+    fn process_evaluate_attestations_batch<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        reward_manager_authority_info: &AccountInfo<'a>,
+        reward_token_source_info: &AccountInfo<'a>,
+        bot_oracle_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        transfer_infos: &[AccountInfo<'a>],
+        transfers: Vec<EvaluateAttestationsArgs>,
+        seed: TransferIdSeed,
+    ) -> ProgramResult {
+        let rent = &Rent::from_account_info(rent_info)?;
+
+        // Verified messages, recipient and transfer account per transfer,
+        // followed by the legacy transfer account for hashed seeds
+        let accounts_per_transfer = match seed {
+            TransferIdSeed::Raw => 3,
+            TransferIdSeed::Hashed => 4,
+        };
+        if transfers.is_empty() || transfer_infos.len() != transfers.len() * accounts_per_transfer {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        assert_owned_by(reward_manager_info, program_id)?;
+        assert_owned_by(bot_oracle_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_not_paused(&reward_manager)?;
+
+        let bot_oracle = SenderAccount::unpack(&bot_oracle_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &bot_oracle.reward_manager)?;
+
+        assert_account_key(reward_token_source_info, &reward_manager.token_account)?;
+        let reward_token_source =
+            spl_token::state::Account::unpack(&reward_token_source_info.data.borrow())?;
+
+        let slot = Clock::get()?.slot;
+        for (transfer_data, accounts) in transfers
+            .into_iter()
+            .zip(transfer_infos.chunks(accounts_per_transfer))
+        {
+            let verified_messages_info = &accounts[0];
+            assert_owned_by(verified_messages_info, program_id)?;
+
+            Self::evaluate_transfer(
+                program_id,
+                &mut reward_manager,
+                reward_manager_info,
+                reward_manager_authority_info,
+                reward_token_source_info,
+                &reward_token_source.mint,
+                &bot_oracle,
+                payer_info,
+                rent,
+                slot,
+                verified_messages_info,
+                &accounts[1],
+                &accounts[2],
+                accounts.get(3),
+                transfer_data,
+                seed,
+            )?;
+        }
+
+        // Legacy accounts don't track disbursements
+        if !reward_manager.is_legacy() {
+            RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the attestations for a single transfer and pays it out,
+    /// marking the transfer as done and deleting its verified messages.
+    /// Disbursement limits are tracked on `reward_manager` but not stored.
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_transfer<'a>(
+        program_id: &Pubkey,
+        reward_manager: &mut RewardManager,
+        reward_manager_info: &AccountInfo<'a>,
+        reward_manager_authority_info: &AccountInfo<'a>,
+        reward_token_source_info: &AccountInfo<'a>,
+        mint: &Pubkey,
+        bot_oracle: &SenderAccount,
+        payer_info: &AccountInfo<'a>,
+        rent: &Rent,
+        slot: Slot,
+        verified_messages_info: &AccountInfo<'a>,
+        reward_token_recipient_info: &AccountInfo<'a>,
+        transfer_account_info: &AccountInfo<'a>,
+        legacy_transfer_account_info: Option<&AccountInfo<'a>>,
+        transfer_data: EvaluateAttestationsArgs,
+        seed: TransferIdSeed,
+    ) -> ProgramResult {
+        // The verified messages must have been submitted for this transfer
+        // id to this reward manager
+        let verified_messages_account_seed =
+            seed.seed(VERIFY_TRANSFER_SEED_PREFIX, &transfer_data.id)?;
+        let (_, derived_verified_messages_account, _) =
+            find_derived_pair(program_id, reward_manager_info.key, verified_messages_account_seed.as_ref());
+        assert_account_key(verified_messages_info, &derived_verified_messages_account)?;
 
         let verified_messages = VerifiedMessages::unpack(&verified_messages_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &verified_messages.reward_manager)?;
+
+        Self::pay_out_transfer(
+            program_id,
+            reward_manager,
+            reward_manager_info,
+            reward_manager_authority_info,
+            reward_token_source_info,
+            mint,
+            bot_oracle,
+            payer_info,
+            rent,
+            slot,
+            &verified_messages.messages,
+            reward_token_recipient_info,
+            transfer_account_info,
+            legacy_transfer_account_info,
+            transfer_data,
+            seed,
+        )?;
+
+        // Delete verified messages account, so later instructions can't reuse it
+        close_account(verified_messages_info, payer_info)?;
+
+        Ok(())
+    }
 
+    /// Checks `messages` attest the transfer and pays it out,
+    /// creating the transfer account so it can't be paid again.
+    #[allow(clippy::too_many_arguments)]
+    fn pay_out_transfer<'a>(
+        program_id: &Pubkey,
+        reward_manager: &mut RewardManager,
+        reward_manager_info: &AccountInfo<'a>,
+        reward_manager_authority_info: &AccountInfo<'a>,
+        reward_token_source_info: &AccountInfo<'a>,
+        mint: &Pubkey,
+        bot_oracle: &SenderAccount,
+        payer_info: &AccountInfo<'a>,
+        rent: &Rent,
+        slot: Slot,
+        messages: &[VerifiedMessage],
+        reward_token_recipient_info: &AccountInfo<'a>,
+        transfer_account_info: &AccountInfo<'a>,
+        legacy_transfer_account_info: Option<&AccountInfo<'a>>,
+        transfer_data: EvaluateAttestationsArgs,
+        seed: TransferIdSeed,
+    ) -> ProgramResult {
         // Check signs for minimum required votes, accounting for extra bot oracle
         // attestation
-        if verified_messages.messages.len() != (reward_manager.min_votes + 1) as usize {
+        if messages.len() != reward_manager.min_votes as usize + 1 {
             return Err(AudiusProgramError::NotEnoughSigners.into());
         }
 
-        let bot_oracle = SenderAccount::unpack(&bot_oracle_info.data.borrow())?;
-        assert_account_key(reward_manager_info, &bot_oracle.reward_manager)?;
-
         // Valid senders message
         let valid_message = [
+            TRANSFER_MESSAGE_PREFIX.as_ref(),
             transfer_data.eth_recipient.as_ref(),
             b"_",
             transfer_data.amount.to_le_bytes().as_ref(),
@@ -440,6 +1090,7 @@ impl Processor {
 
         // Valid bot oracle message
         let valid_bot_oracle_message = [
+            TRANSFER_MESSAGE_PREFIX.as_ref(),
             transfer_data.eth_recipient.as_ref(),
             b"_",
             transfer_data.amount.to_le_bytes().as_ref(),
@@ -453,9 +1104,18 @@ impl Processor {
             &valid_message,
             &valid_bot_oracle_message,
             &bot_oracle.eth_address,
-            &verified_messages.messages,
+            messages,
         )?;
 
+        // Ensure the payout goes to the claimable-tokens user bank derived
+        // from `eth_recipient` for the reward manager's mint
+        assert_recipient_bank(reward_token_recipient_info, mint, &transfer_data.eth_recipient)?;
+
+        // Enforce disbursement limits, legacy accounts have none stored
+        if !reward_manager.is_legacy() {
+            reward_manager.disburse(transfer_data.amount, slot)?;
+        }
+
         // Transfer reward tokens to user
         spl_token_transfer(
             program_id,
@@ -468,36 +1128,181 @@ impl Processor {
 
         // Create the transfer account to represent this disbursement, 
         // preventing the same transfer_data from being used twice.
-        let transfer_account_seed = [
-            TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            transfer_data.id.as_ref(),
-        ]
-        .concat();
-        let (reward_manager_authority, _, bump_seed) =
-            find_derived_pair(program_id, reward_manager_info.key, transfer_account_seed.as_ref());
+        let transfer_account_seed = seed.seed(TRANSFER_SEED_PREFIX, &transfer_data.id)?;
+        Self::create_transfer_account(
+            program_id,
+            reward_manager_info.key,
+            payer_info,
+            transfer_account_info,
+            &transfer_account_seed,
+            rent,
+        )?;
+
+        // Ids that fit into a seed also get the transfer account of the legacy
+        // instructions, so neither version pays the same transfer twice
+        if let Some(legacy_transfer_account_info) = legacy_transfer_account_info {
+            if let Ok(legacy_seed) = transfer_id_seed(TRANSFER_SEED_PREFIX, &transfer_data.id) {
+                Self::create_transfer_account(
+                    program_id,
+                    reward_manager_info.key,
+                    payer_info,
+                    legacy_transfer_account_info,
+                    &legacy_seed,
+                    rent,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates the transfer account derived from `seed`,
+    /// failing if the transfer was already paid out
+    fn create_transfer_account<'a>(
+        program_id: &Pubkey,
+        reward_manager: &Pubkey,
+        payer_info: &AccountInfo<'a>,
+        transfer_account_info: &AccountInfo<'a>,
+        seed: &[u8],
+        rent: &Rent,
+    ) -> ProgramResult {
+        let (reward_manager_authority, derived_transfer_account, bump_seed) =
+            find_derived_pair(program_id, reward_manager, seed);
+        assert_account_key(transfer_account_info, &derived_transfer_account)?;
+        if transfer_account_info.owner == program_id {
+            return Err(AudiusProgramError::AlreadySent.into());
+        }
 
         let signers_seeds = &[
             &reward_manager_authority.to_bytes()[..32],
-            &transfer_account_seed.as_slice(),
+            seed,
             &[bump_seed],
         ];
         create_account(
             program_id,
             payer_info.clone(),
             transfer_account_info.clone(),
-            0,
+            TRANSFER_ACC_SPACE,
             &[signers_seeds],
             rent,
+        )
+    }
+
+    /// Process `submit_and_evaluate` instruction.
+    /// Checks the attestations signed in the preceding secp instructions
+    /// by `senders_info` and the bot oracle, then pays out the transfer.
+    #[allow(clippy::too_many_arguments)]
+    fn process_submit_and_evaluate<'a>(
+        program_id: &Pubkey,
+        reward_manager_info: &AccountInfo<'a>,
+        reward_manager_authority_info: &AccountInfo<'a>,
+        reward_token_source_info: &AccountInfo<'a>,
+        reward_token_recipient_info: &AccountInfo<'a>,
+        transfer_account_info: &AccountInfo<'a>,
+        bot_oracle_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        instructions_info: &AccountInfo<'a>,
+        rent_info: &AccountInfo<'a>,
+        legacy_transfer_account_info: Option<&AccountInfo<'a>>,
+        senders_info: Vec<&AccountInfo<'a>>,
+        transfer_data: EvaluateAttestationsArgs,
+        seed: TransferIdSeed,
+    ) -> ProgramResult {
+        let rent = &Rent::from_account_info(rent_info)?;
+
+        assert_owned_by(reward_manager_info, program_id)?;
+        assert_owned_by(bot_oracle_info, program_id)?;
+
+        let mut reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        assert_not_paused(&reward_manager)?;
+
+        let bot_oracle = SenderAccount::unpack(&bot_oracle_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &bot_oracle.reward_manager)?;
+
+        assert_account_key(reward_token_source_info, &reward_manager.token_account)?;
+        let reward_token_source =
+            spl_token::state::Account::unpack(&reward_token_source_info.data.borrow())?;
+
+        // Every sender and the bot oracle sign one secp instruction
+        let mut signers_info = senders_info;
+        signers_info.push(bot_oracle_info);
+        let messages = get_secp_transfer_attestations(
+            program_id,
+            reward_manager_info.key,
+            instructions_info,
+            signers_info,
         )?;
 
-        // Delete verified messages account by zeroing its rent
-        let verified_messages_lamports = verified_messages_info.lamports();
-        let payer_lamports = payer_info.lamports();
+        Self::pay_out_transfer(
+            program_id,
+            &mut reward_manager,
+            reward_manager_info,
+            reward_manager_authority_info,
+            reward_token_source_info,
+            &reward_token_source.mint,
+            &bot_oracle,
+            payer_info,
+            rent,
+            Clock::get()?.slot,
+            &messages,
+            reward_token_recipient_info,
+            transfer_account_info,
+            legacy_transfer_account_info,
+            transfer_data,
+            seed,
+        )?;
 
-        **verified_messages_info.lamports.borrow_mut() = 0u64;
-        **payer_info.lamports.borrow_mut() = payer_lamports
-            .checked_add(verified_messages_lamports)
-            .ok_or(AudiusProgramError::MathOverflow)?;
+        // Legacy accounts don't track disbursements
+        if !reward_manager.is_legacy() {
+            RewardManager::pack(reward_manager, *reward_manager_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    /// Process `close_verified_messages` instruction.
+    /// Closes a `VerifiedMessages` account older than the reward manager's
+    /// `verified_messages_max_age`, refunding its rent to the funder that
+    /// created it. Legacy accounts record neither, so only the manager can
+    /// close them, getting their rent.
+    fn process_close_verified_messages<'a>(
+        program_id: &Pubkey,
+        verified_messages_info: &AccountInfo<'a>,
+        reward_manager_info: &AccountInfo<'a>,
+        funder_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        assert_owned_by(verified_messages_info, program_id)?;
+        assert_owned_by(reward_manager_info, program_id)?;
+
+        let reward_manager = RewardManager::unpack(&reward_manager_info.data.borrow())?;
+        let verified_messages = VerifiedMessages::unpack(&verified_messages_info.data.borrow())?;
+        assert_account_key(reward_manager_info, &verified_messages.reward_manager)?;
+        if verified_messages_info.data_len() != verified_messages.packed_len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if verified_messages.is_legacy() {
+            if !funder_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            assert_account_key(funder_info, &reward_manager.manager)?;
+        } else {
+            // Only accounts created by `submit_attestations` have this address
+            let (_, derived_verified_messages_account, _) =
+                find_derived_pair(program_id, reward_manager_info.key, &verified_messages.seed);
+            assert_account_key(verified_messages_info, &derived_verified_messages_account)?;
+            assert_account_key(funder_info, &verified_messages.funder)?;
+
+            let expires_at = verified_messages
+                .slot
+                .checked_add(reward_manager.verified_messages_max_age)
+                .ok_or(AudiusProgramError::MathOverflow)?;
+            if Clock::get()?.slot < expires_at {
+                return Err(AudiusProgramError::VerifiedMessagesNotExpired.into());
+            }
+        }
+
+        close_account(verified_messages_info, funder_info)?;
 
         Ok(())
     }
@@ -520,8 +1325,10 @@ impl Processor {
                 let mint = next_account_info(account_info_iter)?;
                 let manager = next_account_info(account_info_iter)?;
                 let authority = next_account_info(account_info_iter)?;
-                let _spl_token = next_account_info(account_info_iter)?;
+                let spl_token = next_account_info(account_info_iter)?;
                 let rent = next_account_info(account_info_iter)?;
+                assert_spl_token_program(spl_token)?;
+                assert_rent_sysvar(rent)?;
 
                 Self::process_init_instruction(
                     program_id,
@@ -530,7 +1337,7 @@ impl Processor {
                     mint,
                     manager,
                     authority,
-                    _spl_token,
+                    spl_token,
                     rent,
                     min_votes,
                 )
@@ -561,6 +1368,8 @@ impl Processor {
                 let sender = next_account_info(account_info_iter)?;
                 let sys_prog = next_account_info(account_info_iter)?;
                 let rent = next_account_info(account_info_iter)?;
+                assert_system_program(sys_prog)?;
+                assert_rent_sysvar(rent)?;
 
                 Self::process_create_sender(
                     program_id,
@@ -583,6 +1392,7 @@ impl Processor {
                 let sender = next_account_info(account_info_iter)?;
                 let refunder = next_account_info(account_info_iter)?;
                 let sys_prog = next_account_info(account_info_iter)?;
+                assert_system_program(sys_prog)?;
 
                 Self::process_delete_sender(
                     program_id,
@@ -605,7 +1415,9 @@ impl Processor {
                 let new_sender = next_account_info(account_info_iter)?;
                 let instructions_info = next_account_info(account_info_iter)?;
                 let rent = next_account_info(account_info_iter)?;
-                let _system_program = next_account_info(account_info_iter)?;
+                let system_program = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent)?;
+                assert_system_program(system_program)?;
                 let signers = account_info_iter.collect::<Vec<&AccountInfo>>();
 
                 Self::process_create_sender_public(
@@ -648,7 +1460,9 @@ impl Processor {
                 let sender = next_account_info(account_info_iter)?;
                 let rent_info = next_account_info(account_info_iter)?;
                 let instructions_info = next_account_info(account_info_iter)?;
-                let _system_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_system_program(system_program_id)?;
 
                 Self::process_submit_attestations(
                     program_id,
@@ -660,6 +1474,7 @@ impl Processor {
                     sender,
                     instructions_info,
                     SubmitAttestationsArgs { id },
+                    TransferIdSeed::Raw,
                 )
             }
             Instructions::EvaluateAttestations(EvaluateAttestationsArgs {
@@ -678,8 +1493,11 @@ impl Processor {
                 let bot_oracle_info = next_account_info(account_info_iter)?;
                 let payer_info = next_account_info(account_info_iter)?;
                 let rent_info = next_account_info(account_info_iter)?;
-                let _token_program_id = next_account_info(account_info_iter)?;
-                let _system_program_id = next_account_info(account_info_iter)?;
+                let token_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_spl_token_program(token_program_id)?;
+                assert_system_program(system_program_id)?;
 
                 Self::process_evaluate_attestations(
                     program_id,
@@ -692,11 +1510,395 @@ impl Processor {
                     bot_oracle_info,
                     payer_info,
                     rent_info,
+                    None,
                     EvaluateAttestationsArgs {
                         amount,
                         id,
                         eth_recipient,
                     },
+                    TransferIdSeed::Raw,
+                )
+            }
+            Instructions::EvaluateAttestationsBatch(EvaluateAttestationsBatchArgs { transfers }) => {
+                msg!("Instruction: EvaluateAttestationsBatch");
+
+                let reward_manager_info = next_account_info(account_info_iter)?;
+                let reward_manager_authority_info = next_account_info(account_info_iter)?;
+                let reward_token_source_info = next_account_info(account_info_iter)?;
+                let bot_oracle_info = next_account_info(account_info_iter)?;
+                let payer_info = next_account_info(account_info_iter)?;
+                let rent_info = next_account_info(account_info_iter)?;
+                let token_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_spl_token_program(token_program_id)?;
+                assert_system_program(system_program_id)?;
+
+                Self::process_evaluate_attestations_batch(
+                    program_id,
+                    reward_manager_info,
+                    reward_manager_authority_info,
+                    reward_token_source_info,
+                    bot_oracle_info,
+                    payer_info,
+                    rent_info,
+                    account_info_iter.as_slice(),
+                    transfers,
+                    TransferIdSeed::Raw,
+                )
+            }
+            Instructions::SubmitAndEvaluate(transfer_data) => {
+                msg!("Instruction: SubmitAndEvaluate");
+
+                let reward_manager_info = next_account_info(account_info_iter)?;
+                let reward_manager_authority_info = next_account_info(account_info_iter)?;
+                let reward_token_source_info = next_account_info(account_info_iter)?;
+                let reward_token_recipient_info = next_account_info(account_info_iter)?;
+                let transfer_account_info = next_account_info(account_info_iter)?;
+                let bot_oracle_info = next_account_info(account_info_iter)?;
+                let payer_info = next_account_info(account_info_iter)?;
+                let instructions_info = next_account_info(account_info_iter)?;
+                let rent_info = next_account_info(account_info_iter)?;
+                let token_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_spl_token_program(token_program_id)?;
+                assert_system_program(system_program_id)?;
+                let senders = account_info_iter.collect::<Vec<&AccountInfo>>();
+
+                Self::process_submit_and_evaluate(
+                    program_id,
+                    reward_manager_info,
+                    reward_manager_authority_info,
+                    reward_token_source_info,
+                    reward_token_recipient_info,
+                    transfer_account_info,
+                    bot_oracle_info,
+                    payer_info,
+                    instructions_info,
+                    rent_info,
+                    None,
+                    senders,
+                    transfer_data,
+                    TransferIdSeed::Raw,
+                )
+            }
+            Instructions::UpdateRewardManagerConfig(UpdateRewardManagerConfigArgs { min_votes }) => {
+                msg!("Instruction: UpdateRewardManagerConfig");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let manager_account = next_account_info(account_info_iter)?;
+
+                Self::process_update_reward_manager_config(
+                    program_id,
+                    reward_manager,
+                    manager_account,
+                    min_votes,
+                )
+            }
+            Instructions::UpdateRewardManagerConfigPublic(UpdateRewardManagerConfigArgs {
+                min_votes,
+            }) => {
+                msg!("Instruction: UpdateRewardManagerConfigPublic");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let instructions_info = next_account_info(account_info_iter)?;
+                let signers = account_info_iter.collect::<Vec<&AccountInfo>>();
+
+                Self::process_update_reward_manager_config_public(
+                    program_id,
+                    reward_manager,
+                    instructions_info,
+                    signers,
+                    min_votes,
+                )
+            }
+            Instructions::CloseVerifiedMessages => {
+                msg!("Instruction: CloseVerifiedMessages");
+
+                let verified_messages = next_account_info(account_info_iter)?;
+                let reward_manager = next_account_info(account_info_iter)?;
+                let funder = next_account_info(account_info_iter)?;
+
+                Self::process_close_verified_messages(
+                    program_id,
+                    verified_messages,
+                    reward_manager,
+                    funder,
+                )
+            }
+            Instructions::SetPaused(SetPausedArgs { paused }) => {
+                msg!("Instruction: SetPaused");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let manager_account = next_account_info(account_info_iter)?;
+
+                Self::process_set_paused(program_id, reward_manager, manager_account, paused)
+            }
+            Instructions::ProposeManager => {
+                msg!("Instruction: ProposeManager");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let current_manager = next_account_info(account_info_iter)?;
+                let proposed_manager = next_account_info(account_info_iter)?;
+
+                Self::process_propose_manager(
+                    program_id,
+                    reward_manager,
+                    current_manager,
+                    proposed_manager,
+                )
+            }
+            Instructions::AcceptManager => {
+                msg!("Instruction: AcceptManager");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let proposed_manager = next_account_info(account_info_iter)?;
+
+                Self::process_accept_manager(program_id, reward_manager, proposed_manager)
+            }
+            Instructions::CancelManagerProposal => {
+                msg!("Instruction: CancelManagerProposal");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let current_manager = next_account_info(account_info_iter)?;
+
+                Self::process_cancel_manager_proposal(program_id, reward_manager, current_manager)
+            }
+            Instructions::UpdateDisbursementLimits(limits) => {
+                msg!("Instruction: UpdateDisbursementLimits");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let manager_account = next_account_info(account_info_iter)?;
+
+                Self::process_update_disbursement_limits(
+                    program_id,
+                    reward_manager,
+                    manager_account,
+                    limits,
+                )
+            }
+            Instructions::UpdateSender(UpdateSenderArgs {
+                eth_address,
+                operator,
+            }) => {
+                msg!("Instruction: UpdateSender");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let manager_account = next_account_info(account_info_iter)?;
+                let authority = next_account_info(account_info_iter)?;
+                let funder_account = next_account_info(account_info_iter)?;
+                let sender = next_account_info(account_info_iter)?;
+                let updated_sender = next_account_info(account_info_iter)?;
+                let sys_prog = next_account_info(account_info_iter)?;
+                let rent = next_account_info(account_info_iter)?;
+                assert_system_program(sys_prog)?;
+                assert_rent_sysvar(rent)?;
+
+                Self::process_update_sender(
+                    program_id,
+                    reward_manager,
+                    manager_account,
+                    authority,
+                    funder_account,
+                    sender,
+                    updated_sender,
+                    rent,
+                    eth_address,
+                    operator,
+                )
+            }
+            Instructions::UpdateSenderPublic(UpdateSenderArgs {
+                eth_address,
+                operator,
+            }) => {
+                msg!("Instruction: UpdateSenderPublic");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let authority = next_account_info(account_info_iter)?;
+                let funder = next_account_info(account_info_iter)?;
+                let sender = next_account_info(account_info_iter)?;
+                let updated_sender = next_account_info(account_info_iter)?;
+                let instructions_info = next_account_info(account_info_iter)?;
+                let rent = next_account_info(account_info_iter)?;
+                let system_program = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent)?;
+                assert_system_program(system_program)?;
+                let signers = account_info_iter.collect::<Vec<&AccountInfo>>();
+
+                Self::process_update_sender_public(
+                    program_id,
+                    reward_manager,
+                    authority,
+                    funder,
+                    sender,
+                    updated_sender,
+                    instructions_info,
+                    rent,
+                    signers,
+                    eth_address,
+                    operator,
+                )
+            }
+            Instructions::UpdateVerifiedMessagesMaxAge(UpdateVerifiedMessagesMaxAgeArgs {
+                max_age,
+            }) => {
+                msg!("Instruction: UpdateVerifiedMessagesMaxAge");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let manager_account = next_account_info(account_info_iter)?;
+
+                Self::process_update_verified_messages_max_age(
+                    program_id,
+                    reward_manager,
+                    manager_account,
+                    max_age,
+                )
+            }
+            Instructions::MigrateRewardManager => {
+                msg!("Instruction: MigrateRewardManager");
+
+                let reward_manager = next_account_info(account_info_iter)?;
+                let manager_account = next_account_info(account_info_iter)?;
+                let funder = next_account_info(account_info_iter)?;
+                let sys_prog = next_account_info(account_info_iter)?;
+                let rent = next_account_info(account_info_iter)?;
+                assert_system_program(sys_prog)?;
+                assert_rent_sysvar(rent)?;
+
+                Self::process_migrate_reward_manager(
+                    program_id,
+                    reward_manager,
+                    manager_account,
+                    funder,
+                    rent,
+                )
+            }
+            Instructions::SubmitAttestationsV2(args) => {
+                msg!("Instruction: SubmitAttestationsV2");
+
+                let verified_messages = next_account_info(account_info_iter)?;
+                let reward_manager = next_account_info(account_info_iter)?;
+                let authority = next_account_info(account_info_iter)?;
+                let funder_info = next_account_info(account_info_iter)?;
+                let sender = next_account_info(account_info_iter)?;
+                let rent_info = next_account_info(account_info_iter)?;
+                let instructions_info = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_system_program(system_program_id)?;
+
+                Self::process_submit_attestations(
+                    program_id,
+                    verified_messages,
+                    reward_manager,
+                    authority,
+                    funder_info,
+                    rent_info,
+                    sender,
+                    instructions_info,
+                    args,
+                    TransferIdSeed::Hashed,
+                )
+            }
+            Instructions::EvaluateAttestationsV2(transfer_data) => {
+                msg!("Instruction: EvaluateAttestationsV2");
+
+                let verified_messages_info = next_account_info(account_info_iter)?;
+                let reward_manager_info = next_account_info(account_info_iter)?;
+                let reward_manager_authority_info = next_account_info(account_info_iter)?;
+                let reward_token_source_info = next_account_info(account_info_iter)?;
+                let reward_token_recipient_info = next_account_info(account_info_iter)?;
+                let transfer_account_info = next_account_info(account_info_iter)?;
+                let bot_oracle_info = next_account_info(account_info_iter)?;
+                let payer_info = next_account_info(account_info_iter)?;
+                let rent_info = next_account_info(account_info_iter)?;
+                let token_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                let legacy_transfer_account_info = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_spl_token_program(token_program_id)?;
+                assert_system_program(system_program_id)?;
+
+                Self::process_evaluate_attestations(
+                    program_id,
+                    verified_messages_info,
+                    reward_manager_info,
+                    reward_manager_authority_info,
+                    reward_token_source_info,
+                    reward_token_recipient_info,
+                    transfer_account_info,
+                    bot_oracle_info,
+                    payer_info,
+                    rent_info,
+                    Some(legacy_transfer_account_info),
+                    transfer_data,
+                    TransferIdSeed::Hashed,
+                )
+            }
+            Instructions::EvaluateAttestationsBatchV2(EvaluateAttestationsBatchArgs { transfers }) => {
+                msg!("Instruction: EvaluateAttestationsBatchV2");
+
+                let reward_manager_info = next_account_info(account_info_iter)?;
+                let reward_manager_authority_info = next_account_info(account_info_iter)?;
+                let reward_token_source_info = next_account_info(account_info_iter)?;
+                let bot_oracle_info = next_account_info(account_info_iter)?;
+                let payer_info = next_account_info(account_info_iter)?;
+                let rent_info = next_account_info(account_info_iter)?;
+                let token_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_spl_token_program(token_program_id)?;
+                assert_system_program(system_program_id)?;
+
+                Self::process_evaluate_attestations_batch(
+                    program_id,
+                    reward_manager_info,
+                    reward_manager_authority_info,
+                    reward_token_source_info,
+                    bot_oracle_info,
+                    payer_info,
+                    rent_info,
+                    account_info_iter.as_slice(),
+                    transfers,
+                    TransferIdSeed::Hashed,
+                )
+            }
+            Instructions::SubmitAndEvaluateV2(transfer_data) => {
+                msg!("Instruction: SubmitAndEvaluateV2");
+
+                let reward_manager_info = next_account_info(account_info_iter)?;
+                let reward_manager_authority_info = next_account_info(account_info_iter)?;
+                let reward_token_source_info = next_account_info(account_info_iter)?;
+                let reward_token_recipient_info = next_account_info(account_info_iter)?;
+                let transfer_account_info = next_account_info(account_info_iter)?;
+                let bot_oracle_info = next_account_info(account_info_iter)?;
+                let payer_info = next_account_info(account_info_iter)?;
+                let instructions_info = next_account_info(account_info_iter)?;
+                let rent_info = next_account_info(account_info_iter)?;
+                let token_program_id = next_account_info(account_info_iter)?;
+                let system_program_id = next_account_info(account_info_iter)?;
+                let legacy_transfer_account_info = next_account_info(account_info_iter)?;
+                assert_rent_sysvar(rent_info)?;
+                assert_spl_token_program(token_program_id)?;
+                assert_system_program(system_program_id)?;
+                let senders = account_info_iter.collect::<Vec<&AccountInfo>>();
+
+                Self::process_submit_and_evaluate(
+                    program_id,
+                    reward_manager_info,
+                    reward_manager_authority_info,
+                    reward_token_source_info,
+                    reward_token_recipient_info,
+                    transfer_account_info,
+                    bot_oracle_info,
+                    payer_info,
+                    instructions_info,
+                    rent_info,
+                    Some(legacy_transfer_account_info),
+                    senders,
+                    transfer_data,
+                    TransferIdSeed::Hashed,
                 )
             }
         }
@@ -11,3 +11,6 @@ pub use verified_messages::*;
 /// Accounts are created with data zeroed out, so uninitialized state instances
 /// will have the version set to 0.
 pub const UNINITIALIZED_VERSION: u8 = 0;
+
+/// Closed accounts keep this version until the runtime garbage-collects them.
+pub use audius_secp_utils::CLOSED_ACCOUNT_DISCRIMINATOR as CLOSED_VERSION;
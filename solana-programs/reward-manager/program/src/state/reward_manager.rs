@@ -1,7 +1,8 @@
 use super::UNINITIALIZED_VERSION;
-use crate::PROGRAM_VERSION;
+use crate::{error::AudiusProgramError, PROGRAM_VERSION};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::Slot,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
@@ -9,6 +10,28 @@ use solana_program::{
 };
 
 /// Reward manager
+///
+/// Layout at `REWARD_MANAGER_VERSION`, 163 bytes:
+///
+/// | Offset | Size | Field                       |
+/// |--------|------|-----------------------------|
+/// | 0      | 1    | `version`                   |
+/// | 1      | 32   | `token_account`             |
+/// | 33     | 32   | `manager`                   |
+/// | 65     | 1    | `min_votes`                 |
+/// | 66     | 1    | `paused`                    |
+/// | 67     | 32   | `pending_manager`           |
+/// | 99     | 8    | `max_transfer_amount`       |
+/// | 107    | 8    | `max_window_amount`         |
+/// | 115    | 8    | `window_slots`              |
+/// | 123    | 8    | `window_start`              |
+/// | 131    | 8    | `window_disbursed`          |
+/// | 139    | 8    | `previous_window_disbursed` |
+/// | 147    | 8    | `verified_messages_max_age` |
+/// | 155    | 8    | `config_nonce`              |
+///
+/// Legacy accounts at `PROGRAM_VERSION` only store the first 66 bytes and
+/// are moved to this layout with `MigrateRewardManager`
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct RewardManager {
     /// Version
@@ -19,24 +42,143 @@ pub struct RewardManager {
     pub manager: Pubkey,
     /// Number of signer votes required for sending rewards
     pub min_votes: u8,
+    /// Whether rewards are paused, only stored by the current layout
+    pub paused: bool,
+    /// Manager proposed by the current one, default when there is no proposal.
+    /// Only stored by the current layout
+    pub pending_manager: Pubkey,
+    /// Maximum amount of a single transfer
+    pub max_transfer_amount: u64,
+    /// Maximum amount disbursed within the last `window_slots` slots
+    pub max_window_amount: u64,
+    /// Length of the disbursement window in slots
+    pub window_slots: Slot,
+    /// Slot the current disbursement window started at
+    pub window_start: Slot,
+    /// Amount disbursed within the current window
+    pub window_disbursed: u64,
+    /// Amount disbursed within the window before the current one
+    pub previous_window_disbursed: u64,
+    /// Slots after which an unevaluated verified messages account can be closed
+    pub verified_messages_max_age: Slot,
+    /// Number of config updates so far, signed by senders to keep
+    /// their signatures from being replayed
+    pub config_nonce: u64,
 }
 
+/// Version of the `RewardManager` layout with the manager handoff and disbursement limits
+pub const REWARD_MANAGER_VERSION: u8 = 2;
+/// `verified_messages_max_age` of new and legacy reward managers, roughly a day
+pub const DEFAULT_VERIFIED_MESSAGES_MAX_AGE: Slot = 216_000;
+// 1 + 32 + 32 + 1
+const LEGACY_REWARD_MANAGER_LEN: usize = 66;
+
 impl RewardManager {
     /// Creates new `RewardManager`
     pub fn new(token_account: Pubkey, manager: Pubkey, min_votes: u8) -> Self {
         Self {
-            version: PROGRAM_VERSION,
+            version: REWARD_MANAGER_VERSION,
             token_account,
             manager,
             min_votes,
+            paused: false,
+            pending_manager: Pubkey::default(),
+            max_transfer_amount: u64::MAX,
+            max_window_amount: u64::MAX,
+            window_slots: 0,
+            window_start: 0,
+            window_disbursed: 0,
+            previous_window_disbursed: 0,
+            verified_messages_max_age: DEFAULT_VERIFIED_MESSAGES_MAX_AGE,
+            config_nonce: 0,
+        }
+    }
+
+    /// Whether the account uses the layout with only the fields of `new` set
+    pub fn is_legacy(&self) -> bool {
+        self.version == PROGRAM_VERSION
+    }
+
+    /// Records `amount` disbursed at `slot`, failing if it exceeds the limits.
+    ///
+    /// Disbursements are counted over the last `window_slots` slots, estimated
+    /// from the current window and the part of the previous one still within
+    /// reach, so the limit also holds across window boundaries
+    pub fn disburse(&mut self, amount: u64, slot: Slot) -> Result<(), ProgramError> {
+        if amount > self.max_transfer_amount {
+            return Err(AudiusProgramError::TransferLimitExceeded.into());
+        }
+
+        self.advance_window(slot);
+
+        let window_disbursed = self
+            .window_disbursed
+            .checked_add(amount)
+            .ok_or(AudiusProgramError::MathOverflow)?;
+        let rolling_disbursed = window_disbursed
+            .checked_add(self.previous_window_weight(slot))
+            .ok_or(AudiusProgramError::MathOverflow)?;
+        if rolling_disbursed > self.max_window_amount {
+            return Err(AudiusProgramError::WindowLimitExceeded.into());
         }
+        self.window_disbursed = window_disbursed;
+
+        Ok(())
+    }
+
+    /// Moves the current window forward to the one containing `slot`
+    fn advance_window(&mut self, slot: Slot) {
+        if self.window_slots == 0 {
+            // Without a window only single transfers are limited
+            self.window_start = slot;
+            self.window_disbursed = 0;
+            self.previous_window_disbursed = 0;
+            return;
+        }
+
+        let elapsed_windows = slot.saturating_sub(self.window_start) / self.window_slots;
+        if elapsed_windows == 0 {
+            return;
+        }
+        self.previous_window_disbursed = if elapsed_windows == 1 {
+            self.window_disbursed
+        } else {
+            0
+        };
+        self.window_start += elapsed_windows * self.window_slots;
+        self.window_disbursed = 0;
+    }
+
+    /// Part of the previous window's disbursements that falls within the
+    /// last `window_slots` slots at `slot`, rounded up
+    fn previous_window_weight(&self, slot: Slot) -> u64 {
+        if self.window_slots == 0 {
+            return 0;
+        }
+        let remaining = self
+            .window_slots
+            .saturating_sub(slot.saturating_sub(self.window_start));
+        let weight = (self.previous_window_disbursed as u128 * remaining as u128)
+            .div_ceil(self.window_slots as u128);
+        // Never more than `previous_window_disbursed`
+        weight as u64
+    }
+
+    /// Records a config update, so signatures of the previous config
+    /// nonce can't be used again
+    pub fn bump_config_nonce(&mut self) -> Result<(), ProgramError> {
+        self.config_nonce = self
+            .config_nonce
+            .checked_add(1)
+            .ok_or(AudiusProgramError::MathOverflow)?;
+        Ok(())
     }
 }
 
 impl Sealed for RewardManager {}
 impl Pack for RewardManager {
-    // 1 + 32 + 32 + 1
-    const LEN: usize = 66;
+    // 1 + 32 + 32 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8
+    const LEN: usize = 163;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -50,6 +192,48 @@ impl Pack for RewardManager {
             ProgramError::InvalidAccountData
         })
     }
+
+    fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+        // Legacy accounts only store the fields of `new`, the rest are defaults
+        if input.first() == Some(&PROGRAM_VERSION) {
+            if input.len() != LEGACY_REWARD_MANAGER_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let mut data = [0; Self::LEN];
+            data[..LEGACY_REWARD_MANAGER_LEN].copy_from_slice(input);
+            let legacy = Self::unpack_from_slice(&data)?;
+            return Ok(Self {
+                version: legacy.version,
+                ..Self::new(legacy.token_account, legacy.manager, legacy.min_votes)
+            });
+        }
+        if input.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::unpack_from_slice(input)
+    }
+
+    fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if src.is_legacy() {
+            // Legacy accounts have no room to store the new fields
+            let defaults = Self {
+                version: src.version,
+                ..Self::new(src.token_account, src.manager, src.min_votes)
+            };
+            if src != defaults || dst.len() != LEGACY_REWARD_MANAGER_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let mut data = [0; Self::LEN];
+            src.pack_into_slice(&mut data);
+            dst.copy_from_slice(&data[..LEGACY_REWARD_MANAGER_LEN]);
+            return Ok(());
+        }
+        if dst.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        src.pack_into_slice(dst);
+        Ok(())
+    }
 }
 
 impl IsInitialized for RewardManager {
@@ -57,3 +241,140 @@ impl IsInitialized for RewardManager {
         self.version != UNINITIALIZED_VERSION
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_unpack() {
+        let mut reward_manager = RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), 3);
+        reward_manager.paused = true;
+        reward_manager.pending_manager = Pubkey::new_unique();
+        reward_manager.max_window_amount = 42;
+        reward_manager.verified_messages_max_age = 7;
+        reward_manager.config_nonce = 9;
+
+        let mut data = vec![0; RewardManager::LEN];
+        RewardManager::pack(reward_manager.clone(), &mut data).unwrap();
+        assert_eq!(data[66], 1);
+        assert_eq!(&data[67..99], reward_manager.pending_manager.as_ref());
+        assert_eq!(&data[147..155], &7u64.to_le_bytes());
+        assert_eq!(&data[155..163], &9u64.to_le_bytes());
+        assert_eq!(RewardManager::unpack(&data).unwrap(), reward_manager);
+    }
+
+    #[test]
+    fn pack_unpack_legacy() {
+        let mut reward_manager = RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), 3);
+        reward_manager.version = PROGRAM_VERSION;
+
+        let mut data = vec![0; LEGACY_REWARD_MANAGER_LEN];
+        RewardManager::pack(reward_manager.clone(), &mut data).unwrap();
+        assert_eq!(RewardManager::unpack(&data).unwrap(), reward_manager);
+
+        let mut paused = reward_manager.clone();
+        paused.paused = true;
+        assert_eq!(
+            RewardManager::pack(paused, &mut data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut pending = reward_manager.clone();
+        pending.pending_manager = Pubkey::new_unique();
+        assert_eq!(
+            RewardManager::pack(pending, &mut data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut updated = reward_manager.clone();
+        updated.config_nonce = 1;
+        assert_eq!(
+            RewardManager::pack(updated, &mut data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        reward_manager.max_transfer_amount = 42;
+        assert_eq!(
+            RewardManager::pack(reward_manager, &mut data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn disburse_limits() {
+        let mut reward_manager = RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), 3);
+        reward_manager.max_transfer_amount = 10;
+        reward_manager.max_window_amount = 15;
+        reward_manager.window_slots = 100;
+
+        assert_eq!(
+            reward_manager.disburse(11, 1),
+            Err(AudiusProgramError::TransferLimitExceeded.into())
+        );
+        reward_manager.disburse(10, 1).unwrap();
+        reward_manager.disburse(5, 50).unwrap();
+        assert_eq!(
+            reward_manager.disburse(1, 99),
+            Err(AudiusProgramError::WindowLimitExceeded.into())
+        );
+
+        // Windows follow each other every `window_slots`
+        reward_manager.disburse(10, 250).unwrap();
+        assert_eq!(reward_manager.window_start, 200);
+        assert_eq!(reward_manager.window_disbursed, 10);
+        assert_eq!(reward_manager.previous_window_disbursed, 0);
+    }
+
+    #[test]
+    fn disburse_limits_across_window_boundary() {
+        let mut reward_manager = RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), 3);
+        reward_manager.max_transfer_amount = 10;
+        reward_manager.max_window_amount = 20;
+        reward_manager.window_slots = 100;
+
+        // Spend the whole limit right before the boundary
+        reward_manager.disburse(10, 98).unwrap();
+        reward_manager.disburse(10, 99).unwrap();
+
+        // It still counts right after the boundary
+        assert_eq!(
+            reward_manager.disburse(1, 100),
+            Err(AudiusProgramError::WindowLimitExceeded.into())
+        );
+        assert_eq!(reward_manager.window_start, 100);
+        assert_eq!(reward_manager.previous_window_disbursed, 20);
+
+        // And frees up as the previous window falls out of reach
+        reward_manager.disburse(5, 125).unwrap();
+        assert_eq!(
+            reward_manager.disburse(1, 125),
+            Err(AudiusProgramError::WindowLimitExceeded.into())
+        );
+        reward_manager.disburse(10, 175).unwrap();
+        assert_eq!(
+            reward_manager.disburse(1, 175),
+            Err(AudiusProgramError::WindowLimitExceeded.into())
+        );
+
+        // The current window then becomes the previous one
+        assert_eq!(
+            reward_manager.disburse(10, 200),
+            Err(AudiusProgramError::WindowLimitExceeded.into())
+        );
+        reward_manager.disburse(5, 200).unwrap();
+        assert_eq!(reward_manager.previous_window_disbursed, 15);
+        assert_eq!(reward_manager.window_disbursed, 5);
+    }
+
+    #[test]
+    fn disburse_limits_without_window() {
+        let mut reward_manager = RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), 3);
+        reward_manager.max_transfer_amount = 10;
+
+        reward_manager.disburse(10, 1).unwrap();
+        reward_manager.disburse(10, 1).unwrap();
+        assert_eq!(reward_manager.window_disbursed, 10);
+        assert_eq!(reward_manager.previous_window_disbursed, 0);
+    }
+}
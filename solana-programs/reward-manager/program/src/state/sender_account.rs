@@ -1,4 +1,4 @@
-use super::UNINITIALIZED_VERSION;
+use super::{CLOSED_VERSION, UNINITIALIZED_VERSION};
 use crate::{utils::EthereumAddress, PROGRAM_VERSION};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
@@ -58,6 +58,6 @@ impl Pack for SenderAccount {
 
 impl IsInitialized for SenderAccount {
     fn is_initialized(&self) -> bool {
-        self.version != UNINITIALIZED_VERSION
+        self.version != UNINITIALIZED_VERSION && self.version != CLOSED_VERSION
     }
 }
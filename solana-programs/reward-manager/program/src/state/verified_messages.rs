@@ -1,11 +1,12 @@
-use super::UNINITIALIZED_VERSION;
-use crate::{utils::EthereumAddress, PROGRAM_VERSION};
+use super::{CLOSED_VERSION, UNINITIALIZED_VERSION};
+use crate::{error::AudiusProgramError, utils::EthereumAddress, PROGRAM_VERSION};
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::Slot,
     program_error::ProgramError,
-    program_pack::{IsInitialized, Pack, Sealed},
-    pubkey::{Pubkey, PUBKEY_BYTES},
+    program_pack::{IsInitialized, Sealed},
+    pubkey::{Pubkey, MAX_SEED_LEN, PUBKEY_BYTES},
 };
 use std::convert::TryFrom;
 
@@ -15,9 +16,35 @@ pub type VoteMessage = [u8; 128];
 /// Add sender message prefix
 pub const ADD_SENDER_MESSAGE_PREFIX: &str = "add";
 
+/// Whether add sender messages may be signed with `personal_sign` (EIP-191)
+pub const ADD_SENDER_MESSAGE_EIP191: bool = true;
+
 /// Delete sender message prefix
 pub const DELETE_SENDER_MESSAGE_PREFIX: &str = "delete";
 
+/// Whether delete sender messages may be signed with `personal_sign` (EIP-191)
+pub const DELETE_SENDER_MESSAGE_EIP191: bool = true;
+
+/// Update sender message prefix
+pub const UPDATE_SENDER_MESSAGE_PREFIX: &str = "update_sender";
+
+/// Whether update sender messages may be signed with `personal_sign` (EIP-191)
+pub const UPDATE_SENDER_MESSAGE_EIP191: bool = true;
+
+/// Update reward manager config message prefix
+pub const UPDATE_CONFIG_MESSAGE_PREFIX: &str = "update_config";
+
+/// Whether update config messages may be signed with `personal_sign` (EIP-191)
+pub const UPDATE_CONFIG_MESSAGE_EIP191: bool = true;
+
+/// Transfer attestation message prefix, the version of the attestation format
+pub const TRANSFER_MESSAGE_PREFIX: &str = "transfer_v2";
+
+/// Whether transfer attestations may be signed with `personal_sign` (EIP-191).
+/// They are stored without the EIP-191 prefix, so evaluation compares them
+/// the same way whichever way they were signed.
+pub const TRANSFER_MESSAGE_EIP191: bool = true;
+
 /// Generates fixed vote message from slice
 #[macro_export]
 macro_rules! vote_message {
@@ -47,54 +74,135 @@ pub struct VerifiedMessages {
     pub version: u8,
     /// Reward manager
     pub reward_manager: Pubkey,
+    /// Account which paid the rent, refunded when the account is closed
+    pub funder: Pubkey,
+    /// Slot the account was created at
+    pub slot: Slot,
+    /// Seed the account address was derived from, see `transfer_id_seed`
+    pub seed: Vec<u8>,
+    /// Maximum number of messages the account can hold
+    pub capacity: u8,
     /// Messages
     pub messages: Vec<VerifiedMessage>,
 }
 
-/// Total verified messages
-pub const TOTAL_VERIFIED_MESSAGES: usize = 5;
+/// Version of the variable length `VerifiedMessages` layout. No other
+/// account type of the program uses it, so the version alone tells
+/// verified messages apart from reward managers and senders.
+pub const VERIFIED_MESSAGES_VERSION: u8 = 3;
+/// Capacity of accounts created before the layout had a capacity field,
+/// these are still versioned with `PROGRAM_VERSION` and have no funder
+pub const LEGACY_VERIFIED_MESSAGES_CAPACITY: u8 = 5;
 // 20 + 128 + 20
 const VERIFIED_MESSAGE_LEN: usize = 168;
-// 1 + 32 + 1 + (168 * 5)
-const VERIFIED_MESSAGES_LEN: usize = 874;
+// 1 + 32 + 32 + 8 + 1 + 32 + 1 + 1
+const VERIFIED_MESSAGES_HEADER_LEN: usize = 108;
+// 1 + 32 + 1
+const LEGACY_VERIFIED_MESSAGES_HEADER_LEN: usize = 34;
 
 impl VerifiedMessages {
-    /// Creates new `VerifiedMessages`
-    pub fn new(reward_manager: Pubkey) -> Self {
+    /// Creates new `VerifiedMessages` holding up to `capacity` messages
+    pub fn new(
+        reward_manager: Pubkey,
+        funder: Pubkey,
+        slot: Slot,
+        seed: Vec<u8>,
+        capacity: u8,
+    ) -> Self {
         Self {
-            version: PROGRAM_VERSION,
+            version: VERIFIED_MESSAGES_VERSION,
             reward_manager,
+            funder,
+            slot,
+            seed,
+            capacity,
             messages: vec![],
         }
     }
 
+    /// Account size needed to hold `capacity` messages
+    pub fn size(capacity: u8) -> usize {
+        VERIFIED_MESSAGES_HEADER_LEN + VERIFIED_MESSAGE_LEN * capacity as usize
+    }
+
+    /// Account size of `self`'s layout and capacity
+    pub fn packed_len(&self) -> usize {
+        if self.is_legacy() {
+            LEGACY_VERIFIED_MESSAGES_HEADER_LEN + VERIFIED_MESSAGE_LEN * self.capacity as usize
+        } else {
+            Self::size(self.capacity)
+        }
+    }
+
     /// Add verified message
-    pub fn add(&mut self, message: VerifiedMessage) {
+    pub fn add(&mut self, message: VerifiedMessage) -> Result<(), ProgramError> {
+        if self.messages.len() >= self.capacity as usize {
+            return Err(AudiusProgramError::MessagesOverflow.into());
+        }
         self.messages.push(message);
+        Ok(())
     }
-}
 
-impl Sealed for VerifiedMessages {}
-impl Pack for VerifiedMessages {
-    const LEN: usize = VERIFIED_MESSAGES_LEN;
-
-    fn pack_into_slice(&self, dst: &mut [u8]) {
-        let output = array_mut_ref![dst, 0, VERIFIED_MESSAGES_LEN];
-        #[allow(clippy::ptr_offset_with_cast)]
-        let (version, reward_manager, messages_len, data_flat) = mut_array_refs![
-            output,
-            1,
-            PUBKEY_BYTES,
-            1,
-            VERIFIED_MESSAGE_LEN * TOTAL_VERIFIED_MESSAGES
-        ];
-
-        *version = self.version.to_le_bytes();
-        reward_manager.copy_from_slice(self.reward_manager.as_ref());
-        *messages_len = u8::try_from(self.messages.len()).unwrap().to_le_bytes();
+    /// Whether the account uses the fixed size layout without funder, slot and seed
+    pub fn is_legacy(&self) -> bool {
+        self.version == PROGRAM_VERSION
+    }
+
+    /// Packs `src` into `dst`, which must be large enough for `src.capacity` messages
+    pub fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let header_len = if src.is_legacy() {
+            LEGACY_VERIFIED_MESSAGES_HEADER_LEN
+        } else {
+            VERIFIED_MESSAGES_HEADER_LEN
+        };
+        if src.messages.len() > src.capacity as usize {
+            return Err(AudiusProgramError::MessagesOverflow.into());
+        }
+        if src.seed.len() > MAX_SEED_LEN {
+            return Err(ProgramError::MaxSeedLengthExceeded);
+        }
+        if dst.len() < header_len + VERIFIED_MESSAGE_LEN * src.capacity as usize {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        let messages_len = u8::try_from(src.messages.len()).unwrap();
+        let (header, data_flat) = dst.split_at_mut(header_len);
+        if src.is_legacy() {
+            let header = array_mut_ref![header, 0, LEGACY_VERIFIED_MESSAGES_HEADER_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (version, reward_manager, len) = mut_array_refs![header, 1, PUBKEY_BYTES, 1];
+
+            version[0] = src.version;
+            reward_manager.copy_from_slice(src.reward_manager.as_ref());
+            len[0] = messages_len;
+        } else {
+            let header = array_mut_ref![header, 0, VERIFIED_MESSAGES_HEADER_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (version, reward_manager, funder, slot, seed_len, seed, capacity, len) = mut_array_refs![
+                header,
+                1,
+                PUBKEY_BYTES,
+                PUBKEY_BYTES,
+                8,
+                1,
+                MAX_SEED_LEN,
+                1,
+                1
+            ];
+
+            version[0] = src.version;
+            reward_manager.copy_from_slice(src.reward_manager.as_ref());
+            funder.copy_from_slice(src.funder.as_ref());
+            *slot = src.slot.to_le_bytes();
+            seed_len[0] = src.seed.len() as u8;
+            seed.fill(0);
+            seed[..src.seed.len()].copy_from_slice(&src.seed);
+            capacity[0] = src.capacity;
+            len[0] = messages_len;
+        }
 
         let mut offset = 0;
-        for verified_message in &self.messages {
+        for verified_message in &src.messages {
             let messages_flat = array_mut_ref![data_flat, offset, VERIFIED_MESSAGE_LEN];
             let (address, message, operator) = mut_array_refs![messages_flat, 20, 128, 20];
 
@@ -104,29 +212,80 @@ impl Pack for VerifiedMessages {
 
             offset += VERIFIED_MESSAGE_LEN;
         }
+
+        Ok(())
     }
 
-    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let input = array_ref![src, 0, VERIFIED_MESSAGES_LEN];
-        #[allow(clippy::ptr_offset_with_cast)]
-        let (version, reward_manager, messages_len, data_flat) = array_refs![
-            input,
-            1,
-            PUBKEY_BYTES,
-            1,
-            VERIFIED_MESSAGE_LEN * TOTAL_VERIFIED_MESSAGES
-        ];
-
-        let version = u8::from_le_bytes(*version);
-        let messages_len = u8::from_le_bytes(*messages_len);
-        let mut messages = Vec::<VerifiedMessage>::with_capacity(messages_len as usize + 1);
+    /// Unpacks `VerifiedMessages` of either layout, without checking
+    /// that it is initialized
+    pub fn unpack_unchecked(src: &[u8]) -> Result<Self, ProgramError> {
+        let version = *src.first().ok_or(ProgramError::InvalidAccountData)?;
+        // Other account types of the program use other versions
+        if ![
+            UNINITIALIZED_VERSION,
+            CLOSED_VERSION,
+            PROGRAM_VERSION,
+            VERIFIED_MESSAGES_VERSION,
+        ]
+        .contains(&version)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut verified_messages = Self::new(Pubkey::default(), Pubkey::default(), 0, vec![], 0);
+        verified_messages.version = version;
+
+        let (messages_len, data_flat) = if verified_messages.is_legacy() {
+            if src.len() < LEGACY_VERIFIED_MESSAGES_HEADER_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let header = array_ref![src, 0, LEGACY_VERIFIED_MESSAGES_HEADER_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (_, reward_manager, len) = array_refs![header, 1, PUBKEY_BYTES, 1];
+
+            verified_messages.reward_manager = Pubkey::new_from_array(*reward_manager);
+            verified_messages.capacity = LEGACY_VERIFIED_MESSAGES_CAPACITY;
+            (len[0], &src[LEGACY_VERIFIED_MESSAGES_HEADER_LEN..])
+        } else {
+            if src.len() < VERIFIED_MESSAGES_HEADER_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let header = array_ref![src, 0, VERIFIED_MESSAGES_HEADER_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (_, reward_manager, funder, slot, seed_len, seed, capacity, len) = array_refs![
+                header,
+                1,
+                PUBKEY_BYTES,
+                PUBKEY_BYTES,
+                8,
+                1,
+                MAX_SEED_LEN,
+                1,
+                1
+            ];
+
+            let seed_len = seed_len[0] as usize;
+            if seed_len > MAX_SEED_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            verified_messages.reward_manager = Pubkey::new_from_array(*reward_manager);
+            verified_messages.funder = Pubkey::new_from_array(*funder);
+            verified_messages.slot = Slot::from_le_bytes(*slot);
+            verified_messages.seed = seed[..seed_len].to_vec();
+            verified_messages.capacity = capacity[0];
+            (len[0], &src[VERIFIED_MESSAGES_HEADER_LEN..])
+        };
+
+        let capacity = verified_messages.capacity;
+        if messages_len > capacity || data_flat.len() < VERIFIED_MESSAGE_LEN * capacity as usize {
+            return Err(ProgramError::InvalidAccountData);
+        }
 
         let mut offset = 0;
         for _ in 0..messages_len {
             let messages_flat = array_ref![data_flat, offset, VERIFIED_MESSAGE_LEN];
             let (address, message, operator) = array_refs![messages_flat, 20, 128, 20];
 
-            messages.push(VerifiedMessage {
+            verified_messages.messages.push(VerifiedMessage {
                 address: *address as EthereumAddress,
                 message: *message as VoteMessage,
                 operator: *operator as EthereumAddress,
@@ -135,17 +294,24 @@ impl Pack for VerifiedMessages {
             offset += VERIFIED_MESSAGE_LEN;
         }
 
-        Ok(Self {
-            version,
-            reward_manager: Pubkey::new_from_array(*reward_manager),
-            messages,
-        })
+        Ok(verified_messages)
+    }
+
+    /// Unpacks initialized `VerifiedMessages` of either layout
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        let verified_messages = Self::unpack_unchecked(src)?;
+        if !verified_messages.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(verified_messages)
     }
 }
 
+impl Sealed for VerifiedMessages {}
+
 impl IsInitialized for VerifiedMessages {
     fn is_initialized(&self) -> bool {
-        self.version != UNINITIALIZED_VERSION
+        self.version != UNINITIALIZED_VERSION && self.version != CLOSED_VERSION
     }
 }
 
@@ -162,6 +328,10 @@ pub mod tests {
     pub const TEST_DATA: VerifiedMessages = VerifiedMessages {
         version: TEST_VERSION,
         reward_manager: TEST_PUBKEY,
+        funder: Pubkey::new_from_array([0; 32]),
+        slot: 0,
+        seed: vec![],
+        capacity: LEGACY_VERIFIED_MESSAGES_CAPACITY,
         messages: vec![],
     };
 
@@ -178,4 +348,87 @@ pub mod tests {
             TEST_DATA
         );
     }
+
+    #[test]
+    fn pack_unpack_capacity() {
+        let mut verified_messages = VerifiedMessages::new(
+            TEST_PUBKEY,
+            Pubkey::new_from_array([1; 32]),
+            42,
+            b"V_4r4t23df32543f55".to_vec(),
+            21,
+        );
+        for i in 0..21 {
+            verified_messages
+                .add(VerifiedMessage {
+                    address: [i; 20],
+                    message: [i; 128],
+                    operator: [i; 20],
+                })
+                .unwrap();
+        }
+        assert!(verified_messages
+            .add(VerifiedMessage {
+                address: [0; 20],
+                message: [0; 128],
+                operator: [0; 20],
+            })
+            .is_err());
+
+        let mut data = vec![0; VerifiedMessages::size(21)];
+        VerifiedMessages::pack(verified_messages.clone(), &mut data).unwrap();
+        assert_eq!(VerifiedMessages::unpack(&data).unwrap(), verified_messages);
+
+        let mut small = vec![0; VerifiedMessages::size(20)];
+        assert_eq!(
+            VerifiedMessages::pack(verified_messages, &mut small),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn unpack_other_account_types() {
+        let mut data = vec![0; VerifiedMessages::size(5)];
+        data[0] = crate::state::REWARD_MANAGER_VERSION;
+        assert_eq!(
+            VerifiedMessages::unpack(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // Seeds can't be longer than `MAX_SEED_LEN`
+        let mut verified_messages =
+            VerifiedMessages::new(TEST_PUBKEY, TEST_PUBKEY, 0, vec![1; MAX_SEED_LEN], 5);
+        VerifiedMessages::pack(verified_messages.clone(), &mut data).unwrap();
+        data[73] = MAX_SEED_LEN as u8 + 1;
+        assert_eq!(
+            VerifiedMessages::unpack(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        verified_messages.seed.push(1);
+        assert_eq!(
+            VerifiedMessages::pack(verified_messages, &mut data),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        );
+    }
+
+    #[test]
+    fn pack_legacy_layout() {
+        let mut data = vec![0; 874];
+        data[0] = TEST_VERSION;
+        let mut verified_messages = VerifiedMessages::unpack_unchecked(&data).unwrap();
+        verified_messages
+            .add(VerifiedMessage {
+                address: [1; 20],
+                message: [2; 128],
+                operator: [3; 20],
+            })
+            .unwrap();
+
+        VerifiedMessages::pack(verified_messages.clone(), &mut data).unwrap();
+        assert_eq!(verified_messages.packed_len(), 874);
+        assert_eq!(data[33], 1);
+        assert_eq!(&data[34..54], &[1; 20]);
+        assert_eq!(VerifiedMessages::unpack(&data).unwrap(), verified_messages);
+    }
 }
@@ -1,16 +1,23 @@
 #![allow(missing_docs)]
 use crate::{
     error::AudiusProgramError,
-    state::{VerifiedMessage, TOTAL_VERIFIED_MESSAGES},
+    state::{RewardManager, VerifiedMessage},
     vote_message,
 };
+pub use audius_secp_utils::{
+    assert_instructions_sysvar, assert_rent_sysvar, assert_system_program, close_account,
+    resize_account, EthereumAddress,
+};
+use claimable_tokens::utils::program::find_address_pair;
 use solana_program::{
     account_info::AccountInfo,
+    clock::Slot,
     entrypoint::ProgramResult,
+    hash::hashv,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
-    program_pack::IsInitialized,
-    pubkey::Pubkey,
+    program_pack::{IsInitialized, Pack},
+    pubkey::{Pubkey, MAX_SEED_LEN},
     rent::Rent,
     system_instruction,
 };
@@ -37,6 +44,15 @@ pub fn assert_uninitialized<T: IsInitialized>(account: &T) -> ProgramResult {
     }
 }
 
+/// Assert `account_info` wasn't closed earlier in the transaction
+pub fn assert_not_closed(account_info: &AccountInfo) -> ProgramResult {
+    if audius_secp_utils::is_closed(&account_info.data.borrow()) {
+        Err(AudiusProgramError::AccountClosed.into())
+    } else {
+        Ok(())
+    }
+}
+
 /// Assert `account_info`'s key matches `key`
 pub fn assert_account_key(account_info: &AccountInfo, key: &Pubkey) -> ProgramResult {
     if *account_info.key != *key {
@@ -46,24 +62,61 @@ pub fn assert_account_key(account_info: &AccountInfo, key: &Pubkey) -> ProgramRe
     }
 }
 
-/// Assert unique senders & operators, and message count <= TOTAL_VERIFIED_MESSAGES
-pub fn assert_unique_senders(messages: &[VerifiedMessage]) -> ProgramResult {
-    let mut uniq_senders = BTreeSet::new();
-    let mut uniq_operators = BTreeSet::new();
-    let mut messages_iter = messages.iter();
+/// Assert `account_info` is the SPL token program
+pub fn assert_spl_token_program(account_info: &AccountInfo) -> ProgramResult {
+    audius_secp_utils::assert_program(account_info, &spl_token::id())
+}
 
-    if messages.len() > TOTAL_VERIFIED_MESSAGES {
-        return Err(AudiusProgramError::MessagesOverflow.into());
+/// Assert at least one sender vote is required, and `min_votes` senders
+/// plus the bot oracle fit into `VerifiedMessages`
+pub fn assert_valid_min_votes(min_votes: u8) -> ProgramResult {
+    if min_votes == 0 || min_votes.checked_add(1).is_none() {
+        Err(AudiusProgramError::InvalidMinVotes.into())
+    } else {
+        Ok(())
     }
+}
 
-    // Check sender address collision
-    if !messages_iter.all(move |x| uniq_senders.insert(x.address)) {
-        return Err(AudiusProgramError::RepeatedSenders.into());
+/// Assert a window limit has a window to apply to, and single transfers
+/// may use the whole window limit at most
+pub fn assert_valid_disbursement_limits(
+    max_transfer_amount: u64,
+    max_window_amount: u64,
+    window_slots: Slot,
+) -> ProgramResult {
+    if (window_slots == 0 && max_window_amount != u64::MAX)
+        || max_transfer_amount > max_window_amount
+    {
+        Err(AudiusProgramError::InvalidDisbursementLimits.into())
+    } else {
+        Ok(())
     }
+}
 
-    // Check sender operator collision
-    if !messages_iter.all(move |x| uniq_operators.insert(x.operator)) {
-        return Err(AudiusProgramError::OperatorCollision.into());
+/// Assert rewards aren't paused by the manager
+pub fn assert_not_paused(reward_manager: &RewardManager) -> ProgramResult {
+    if reward_manager.paused {
+        Err(AudiusProgramError::Paused.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Assert unique senders & operators
+pub fn assert_unique_senders(messages: &[VerifiedMessage]) -> ProgramResult {
+    let mut uniq_senders = BTreeSet::new();
+    let mut uniq_operators = BTreeSet::new();
+
+    for message in messages {
+        // Check sender address collision
+        if !uniq_senders.insert(message.address) {
+            return Err(AudiusProgramError::RepeatedSenders.into());
+        }
+
+        // Check sender operator collision
+        if !uniq_operators.insert(message.operator) {
+            return Err(AudiusProgramError::OperatorCollision.into());
+        }
     }
 
     Ok(())
@@ -102,6 +155,26 @@ pub fn assert_valid_attestations(
     Ok(())
 }
 
+/// Assert `recipient_info` is the claimable-tokens user bank derived from
+/// `eth_recipient` and holds tokens of `mint`
+pub fn assert_recipient_bank(
+    recipient_info: &AccountInfo,
+    mint: &Pubkey,
+    eth_recipient: &EthereumAddress,
+) -> ProgramResult {
+    let pair = find_address_pair(&claimable_tokens::id(), mint, *eth_recipient)?;
+    if *recipient_info.key != pair.derive.address {
+        return Err(AudiusProgramError::WrongRecipientKey.into());
+    }
+
+    let recipient = spl_token::state::Account::unpack(&recipient_info.data.borrow())?;
+    if recipient.mint != *mint {
+        return Err(AudiusProgramError::WrongRecipientKey.into());
+    }
+
+    Ok(())
+}
+
 pub fn assert_initialized<T: IsInitialized>(account: &T) -> ProgramResult {
     if !account.is_initialized() {
         Err(ProgramError::InvalidAccountData)
@@ -110,9 +183,6 @@ pub fn assert_initialized<T: IsInitialized>(account: &T) -> ProgramResult {
     }
 }
 
-/// Represent compressed ethereum pubkey
-pub type EthereumAddress = [u8; 20];
-
 /// Finds a program address, using first 32 bytes of `pubkey` as seed and
 /// `program_id` as base
 pub fn find_program_address(program_id: &Pubkey, pubkey: &Pubkey) -> (Pubkey, u8) {
@@ -139,6 +209,42 @@ pub fn find_derived_pair(
     (reward_manager_authority, derived_address, bump_seed)
 }
 
+/// Seed of the account derived from `prefix` and transfer `id` by the legacy
+/// transfer instructions, which use the id as is. Longer ids don't fit into
+/// a seed and need the `V2` instructions.
+pub fn transfer_id_seed(prefix: &str, id: &str) -> Result<Vec<u8>, ProgramError> {
+    let seed = [prefix.as_bytes(), id.as_bytes()].concat();
+    if seed.len() > MAX_SEED_LEN {
+        return Err(AudiusProgramError::TransferIdTooLong.into());
+    }
+    Ok(seed)
+}
+
+/// Seed of the account derived from `prefix` and transfer `id` by the `V2`
+/// transfer instructions, a sha256 of both so ids of any length fit
+pub fn hashed_transfer_id_seed(prefix: &str, id: &str) -> Vec<u8> {
+    hashv(&[prefix.as_bytes(), id.as_bytes()]).to_bytes().to_vec()
+}
+
+/// Seeds the accounts of a transfer id are derived from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferIdSeed {
+    /// Prefixed id as is, see `transfer_id_seed`
+    Raw,
+    /// Hash of the prefix and id, see `hashed_transfer_id_seed`
+    Hashed,
+}
+
+impl TransferIdSeed {
+    /// Seed of the account derived from `prefix` and transfer `id`
+    pub fn seed(self, prefix: &str, id: &str) -> Result<Vec<u8>, ProgramError> {
+        match self {
+            TransferIdSeed::Raw => transfer_id_seed(prefix, id),
+            TransferIdSeed::Hashed => Ok(hashed_transfer_id_seed(prefix, id)),
+        }
+    }
+}
+
 /// Initialize SPL account instruction.
 pub fn spl_initialize_account<'a>(
     account: AccountInfo<'a>,
@@ -206,3 +312,68 @@ pub fn create_account<'a>(
 
     invoke_signed(&ix, &[from, to], signers_seeds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_transfer_id_seed() {
+        // Keeps the addresses of accounts created by the legacy instructions
+        assert_eq!(
+            transfer_id_seed("T_", "4r4t23df32543f55").unwrap(),
+            b"T_4r4t23df32543f55"
+        );
+        let id = "a".repeat(MAX_SEED_LEN - 2);
+        assert_eq!(
+            transfer_id_seed("V_", &id).unwrap(),
+            [b"V_", id.as_bytes()].concat()
+        );
+        assert_eq!(
+            transfer_id_seed("V_", &"a".repeat(MAX_SEED_LEN - 1)),
+            Err(AudiusProgramError::TransferIdTooLong.into())
+        );
+    }
+
+    #[test]
+    fn hashed_transfer_id_seeds() {
+        // Short ids are hashed too
+        let seed = TransferIdSeed::Hashed.seed("T_", "4r4t23df32543f55").unwrap();
+        assert_eq!(seed.len(), MAX_SEED_LEN);
+        assert_ne!(seed, transfer_id_seed("T_", "4r4t23df32543f55").unwrap());
+
+        let id = "a".repeat(MAX_SEED_LEN - 1);
+        let seed = TransferIdSeed::Hashed.seed("V_", &id).unwrap();
+        assert_eq!(seed, hashed_transfer_id_seed("V_", &id));
+        assert_ne!(seed, hashed_transfer_id_seed("T_", &id));
+    }
+
+    #[test]
+    fn unique_senders() {
+        let message = |address, operator| VerifiedMessage {
+            address: [address; 20],
+            message: [0; 128],
+            operator: [operator; 20],
+        };
+
+        assert!(assert_unique_senders(&[message(1, 1), message(2, 2)]).is_ok());
+        assert_eq!(
+            assert_unique_senders(&[message(1, 1), message(1, 2)]),
+            Err(AudiusProgramError::RepeatedSenders.into())
+        );
+        // Two senders run by the same operator
+        assert_eq!(
+            assert_unique_senders(&[message(1, 1), message(2, 1)]),
+            Err(AudiusProgramError::OperatorCollision.into())
+        );
+    }
+
+    #[test]
+    fn valid_min_votes() {
+        assert!(assert_valid_min_votes(1).is_ok());
+        assert!(assert_valid_min_votes(u8::MAX - 1).is_ok());
+        // No votes would let an empty signer list pass every quorum
+        assert!(assert_valid_min_votes(0).is_err());
+        assert!(assert_valid_min_votes(u8::MAX).is_err());
+    }
+}
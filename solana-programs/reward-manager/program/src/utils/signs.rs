@@ -1,48 +1,43 @@
 #![allow(missing_docs)]
 use super::*;
 use crate::{
-    error::{to_audius_program_error, AudiusProgramError},
+    error::AudiusProgramError,
     processor::SENDER_SEED_PREFIX,
-    state::{SenderAccount, VoteMessage},
+    state::{
+        SenderAccount, VerifiedMessage, VoteMessage, TRANSFER_MESSAGE_EIP191,
+        TRANSFER_MESSAGE_PREFIX, UPDATE_CONFIG_MESSAGE_EIP191, UPDATE_CONFIG_MESSAGE_PREFIX,
+        UPDATE_SENDER_MESSAGE_EIP191, UPDATE_SENDER_MESSAGE_PREFIX,
+    },
+    vote_message,
 };
+pub use audius_secp_utils::{SecpSignature, SecpSignatureOffsets};
+use audius_secp_utils::{load_previous_secp_signatures, load_secp_signatures_before};
 use borsh::BorshDeserialize;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
-    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey, secp256k1_program,
-    sysvar,
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey, sysvar,
 };
 use std::{
     collections::{BTreeMap, BTreeSet},
-    convert::TryInto,
+    mem::size_of,
 };
 
-/// Attempts to retrieve all instructions before `index_current_instruction`
-/// as secp recovery instructions, and asserts that it finds `necessary_instructions_count`
-/// instructions.
-pub fn get_secp_instructions(
+/// Retrieves the signatures checked by all secp instructions before
+/// `index_current_instruction`, and asserts that it finds
+/// `necessary_signatures_count` signatures.
+pub fn get_secp_signatures(
     index_current_instruction: u16,
-    necessary_instructions_count: usize,
+    necessary_signatures_count: usize,
     instruction_info: &AccountInfo,
-) -> Result<Vec<Instruction>, AudiusProgramError> {
-    let mut secp_instructions: Vec<Instruction> = Vec::new();
-
-    for ind in 0..index_current_instruction {
-        let instruction = sysvar::instructions::load_instruction_at(
-            ind as usize,
-            &instruction_info.data.borrow(),
-        )
-        .map_err(to_audius_program_error)?;
-
-        if instruction.program_id == secp256k1_program::id() {
-            secp_instructions.push(instruction);
-        }
-    }
+) -> Result<Vec<SecpSignature>, ProgramError> {
+    let signatures = load_secp_signatures_before(instruction_info, index_current_instruction)
+        .map_err(AudiusProgramError::from)?;
 
-    if secp_instructions.len() != necessary_instructions_count {
-        return Err(AudiusProgramError::Secp256InstructionMissing);
+    if signatures.len() != necessary_signatures_count {
+        return Err(AudiusProgramError::Secp256InstructionMissing.into());
     }
 
-    Ok(secp_instructions)
+    Ok(signatures)
 }
 
 /// Returns the eth_addresses and operators associated with `signers`.
@@ -94,45 +89,35 @@ pub fn get_and_verify_signer_metadata<'a>(
     Ok((senders_eth_addresses, operators))
 }
 
-/// Retrieves an eth signer from an secp instruction
-pub fn get_signer_from_secp_instruction(secp_instruction_data: Vec<u8>) -> EthereumAddress {
-    let eth_address_offset = 12;
-    let instruction_signer =
-        secp_instruction_data[eth_address_offset..eth_address_offset + 20].to_vec();
-    let instruction_signer: EthereumAddress = instruction_signer.as_slice().try_into().unwrap();
-    instruction_signer
-}
-
-// meta (12) + address (20) + signature (65) = 97
-const MESSAGE_DATA_OFFSET: usize = 97;
-
-/// Assert that the message contained in `secp_instruction_data`
-/// matches `expected_message`.
-pub fn check_message_from_secp_instruction(
-    secp_instruction_data: Vec<u8>,
-    expected_message: &[u8],
-) -> Result<(), ProgramError> {
-    let message = secp_instruction_data[MESSAGE_DATA_OFFSET..].to_vec();
-    if message != *expected_message {
-        Err(AudiusProgramError::SignatureVerificationFailed.into())
-    } else {
-        Ok(())
-    }
+/// Signed prefix of transfer attestations, binding them to `program_id`
+/// and `reward_manager`
+pub fn transfer_attestation_domain(program_id: &Pubkey, reward_manager: &Pubkey) -> Vec<u8> {
+    [
+        TRANSFER_MESSAGE_PREFIX.as_ref(),
+        program_id.as_ref(),
+        reward_manager.as_ref(),
+    ]
+    .concat()
 }
 
-/// Attempts to parse out a `vote_message` from `secp_instruction_data`.
-pub fn get_vote_message_from_secp_instruction(
-    secp_instruction_data: Vec<u8>,
+/// Attempts to parse out a transfer attestation signed for `program_id` and
+/// `reward_manager` from a signed `message`. The returned `vote_message`
+/// keeps `TRANSFER_MESSAGE_PREFIX` in place of the domain.
+pub fn get_transfer_message(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    message: &[u8],
 ) -> Result<VoteMessage, ProgramError> {
-    let mut message = secp_instruction_data[MESSAGE_DATA_OFFSET..].to_vec();
+    let domain = transfer_attestation_domain(program_id, reward_manager);
+    if !message.starts_with(&domain) {
+        return Err(AudiusProgramError::SignatureVerificationFailed.into());
+    }
 
-    while message.len() < 128 {
-        message.push(0);
+    let message = [TRANSFER_MESSAGE_PREFIX.as_bytes(), &message[domain.len()..]].concat();
+    if message.len() > size_of::<VoteMessage>() {
+        return Err(AudiusProgramError::SignatureVerificationFailed.into());
     }
-    message
-        .as_slice()
-        .try_into()
-        .map_err(|_| AudiusProgramError::SignatureVerificationFailed.into())
+    Ok(vote_message!(message))
 }
 
 fn vec_into_checkmap(vec: &[EthereumAddress]) -> BTreeMap<EthereumAddress, bool> {
@@ -161,31 +146,60 @@ fn check_signer(
     Ok(())
 }
 
-/// Validates secp instructions for add or delete sender instructions.
-pub fn validate_secp_add_delete_sender(
+/// Validates that the secp instructions preceding the current one are
+/// signed by distinct `expected_signers` over `expected_message`,
+/// or over its EIP-191 prefixed form if `accept_eip191` is set.
+pub fn validate_secp_quorum(
     program_id: &Pubkey,
     reward_manager: &Pubkey,
     instruction_info: &AccountInfo,
     expected_signers: Vec<&AccountInfo>,
     extraction_depth: usize,
-    new_sender: EthereumAddress,
-    message_prefix: &str,
+    expected_message: &[u8],
+    accept_eip191: bool,
 ) -> ProgramResult {
-    let index = sysvar::instructions::load_current_index(&instruction_info.data.borrow());
+    assert_instructions_sysvar(instruction_info)?;
+
+    let index = sysvar::instructions::load_current_index_checked(instruction_info)?;
     // Instruction can't be first in transaction
     // because must follow after `new_secp256k1_instruction`
     if index == 0 {
         return Err(AudiusProgramError::Secp256InstructionMissing.into());
     }
 
-    // Load previous instructions
-    let secp_instructions = get_secp_instructions(index, extraction_depth, instruction_info)?;
+    // Load signatures of previous instructions
+    let signatures = get_secp_signatures(index, extraction_depth, instruction_info)?;
 
     // Get the eth addresses associated with our expected_signers
     let (senders_eth_addresses, _) =
         get_and_verify_signer_metadata(program_id, reward_manager, expected_signers)?;
 
     let mut checkmap = vec_into_checkmap(&senders_eth_addresses);
+
+    // For each signature, assert that the signer was expected and not duplicated
+    // and that the message is formatted correctly.
+    for signature in signatures {
+        check_signer(&mut checkmap, &signature.eth_address)?;
+        if signature.signed_message(accept_eip191) != expected_message {
+            return Err(AudiusProgramError::SignatureVerificationFailed.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates secp instructions for add or delete sender instructions.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_secp_add_delete_sender(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    instruction_info: &AccountInfo,
+    expected_signers: Vec<&AccountInfo>,
+    extraction_depth: usize,
+    new_sender: EthereumAddress,
+    message_prefix: &str,
+    accept_eip191: bool,
+) -> ProgramResult {
     let expected_message = [
         message_prefix.as_ref(),
         reward_manager.as_ref(),
@@ -193,48 +207,159 @@ pub fn validate_secp_add_delete_sender(
     ]
     .concat();
 
-    // For each secp instruction, assert that the signer was expected and not duplicated
-    // and that the message is formatted correctly.
-    for secp_instruction in secp_instructions {
-        let eth_signer = get_signer_from_secp_instruction(secp_instruction.data.clone());
-        check_signer(&mut checkmap, &eth_signer)?;
-        check_message_from_secp_instruction(secp_instruction.data, expected_message.as_ref())?;
-    }
+    validate_secp_quorum(
+        program_id,
+        reward_manager,
+        instruction_info,
+        expected_signers,
+        extraction_depth,
+        &expected_message,
+        accept_eip191,
+    )
+}
 
-    Ok(())
+/// Validates secp instructions for the update sender instruction,
+/// the message binds the current sender address and operator to the new ones,
+/// so signatures can't be replayed once the sender was updated.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_secp_update_sender(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    instruction_info: &AccountInfo,
+    expected_signers: Vec<&AccountInfo>,
+    extraction_depth: usize,
+    sender: EthereumAddress,
+    operator: EthereumAddress,
+    new_eth_address: EthereumAddress,
+    new_operator: EthereumAddress,
+) -> ProgramResult {
+    let expected_message = [
+        UPDATE_SENDER_MESSAGE_PREFIX.as_ref(),
+        reward_manager.as_ref(),
+        sender.as_ref(),
+        operator.as_ref(),
+        new_eth_address.as_ref(),
+        new_operator.as_ref(),
+    ]
+    .concat();
+
+    validate_secp_quorum(
+        program_id,
+        reward_manager,
+        instruction_info,
+        expected_signers,
+        extraction_depth,
+        &expected_message,
+        UPDATE_SENDER_MESSAGE_EIP191,
+    )
+}
+
+/// Validates secp instructions for the update reward manager config instruction.
+/// Signatures cover the reward manager's current `config_nonce`, so they
+/// can't be replayed once any config update went through.
+pub fn validate_secp_update_config(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    instruction_info: &AccountInfo,
+    expected_signers: Vec<&AccountInfo>,
+    extraction_depth: usize,
+    config_nonce: u64,
+    min_votes: u8,
+) -> ProgramResult {
+    let expected_message = [
+        UPDATE_CONFIG_MESSAGE_PREFIX.as_ref(),
+        reward_manager.as_ref(),
+        config_nonce.to_le_bytes().as_ref(),
+        &[min_votes],
+    ]
+    .concat();
+
+    validate_secp_quorum(
+        program_id,
+        reward_manager,
+        instruction_info,
+        expected_signers,
+        extraction_depth,
+        &expected_message,
+        UPDATE_CONFIG_MESSAGE_EIP191,
+    )
 }
 
 /// Checks secp instruction for submit_attestation:
-/// ensures the message is signed by `expected_signer`, and
-/// returns the message.
+/// ensures the message is signed by `expected_signer` for `program_id`
+/// and `reward_manager`, and returns the message.
 pub fn validate_secp_submit_attestation(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
     instruction_info: &AccountInfo,
     expected_signer: &EthereumAddress,
 ) -> Result<VoteMessage, ProgramError> {
-    let index = sysvar::instructions::load_current_index(&instruction_info.data.borrow());
+    assert_instructions_sysvar(instruction_info)?;
+
+    // The previous instruction may check several signatures, use the one by `expected_signer`
+    let signature = load_previous_secp_signatures(instruction_info)
+        .map_err(AudiusProgramError::from)?
+        .into_iter()
+        .find(|signature| signature.eth_address == *expected_signer)
+        .ok_or(AudiusProgramError::WrongSigner)?;
+
+    get_transfer_message(
+        program_id,
+        reward_manager,
+        signature.signed_message(TRANSFER_MESSAGE_EIP191),
+    )
+}
+
+/// Checks that the secp instructions preceding the current one are transfer
+/// attestations signed by each of `senders` exactly once, and returns them
+/// as verified messages.
+pub fn get_secp_transfer_attestations(
+    program_id: &Pubkey,
+    reward_manager: &Pubkey,
+    instruction_info: &AccountInfo,
+    senders: Vec<&AccountInfo>,
+) -> Result<Vec<VerifiedMessage>, ProgramError> {
+    assert_instructions_sysvar(instruction_info)?;
 
+    let index = sysvar::instructions::load_current_index_checked(instruction_info)?;
     // Instruction can't be first in transaction
     // because must follow after `new_secp256k1_instruction`
     if index == 0 {
         return Err(AudiusProgramError::Secp256InstructionMissing.into());
     }
 
-    // Load previous instruction
-    let secp_instruction = sysvar::instructions::load_instruction_at(
-        (index - 1) as usize,
-        &instruction_info.data.borrow(),
-    )
-    .map_err(to_audius_program_error)?;
+    // Load signatures of previous instructions
+    let signatures = get_secp_signatures(index, senders.len(), instruction_info)?;
 
-    // Check that instruction is `new_secp256k1_instruction`
-    if secp_instruction.program_id != secp256k1_program::id() {
-        return Err(AudiusProgramError::Secp256InstructionMissing.into());
-    }
+    // Get the eth addresses associated with our senders
+    let (senders_eth_addresses, _) =
+        get_and_verify_signer_metadata(program_id, reward_manager, senders.clone())?;
 
-    let eth_signer = get_signer_from_secp_instruction(secp_instruction.data.clone());
-    if eth_signer != *expected_signer {
-        return Err(AudiusProgramError::WrongSigner.into());
+    let mut checkmap = vec_into_checkmap(&senders_eth_addresses);
+
+    // For each signature, assert that the signer was expected and not duplicated,
+    // and parse out the attestation it signed.
+    let mut messages = Vec::with_capacity(signatures.len());
+    for signature in signatures {
+        let eth_signer = signature.eth_address;
+        check_signer(&mut checkmap, &eth_signer)?;
+
+        let sender_index = senders_eth_addresses
+            .iter()
+            .position(|address| *address == eth_signer)
+            .ok_or(AudiusProgramError::WrongSigner)?;
+        let sender = SenderAccount::try_from_slice(&senders[sender_index].data.borrow())?;
+
+        messages.push(VerifiedMessage {
+            address: eth_signer,
+            message: get_transfer_message(
+                program_id,
+                reward_manager,
+                signature.signed_message(TRANSFER_MESSAGE_EIP191),
+            )?,
+            operator: sender.operator,
+        });
     }
 
-    get_vote_message_from_secp_instruction(secp_instruction.data)
+    Ok(messages)
 }
@@ -5,9 +5,12 @@ use audius_reward_manager::instruction;
 use solana_sdk::signature::Keypair;
 use utils::program_test;
 
-use solana_program::program_pack::Pack;
+use solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_sdk::{
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
 use utils::*;
 
 #[tokio::test]
@@ -55,7 +58,7 @@ async fn success_change_manager_authority() {
         )
         .unwrap()],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &manager_account],
+        &[&context.payer, &manager_account, &new_manager],
         context.last_blockhash,
     );
 
@@ -122,7 +125,7 @@ async fn failure_change_manager_authority_bad_manager() {
         )
         .unwrap()],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &manager_account],
+        &[&context.payer, &manager_account, &new_manager],
         context.last_blockhash,
     );
 
@@ -182,9 +185,71 @@ async fn failure_change_manager_authority_bad_authority() {
         )
         .unwrap()],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &manager_account],
+        &[&context.payer, &manager_account, &new_manager],
         context.last_blockhash,
     );
 
     context.banks_client.process_transaction(tx).await;
 }
+
+#[tokio::test]
+async fn failure_change_manager_authority_new_manager_not_signer() {
+    let program_test = program_test();
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    let min_votes = 3;
+
+    let mut context = program_test.start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        min_votes,
+    )
+    .await;
+
+    // A mistyped new manager can't sign, so it must not be able to take over
+    let mut change_manager = instruction::change_manager_authority(
+        &audius_reward_manager::id(),
+        &reward_manager.pubkey(),
+        &manager_account.pubkey(),
+        &Pubkey::new_unique(),
+    )
+    .unwrap();
+    change_manager.accounts[2].is_signer = false;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[change_manager],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+    );
+}
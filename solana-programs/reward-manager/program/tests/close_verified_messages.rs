@@ -0,0 +1,428 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    error::AudiusProgramError,
+    instruction,
+    processor::VERIFY_TRANSFER_SEED_PREFIX,
+    state::{RewardManager, VerifiedMessages, DEFAULT_VERIFIED_MESSAGES_MAX_AGE},
+    utils::{find_derived_pair, transfer_id_seed},
+    PROGRAM_VERSION,
+};
+use solana_program::{
+    clock::Slot, instruction::InstructionError, program_pack::Pack, pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+const VERIFIED_MESSAGES_LAMPORTS: u64 = 9000;
+const TRANSFER_ID: &str = "4r4t23df32543f55";
+
+/// Program owned account holding `data`
+fn program_account_with_data(data: Vec<u8>) -> Account {
+    Account {
+        lamports: VERIFIED_MESSAGES_LAMPORTS,
+        data,
+        owner: audius_reward_manager::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Adds a reward manager managed by `manager` to `program_test`
+fn add_reward_manager(program_test: &mut ProgramTest, manager: &Pubkey) -> Pubkey {
+    let reward_manager = Pubkey::new_unique();
+    let mut data = vec![0; RewardManager::LEN];
+    RewardManager::pack(
+        RewardManager::new(Pubkey::new_unique(), *manager, 3),
+        &mut data,
+    )
+    .unwrap();
+    program_test.add_account(reward_manager, program_account_with_data(data));
+    reward_manager
+}
+
+/// Verified messages of `TRANSFER_ID` for `reward_manager`, created at `slot` by `funder`
+fn verified_messages_data(reward_manager: &Pubkey, funder: Pubkey, slot: Slot) -> Vec<u8> {
+    let seed = transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, TRANSFER_ID).unwrap();
+    let mut data = vec![0; VerifiedMessages::size(4)];
+    VerifiedMessages::pack(
+        VerifiedMessages::new(*reward_manager, funder, slot, seed, 4),
+        &mut data,
+    )
+    .unwrap();
+    data
+}
+
+fn verified_messages_address(reward_manager: &Pubkey) -> Pubkey {
+    find_derived_pair(
+        &audius_reward_manager::id(),
+        reward_manager,
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, TRANSFER_ID).unwrap(),
+    )
+    .1
+}
+
+/// Starts the program with a verified messages account created at `slot` by `funder`
+async fn setup(funder: Pubkey, slot: Slot) -> (ProgramTestContext, Pubkey, Pubkey, Keypair) {
+    let mut program_test = program_test();
+
+    let manager = Keypair::new();
+    let reward_manager = add_reward_manager(&mut program_test, &manager.pubkey());
+    let verified_messages = verified_messages_address(&reward_manager);
+    program_test.add_account(
+        verified_messages,
+        program_account_with_data(verified_messages_data(&reward_manager, funder, slot)),
+    );
+
+    (
+        program_test.start_with_context().await,
+        verified_messages,
+        reward_manager,
+        manager,
+    )
+}
+
+async fn close_verified_messages(
+    context: &mut ProgramTestContext,
+    verified_messages: &Pubkey,
+    reward_manager: &Pubkey,
+    funder: &Pubkey,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::close_verified_messages(
+            &audius_reward_manager::id(),
+            verified_messages,
+            reward_manager,
+            funder,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_close_verified_messages() {
+    let funder = Pubkey::new_unique();
+    let (mut context, verified_messages, reward_manager, _) = setup(funder, 1).await;
+
+    context
+        .warp_to_slot(1 + DEFAULT_VERIFIED_MESSAGES_MAX_AGE)
+        .unwrap();
+
+    close_verified_messages(&mut context, &verified_messages, &reward_manager, &funder)
+        .await
+        .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(verified_messages)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(funder).await.unwrap(),
+        VERIFIED_MESSAGES_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn success_close_verified_messages_updated_max_age() {
+    let funder = Pubkey::new_unique();
+    let (mut context, verified_messages, reward_manager, manager) = setup(funder, 1).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_verified_messages_max_age(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &manager.pubkey(),
+            10,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let reward_manager_account = get_account(&mut context, &reward_manager).await.unwrap();
+    assert_eq!(
+        RewardManager::unpack(&reward_manager_account.data)
+            .unwrap()
+            .verified_messages_max_age,
+        10
+    );
+
+    context.warp_to_slot(11).unwrap();
+
+    close_verified_messages(&mut context, &verified_messages, &reward_manager, &funder)
+        .await
+        .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(funder).await.unwrap(),
+        VERIFIED_MESSAGES_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn failure_update_verified_messages_max_age_wrong_manager() {
+    let (mut context, _, reward_manager, _) = setup(Pubkey::new_unique(), 1).await;
+
+    let other_manager = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_verified_messages_max_age(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &other_manager.pubkey(),
+            10,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &other_manager],
+        context.last_blockhash,
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_close_verified_messages_twice_in_one_transaction() {
+    let funder = Pubkey::new_unique();
+    let (mut context, verified_messages, reward_manager, _) = setup(funder, 1).await;
+
+    context
+        .warp_to_slot(1 + DEFAULT_VERIFIED_MESSAGES_MAX_AGE)
+        .unwrap();
+
+    let close = instruction::close_verified_messages(
+        &audius_reward_manager::id(),
+        &verified_messages,
+        &reward_manager,
+        &funder,
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[close.clone(), close],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    // The closed account still exists until the end of the transaction
+    let res = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(1, InstructionError::UninitializedAccount)
+    );
+}
+
+#[tokio::test]
+async fn failure_close_verified_messages_not_expired() {
+    let funder = Pubkey::new_unique();
+    let (mut context, verified_messages, reward_manager, _) = setup(funder, 1).await;
+
+    let res =
+        close_verified_messages(&mut context, &verified_messages, &reward_manager, &funder).await;
+    assert_custom_error(res, 0, AudiusProgramError::VerifiedMessagesNotExpired);
+}
+
+#[tokio::test]
+async fn failure_close_verified_messages_wrong_funder() {
+    let (mut context, verified_messages, reward_manager, _) =
+        setup(Pubkey::new_unique(), 1).await;
+
+    context
+        .warp_to_slot(1 + DEFAULT_VERIFIED_MESSAGES_MAX_AGE)
+        .unwrap();
+
+    let res = close_verified_messages(
+        &mut context,
+        &verified_messages,
+        &reward_manager,
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_close_verified_messages_other_reward_manager() {
+    let funder = Pubkey::new_unique();
+    let mut program_test = program_test();
+
+    let reward_manager = add_reward_manager(&mut program_test, &Pubkey::new_unique());
+    let other_reward_manager = add_reward_manager(&mut program_test, &Pubkey::new_unique());
+    let verified_messages = verified_messages_address(&reward_manager);
+    program_test.add_account(
+        verified_messages,
+        program_account_with_data(verified_messages_data(&reward_manager, funder, 1)),
+    );
+
+    // Same data at an address which wasn't derived from the stored seed
+    let copied_verified_messages = Pubkey::new_unique();
+    program_test.add_account(
+        copied_verified_messages,
+        program_account_with_data(verified_messages_data(&reward_manager, funder, 1)),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    context
+        .warp_to_slot(1 + DEFAULT_VERIFIED_MESSAGES_MAX_AGE)
+        .unwrap();
+
+    for (verified_messages, reward_manager) in [
+        (verified_messages, other_reward_manager),
+        (copied_verified_messages, reward_manager),
+    ]
+    .iter()
+    {
+        let res = close_verified_messages(&mut context, verified_messages, reward_manager, &funder)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            res,
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+        );
+    }
+}
+
+#[tokio::test]
+async fn failure_close_reward_manager_as_verified_messages() {
+    let (mut context, _, reward_manager, manager) = setup(Pubkey::new_unique(), 1).await;
+
+    context
+        .warp_to_slot(1 + DEFAULT_VERIFIED_MESSAGES_MAX_AGE)
+        .unwrap();
+
+    let res = close_verified_messages(
+        &mut context,
+        &reward_manager,
+        &reward_manager,
+        &manager.pubkey(),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+    assert!(get_account(&mut context, &reward_manager).await.is_some());
+}
+
+/// Starts the program with a legacy verified messages account, which has
+/// a 34 byte header and room for five messages
+async fn setup_legacy() -> (ProgramTestContext, Pubkey, Pubkey, Keypair) {
+    let mut program_test = program_test();
+
+    let manager = Keypair::new();
+    let reward_manager = add_reward_manager(&mut program_test, &manager.pubkey());
+    let verified_messages = verified_messages_address(&reward_manager);
+    let mut data = vec![0; 874];
+    data[0] = PROGRAM_VERSION;
+    data[1..33].copy_from_slice(reward_manager.as_ref());
+    program_test.add_account(verified_messages, program_account_with_data(data));
+
+    (
+        program_test.start_with_context().await,
+        verified_messages,
+        reward_manager,
+        manager,
+    )
+}
+
+#[tokio::test]
+async fn success_close_legacy_verified_messages() {
+    let (mut context, verified_messages, reward_manager, manager) = setup_legacy().await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::close_legacy_verified_messages(
+            &audius_reward_manager::id(),
+            &verified_messages,
+            &reward_manager,
+            &manager.pubkey(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(get_account(&mut context, &verified_messages).await.is_none());
+    assert_eq!(
+        context.banks_client.get_balance(manager.pubkey()).await.unwrap(),
+        VERIFIED_MESSAGES_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn failure_close_legacy_verified_messages_without_manager() {
+    let (mut context, verified_messages, reward_manager, manager) = setup_legacy().await;
+
+    // Unsigned by the manager
+    let res = close_verified_messages(
+        &mut context,
+        &verified_messages,
+        &reward_manager,
+        &manager.pubkey(),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+    );
+
+    // Signed by someone else
+    let other_manager = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::close_legacy_verified_messages(
+            &audius_reward_manager::id(),
+            &verified_messages,
+            &reward_manager,
+            &other_manager.pubkey(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &other_manager],
+        context.last_blockhash,
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
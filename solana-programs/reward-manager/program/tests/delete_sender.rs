@@ -8,20 +8,24 @@ use audius_reward_manager::{
 };
 use borsh::BorshSerialize;
 use rand::{thread_rng, Rng};
-use solana_program::pubkey::Pubkey;
+use solana_program::{instruction::InstructionError, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::{account::Account, signature::Keypair, signer::Signer, transaction::Transaction};
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
 use utils::program_test;
 
-#[tokio::test]
-async fn success_delete_sender() {
+/// Starts the program with a reward manager and one of its senders
+async fn setup() -> (ProgramTestContext, Pubkey, Keypair, EthereumAddress, Pubkey) {
     let mut program_test = program_test();
     let mut rng = thread_rng();
 
     let token_account = Pubkey::new_unique();
     let reward_manager = Pubkey::new_unique();
     let manager_account = Keypair::new();
-    let refunder_account = Pubkey::new_unique();
     let eth_address: EthereumAddress = rng.gen();
     let operator: EthereumAddress = rng.gen();
 
@@ -57,7 +61,21 @@ async fn success_delete_sender() {
         },
     );
 
-    let mut context = program_test.start_with_context().await;
+    (
+        program_test.start_with_context().await,
+        reward_manager,
+        manager_account,
+        eth_address,
+        derived_address,
+    )
+}
+
+#[tokio::test]
+async fn success_delete_sender() {
+    let (mut context, reward_manager, manager_account, eth_address, derived_address) =
+        setup().await;
+    let refunder_account = Pubkey::new_unique();
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::delete_sender(
             &audius_reward_manager::id(),
@@ -81,3 +99,35 @@ async fn success_delete_sender() {
         .unwrap();
     assert!(account.is_none());
 }
+
+#[tokio::test]
+async fn failure_delete_sender_twice_in_one_transaction() {
+    let (mut context, reward_manager, manager_account, eth_address, _) = setup().await;
+
+    let delete = instruction::delete_sender(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &manager_account.pubkey(),
+        &Pubkey::new_unique(),
+        eth_address,
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[delete.clone(), delete],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+
+    // The deleted sender reads as closed until the end of the transaction
+    let res = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(1, InstructionError::UninitializedAccount)
+    );
+}
@@ -0,0 +1,365 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    instruction::{self, EvaluateAttestationsArgs},
+    processor::{SENDER_SEED_PREFIX, TRANSFER_SEED_PREFIX},
+    utils::{find_derived_pair, EthereumAddress, TransferIdSeed},
+};
+use libsecp256k1::{PublicKey, SecretKey};
+use rand::{thread_rng, Rng};
+use solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    secp256k1_instruction::construct_eth_pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+struct Setup {
+    context: ProgramTestContext,
+    reward_manager: Keypair,
+    mint: Keypair,
+    token_account: Keypair,
+    sender_key: SecretKey,
+    sender: Pubkey,
+    oracle_key: SecretKey,
+    oracle_eth_address: EthereumAddress,
+    oracle: Pubkey,
+}
+
+/// Creates a sender for `key`, returning its eth address and account
+async fn create_sender_from_key(
+    context: &mut ProgramTestContext,
+    reward_manager: &Keypair,
+    manager_account: &Keypair,
+    key: &SecretKey,
+) -> (EthereumAddress, Pubkey) {
+    let mut rng = thread_rng();
+    let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(key));
+    create_sender(
+        context,
+        &reward_manager.pubkey(),
+        manager_account,
+        eth_address,
+        rng.gen(),
+    )
+    .await;
+
+    let (_, sender, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager.pubkey(),
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    (eth_address, sender)
+}
+
+/// Initializes a reward manager with a single vote required, funded with 100_000 tokens
+async fn setup() -> Setup {
+    let mut rng = thread_rng();
+    let mut program_test = program_test();
+    program_test.add_program("claimable_tokens", claimable_tokens::id(), None);
+    let mut context = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        1,
+    )
+    .await;
+
+    mint_tokens_to(
+        &mut context,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        100_000,
+    )
+    .await
+    .unwrap();
+
+    let sender_key = SecretKey::parse(&rng.gen()).unwrap();
+    let (_, sender) =
+        create_sender_from_key(&mut context, &reward_manager, &manager_account, &sender_key).await;
+    let oracle_key = SecretKey::parse(&rng.gen()).unwrap();
+    let (oracle_eth_address, oracle) =
+        create_sender_from_key(&mut context, &reward_manager, &manager_account, &oracle_key).await;
+
+    Setup {
+        context,
+        reward_manager,
+        mint,
+        token_account,
+        sender_key,
+        sender,
+        oracle_key,
+        oracle_eth_address,
+        oracle,
+    }
+}
+
+/// Submits the sender and bot oracle attestations for a transfer
+async fn submit_attestations(
+    setup: &mut Setup,
+    id: &str,
+    eth_recipient: EthereumAddress,
+    amount: u64,
+    seed: TransferIdSeed,
+) {
+    let reward_manager = setup.reward_manager.pubkey();
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager,
+        &[
+            eth_recipient.as_ref(),
+            b"_",
+            amount.to_le_bytes().as_ref(),
+            b"_",
+            id.as_ref(),
+        ]
+        .concat(),
+    );
+    let senders_message = transfer_attestation(
+        &reward_manager,
+        &[
+            eth_recipient.as_ref(),
+            b"_",
+            amount.to_le_bytes().as_ref(),
+            b"_",
+            id.as_ref(),
+            b"_",
+            setup.oracle_eth_address.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let submit_attestations = match seed {
+        TransferIdSeed::Raw => instruction::submit_attestations,
+        TransferIdSeed::Hashed => instruction::submit_attestations_v2,
+    };
+
+    let context = &mut setup.context;
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 0),
+            submit_attestations(
+                &audius_reward_manager::id(),
+                &reward_manager,
+                &setup.sender,
+                &context.payer.pubkey(),
+                id.to_string(),
+            )
+            .unwrap(),
+            new_secp256k1_instruction_2_0(&setup.oracle_key, &bot_oracle_message, 2),
+            submit_attestations(
+                &audius_reward_manager::id(),
+                &reward_manager,
+                &setup.oracle,
+                &context.payer.pubkey(),
+                id.to_string(),
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+/// Creates the claimable-tokens user bank of `eth_recipient`
+async fn create_recipient(setup: &mut Setup, eth_recipient: EthereumAddress) -> Pubkey {
+    create_recipient_with_claimable_program(
+        &mut setup.context,
+        &setup.mint.pubkey(),
+        eth_recipient,
+    )
+    .await;
+    claimable_tokens::utils::program::find_address_pair(
+        &claimable_tokens::id(),
+        &setup.mint.pubkey(),
+        eth_recipient,
+    )
+    .unwrap()
+    .derive
+    .address
+}
+
+async fn evaluate_attestations_batch(
+    setup: &mut Setup,
+    transfers: Vec<(EvaluateAttestationsArgs, Pubkey)>,
+    seed: TransferIdSeed,
+) -> Result<(), TransportError> {
+    let evaluate_attestations_batch = match seed {
+        TransferIdSeed::Raw => instruction::evaluate_attestations_batch,
+        TransferIdSeed::Hashed => instruction::evaluate_attestations_batch_v2,
+    };
+
+    let context = &mut setup.context;
+    let tx = Transaction::new_signed_with_payer(
+        &[evaluate_attestations_batch(
+            &audius_reward_manager::id(),
+            &setup.reward_manager.pubkey(),
+            &setup.token_account.pubkey(),
+            &setup.oracle,
+            &context.payer.pubkey(),
+            transfers,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn token_balance(context: &mut ProgramTestContext, token_account: Pubkey) -> u64 {
+    let account = get_account(context, &token_account).await.unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+fn transfer_account(reward_manager: &Keypair, id: &str, seed: TransferIdSeed) -> Pubkey {
+    find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager.pubkey(),
+        &seed.seed(TRANSFER_SEED_PREFIX, id).unwrap(),
+    )
+    .1
+}
+
+#[tokio::test]
+async fn success_evaluate_attestations_batch() {
+    process_successful_batch(["first-transfer", "second-transfer"], TransferIdSeed::Raw).await;
+}
+
+#[tokio::test]
+async fn success_evaluate_attestations_batch_v2() {
+    // Ids of any length, the second one doesn't fit into a seed
+    process_successful_batch(
+        [
+            "first-transfer",
+            "trending-playlist:2021-08-13:0x7a3f41b27c0d2a9e5b11",
+        ],
+        TransferIdSeed::Hashed,
+    )
+    .await;
+}
+
+async fn process_successful_batch(ids: [&str; 2], seed: TransferIdSeed) {
+    let mut setup = setup().await;
+
+    let transfers = [(ids[0], [1; 20], 100), (ids[1], [2; 20], 200)];
+    let mut batch = Vec::new();
+    for (id, eth_recipient, amount) in transfers.iter() {
+        submit_attestations(&mut setup, id, *eth_recipient, *amount, seed).await;
+        let recipient = create_recipient(&mut setup, *eth_recipient).await;
+        batch.push((
+            EvaluateAttestationsArgs {
+                amount: *amount,
+                id: id.to_string(),
+                eth_recipient: *eth_recipient,
+            },
+            recipient,
+        ));
+    }
+
+    evaluate_attestations_batch(&mut setup, batch.clone(), seed)
+        .await
+        .unwrap();
+
+    for ((id, _, amount), (_, recipient)) in transfers.iter().zip(batch) {
+        assert_eq!(token_balance(&mut setup.context, recipient).await, *amount);
+        assert!(get_account(
+            &mut setup.context,
+            &transfer_account(&setup.reward_manager, id, seed)
+        )
+        .await
+        .is_some());
+    }
+    assert_eq!(
+        token_balance(&mut setup.context, setup.token_account.pubkey()).await,
+        100_000 - 300
+    );
+}
+
+#[tokio::test]
+async fn failure_evaluate_attestations_batch_is_atomic() {
+    let mut setup = setup().await;
+
+    submit_attestations(&mut setup, "first-transfer", [1; 20], 100, TransferIdSeed::Raw).await;
+    submit_attestations(&mut setup, "second-transfer", [2; 20], 200, TransferIdSeed::Raw).await;
+    let first_recipient = create_recipient(&mut setup, [1; 20]).await;
+    let second_recipient = create_recipient(&mut setup, [2; 20]).await;
+
+    // The second transfer claims more than was attested
+    let res = evaluate_attestations_batch(
+        &mut setup,
+        vec![
+            (
+                EvaluateAttestationsArgs {
+                    amount: 100,
+                    id: "first-transfer".to_string(),
+                    eth_recipient: [1; 20],
+                },
+                first_recipient,
+            ),
+            (
+                EvaluateAttestationsArgs {
+                    amount: 300,
+                    id: "second-transfer".to_string(),
+                    eth_recipient: [2; 20],
+                },
+                second_recipient,
+            ),
+        ],
+        TransferIdSeed::Raw,
+    )
+    .await;
+    assert!(res.is_err());
+
+    assert_eq!(token_balance(&mut setup.context, first_recipient).await, 0);
+    assert!(get_account(
+        &mut setup.context,
+        &transfer_account(&setup.reward_manager, "first-transfer", TransferIdSeed::Raw)
+    )
+    .await
+    .is_none());
+}
+
+#[tokio::test]
+async fn failure_evaluate_attestations_batch_empty() {
+    let mut setup = setup().await;
+
+    let res = evaluate_attestations_batch(&mut setup, vec![], TransferIdSeed::Raw)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
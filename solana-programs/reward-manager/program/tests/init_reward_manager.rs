@@ -1,7 +1,7 @@
 #![cfg(feature = "test-bpf")]
 
 mod utils;
-use audius_reward_manager::instruction;
+use audius_reward_manager::{error::AudiusProgramError, instruction};
 use borsh::BorshSerialize;
 use solana_program::program_option::COption;
 use solana_program::program_pack::IsInitialized;
@@ -156,3 +156,49 @@ async fn fail_already_initialized() {
         TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
     );
 }
+
+#[tokio::test]
+async fn fail_zero_min_votes() {
+    let mut program_test = program_test();
+
+    let reward_manager = Pubkey::new_unique();
+    program_test.add_account(
+        reward_manager,
+        Account {
+            lamports: 9000,
+            data: vec![0; audius_reward_manager::state::RewardManager::LEN],
+            owner: audius_reward_manager::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::init(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            0,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AudiusProgramError::InvalidMinVotes as u32)
+        )
+    );
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{instruction, state::RewardManager, PROGRAM_VERSION};
+use solana_program::{
+    instruction::InstructionError, program_pack::Pack, pubkey::Pubkey, rent::Rent,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+// 1 + 32 + 32 + 1
+const LEGACY_REWARD_MANAGER_LEN: usize = 66;
+
+/// Starts the program with a legacy reward manager managed by the returned keypair
+async fn setup_legacy() -> (ProgramTestContext, Pubkey, Keypair, RewardManager) {
+    let mut program_test = program_test();
+
+    let reward_manager = Pubkey::new_unique();
+    let manager = Keypair::new();
+    let mut legacy = RewardManager::new(Pubkey::new_unique(), manager.pubkey(), 3);
+    legacy.version = PROGRAM_VERSION;
+
+    let mut data = vec![0; LEGACY_REWARD_MANAGER_LEN];
+    RewardManager::pack(legacy.clone(), &mut data).unwrap();
+    program_test.add_account(
+        reward_manager,
+        Account {
+            lamports: Rent::default().minimum_balance(LEGACY_REWARD_MANAGER_LEN),
+            data,
+            owner: audius_reward_manager::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    (
+        program_test.start_with_context().await,
+        reward_manager,
+        manager,
+        legacy,
+    )
+}
+
+async fn migrate(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    manager_account: &Keypair,
+    funder: &Pubkey,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::migrate_reward_manager(
+            &audius_reward_manager::id(),
+            reward_manager,
+            &manager_account.pubkey(),
+            funder,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_migrate_reward_manager() {
+    let (mut context, reward_manager, manager, legacy) = setup_legacy().await;
+
+    let payer = context.payer.pubkey();
+    migrate(&mut context, &reward_manager, &manager, &payer)
+        .await
+        .unwrap();
+
+    let account = get_account(&mut context, &reward_manager).await.unwrap();
+    assert_eq!(account.data.len(), RewardManager::LEN);
+    assert!(Rent::default().is_exempt(account.lamports, RewardManager::LEN));
+    assert_eq!(
+        RewardManager::unpack(&account.data).unwrap(),
+        RewardManager::new(legacy.token_account, legacy.manager, legacy.min_votes)
+    );
+
+    // Fields of the current layout can be stored now
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_paused(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &manager.pubkey(),
+            true,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = get_account(&mut context, &reward_manager).await.unwrap();
+    assert!(RewardManager::unpack(&account.data).unwrap().paused);
+
+    // Already migrated, the manager funds to keep the transaction distinct
+    let res = migrate(&mut context, &reward_manager, &manager, &manager.pubkey())
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn failure_migrate_reward_manager_bad_manager() {
+    let (mut context, reward_manager, _, _) = setup_legacy().await;
+
+    let payer = context.payer.pubkey();
+    let res = migrate(&mut context, &reward_manager, &Keypair::new(), &payer)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    let account = get_account(&mut context, &reward_manager).await.unwrap();
+    assert_eq!(account.data.len(), LEGACY_REWARD_MANAGER_LEN);
+}
@@ -0,0 +1,224 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{error::AudiusProgramError, instruction, state::RewardManager};
+use solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+/// Initializes a reward manager and returns it with its manager and token account
+async fn setup() -> (ProgramTestContext, Keypair, Keypair, Keypair) {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        3,
+    )
+    .await;
+
+    (context, reward_manager, manager_account, token_account)
+}
+
+async fn propose_manager(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    current_manager: &Keypair,
+    proposed_manager: &Pubkey,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::propose_manager(
+            &audius_reward_manager::id(),
+            reward_manager,
+            &current_manager.pubkey(),
+            proposed_manager,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, current_manager],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn accept_manager(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    proposed_manager: &Keypair,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_manager(
+            &audius_reward_manager::id(),
+            reward_manager,
+            &proposed_manager.pubkey(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, proposed_manager],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn cancel_manager_proposal(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    current_manager: &Keypair,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::cancel_manager_proposal(
+            &audius_reward_manager::id(),
+            reward_manager,
+            &current_manager.pubkey(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, current_manager],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_propose_and_accept_manager() {
+    let (mut context, reward_manager, manager_account, token_account) = setup().await;
+    let new_manager = Keypair::new();
+
+    propose_manager(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        &new_manager.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let mut expected = RewardManager::new(token_account.pubkey(), manager_account.pubkey(), 3);
+    expected.pending_manager = new_manager.pubkey();
+    assert_eq!(
+        expected,
+        context
+            .banks_client
+            .get_account_data_with_borsh(reward_manager.pubkey())
+            .await
+            .unwrap()
+    );
+
+    accept_manager(&mut context, &reward_manager.pubkey(), &new_manager)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        RewardManager::new(token_account.pubkey(), new_manager.pubkey(), 3),
+        context
+            .banks_client
+            .get_account_data_with_borsh(reward_manager.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn failure_propose_manager_bad_manager() {
+    let (mut context, reward_manager, _, _) = setup().await;
+
+    let res = propose_manager(
+        &mut context,
+        &reward_manager.pubkey(),
+        &Keypair::new(),
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_accept_manager_not_proposed() {
+    let (mut context, reward_manager, manager_account, _) = setup().await;
+    let new_manager = Keypair::new();
+
+    propose_manager(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        &new_manager.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let res = accept_manager(&mut context, &reward_manager.pubkey(), &Keypair::new())
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_accept_manager_without_proposal() {
+    let (mut context, reward_manager, _, _) = setup().await;
+
+    let res = accept_manager(&mut context, &reward_manager.pubkey(), &Keypair::new()).await;
+    assert_custom_error(res, 0, AudiusProgramError::NoPendingManager);
+}
+
+#[tokio::test]
+async fn success_cancel_manager_proposal() {
+    let (mut context, reward_manager, manager_account, token_account) = setup().await;
+    let new_manager = Keypair::new();
+
+    propose_manager(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        &new_manager.pubkey(),
+    )
+    .await
+    .unwrap();
+    cancel_manager_proposal(&mut context, &reward_manager.pubkey(), &manager_account)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        RewardManager::new(token_account.pubkey(), manager_account.pubkey(), 3),
+        context
+            .banks_client
+            .get_account_data_with_borsh(reward_manager.pubkey())
+            .await
+            .unwrap()
+    );
+
+    let res = accept_manager(&mut context, &reward_manager.pubkey(), &new_manager).await;
+    assert_custom_error(res, 0, AudiusProgramError::NoPendingManager);
+}
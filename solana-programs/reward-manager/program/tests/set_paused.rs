@@ -0,0 +1,278 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    error::AudiusProgramError,
+    instruction,
+    processor::{SENDER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX},
+    state::{RewardManager, SenderAccount, VerifiedMessages},
+    utils::{find_derived_pair, transfer_id_seed, EthereumAddress},
+};
+use rand::{thread_rng, Rng};
+use solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+/// Program owned account holding `data`
+fn program_account_with_data(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 9000,
+        data,
+        owner: audius_reward_manager::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Packs `state` into a program owned account
+fn program_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    program_account_with_data(data)
+}
+
+fn sender_address(reward_manager: &Pubkey, eth_address: &EthereumAddress) -> Pubkey {
+    find_derived_pair(
+        &audius_reward_manager::id(),
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    )
+    .1
+}
+
+/// Initializes a reward manager and returns it with its manager
+async fn setup() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        3,
+    )
+    .await;
+
+    (context, reward_manager, manager_account)
+}
+
+async fn set_paused(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    manager_account: &Keypair,
+    paused: bool,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_paused(
+            &audius_reward_manager::id(),
+            reward_manager,
+            &manager_account.pubkey(),
+            paused,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Starts the program with a paused reward manager
+async fn setup_paused(program_test: ProgramTest, reward_manager: Pubkey) -> ProgramTestContext {
+    let mut program_test = program_test;
+    let mut paused = RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    paused.paused = true;
+    program_test.add_account(reward_manager, program_account(paused));
+    program_test.start_with_context().await
+}
+
+#[tokio::test]
+async fn success_set_paused() {
+    let (mut context, reward_manager, manager_account) = setup().await;
+
+    set_paused(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        true,
+    )
+    .await
+    .unwrap();
+    let state: RewardManager = context
+        .banks_client
+        .get_account_data_with_borsh(reward_manager.pubkey())
+        .await
+        .unwrap();
+    assert!(state.paused);
+
+    set_paused(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        false,
+    )
+    .await
+    .unwrap();
+    let state: RewardManager = context
+        .banks_client
+        .get_account_data_with_borsh(reward_manager.pubkey())
+        .await
+        .unwrap();
+    assert!(!state.paused);
+}
+
+#[tokio::test]
+async fn failure_set_paused_bad_manager() {
+    let (mut context, reward_manager, _) = setup().await;
+
+    let res = set_paused(
+        &mut context,
+        &reward_manager.pubkey(),
+        &Keypair::new(),
+        true,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_create_sender_public_paused() {
+    let reward_manager = Pubkey::new_unique();
+    let mut context = setup_paused(program_test(), reward_manager).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::create_sender_public(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &context.payer.pubkey(),
+            [1; 20],
+            [2; 20],
+            &[],
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 0, AudiusProgramError::Paused);
+}
+
+#[tokio::test]
+async fn failure_submit_attestations_paused() {
+    let mut rng = thread_rng();
+    let mut program_test = program_test();
+
+    let reward_manager = Pubkey::new_unique();
+    let eth_address: EthereumAddress = rng.gen();
+    let sender = sender_address(&reward_manager, &eth_address);
+    program_test.add_account(
+        sender,
+        program_account(SenderAccount::new(reward_manager, eth_address, rng.gen())),
+    );
+    let mut context = setup_paused(program_test, reward_manager).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::submit_attestations(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &sender,
+            &context.payer.pubkey(),
+            "4r4t23df32543f55".to_string(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 0, AudiusProgramError::Paused);
+}
+
+#[tokio::test]
+async fn failure_evaluate_attestations_paused() {
+    let mut rng = thread_rng();
+    let mut program_test = program_test();
+
+    let reward_manager = Pubkey::new_unique();
+    let id = "4r4t23df32543f55".to_string();
+    let oracle_eth_address: EthereumAddress = rng.gen();
+    let bot_oracle = sender_address(&reward_manager, &oracle_eth_address);
+    program_test.add_account(
+        bot_oracle,
+        program_account(SenderAccount::new(
+            reward_manager,
+            oracle_eth_address,
+            rng.gen(),
+        )),
+    );
+
+    let (_, verified_messages, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, &id).unwrap(),
+    );
+    let seed = transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, &id).unwrap();
+    let mut verified_messages_data = vec![0; VerifiedMessages::size(1)];
+    VerifiedMessages::pack(
+        VerifiedMessages::new(reward_manager, Pubkey::new_unique(), 0, seed, 1),
+        &mut verified_messages_data,
+    )
+    .unwrap();
+    program_test.add_account(
+        verified_messages,
+        program_account_with_data(verified_messages_data),
+    );
+    let mut context = setup_paused(program_test, reward_manager).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::evaluate_attestations(
+            &audius_reward_manager::id(),
+            &verified_messages,
+            &reward_manager,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &bot_oracle,
+            &context.payer.pubkey(),
+            10_000u64,
+            id,
+            rng.gen(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 0, AudiusProgramError::Paused);
+}
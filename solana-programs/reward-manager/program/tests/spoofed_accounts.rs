@@ -0,0 +1,415 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    instruction,
+    processor::{SENDER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX},
+    state::{RewardManager, SenderAccount, VerifiedMessages},
+    utils::{find_derived_pair, transfer_id_seed, EthereumAddress},
+};
+use rand::{thread_rng, Rng};
+use solana_program::{
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::program_test;
+
+/// Program owned account holding `data`
+fn program_account_with_data(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 9000,
+        data,
+        owner: audius_reward_manager::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Packs `state` into a program owned account
+fn program_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    program_account_with_data(data)
+}
+
+fn sender_address(reward_manager: &Pubkey, eth_address: &EthereumAddress) -> Pubkey {
+    find_derived_pair(
+        &audius_reward_manager::id(),
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    )
+    .1
+}
+
+/// Replaces the account at `position` with a random one and asserts
+/// the instruction fails with `expected_error`
+async fn assert_spoofed_account_rejected(
+    context: &mut ProgramTestContext,
+    instruction: &Instruction,
+    position: usize,
+    signers: &[&Keypair],
+    expected_error: InstructionError,
+) {
+    let mut spoofed = instruction.clone();
+    spoofed.accounts[position].pubkey = Pubkey::new_unique();
+
+    let mut tx_signers = vec![&context.payer];
+    tx_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        &[spoofed],
+        Some(&context.payer.pubkey()),
+        &tx_signers,
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, expected_error)
+    );
+}
+
+#[tokio::test]
+async fn failure_init_spoofed_accounts() {
+    let mut context = program_test().start_with_context().await;
+
+    let instruction = instruction::init(
+        &audius_reward_manager::id(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        3,
+    )
+    .unwrap();
+
+    // spl_token
+    assert_spoofed_account_rejected(
+        &mut context,
+        &instruction,
+        5,
+        &[],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+    // rent
+    assert_spoofed_account_rejected(
+        &mut context,
+        &instruction,
+        6,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn failure_create_delete_sender_spoofed_accounts() {
+    let mut context = program_test().start_with_context().await;
+    let mut rng = thread_rng();
+
+    let reward_manager = Pubkey::new_unique();
+    let manager_account = Keypair::new();
+    let eth_address: EthereumAddress = rng.gen();
+
+    let create_sender = instruction::create_sender(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &manager_account.pubkey(),
+        &context.payer.pubkey(),
+        eth_address,
+        rng.gen(),
+    )
+    .unwrap();
+
+    // system_program
+    assert_spoofed_account_rejected(
+        &mut context,
+        &create_sender,
+        5,
+        &[&manager_account],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+    // rent
+    assert_spoofed_account_rejected(
+        &mut context,
+        &create_sender,
+        6,
+        &[&manager_account],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+
+    let delete_sender = instruction::delete_sender(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &manager_account.pubkey(),
+        &Pubkey::new_unique(),
+        eth_address,
+    )
+    .unwrap();
+
+    // system_program
+    assert_spoofed_account_rejected(
+        &mut context,
+        &delete_sender,
+        4,
+        &[&manager_account],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn failure_create_delete_sender_public_spoofed_accounts() {
+    let mut program_test = program_test();
+    let mut rng = thread_rng();
+
+    let reward_manager = Pubkey::new_unique();
+    let eth_address: EthereumAddress = rng.gen();
+
+    // No votes required, so the instructions sysvar is the only thing
+    // standing between the caller and the sender account
+    program_test.add_account(
+        reward_manager,
+        program_account(RewardManager::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+        )),
+    );
+    program_test.add_account(
+        sender_address(&reward_manager, &eth_address),
+        program_account(SenderAccount::new(reward_manager, eth_address, rng.gen())),
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    let create_sender_public = instruction::create_sender_public(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &context.payer.pubkey(),
+        rng.gen(),
+        rng.gen(),
+        &[],
+    )
+    .unwrap();
+
+    // sysvar::instructions
+    assert_spoofed_account_rejected(
+        &mut context,
+        &create_sender_public,
+        4,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+    // rent
+    assert_spoofed_account_rejected(
+        &mut context,
+        &create_sender_public,
+        5,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+    // system_program
+    assert_spoofed_account_rejected(
+        &mut context,
+        &create_sender_public,
+        6,
+        &[],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+
+    let delete_sender_public = instruction::delete_sender_public(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &Pubkey::new_unique(),
+        eth_address,
+        &[],
+    )
+    .unwrap();
+
+    // sysvar::instructions
+    assert_spoofed_account_rejected(
+        &mut context,
+        &delete_sender_public,
+        3,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn failure_submit_evaluate_attestations_spoofed_accounts() {
+    let mut program_test = program_test();
+    let mut rng = thread_rng();
+
+    let reward_manager = Pubkey::new_unique();
+    let eth_address: EthereumAddress = rng.gen();
+    let sender = sender_address(&reward_manager, &eth_address);
+    let id = String::from("4r4t23df32543f55");
+
+    program_test.add_account(
+        reward_manager,
+        program_account(RewardManager::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            3,
+        )),
+    );
+    program_test.add_account(
+        sender,
+        program_account(SenderAccount::new(reward_manager, eth_address, rng.gen())),
+    );
+
+    let (_, verified_messages, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, &id).unwrap(),
+    );
+    let seed = transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, &id).unwrap();
+    let mut verified_messages_data = vec![0; VerifiedMessages::size(4)];
+    VerifiedMessages::pack(
+        VerifiedMessages::new(reward_manager, Pubkey::new_unique(), 0, seed, 4),
+        &mut verified_messages_data,
+    )
+    .unwrap();
+    program_test.add_account(
+        verified_messages,
+        program_account_with_data(verified_messages_data),
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    let submit_attestations = instruction::submit_attestations(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &sender,
+        &context.payer.pubkey(),
+        id.clone(),
+    )
+    .unwrap();
+
+    // rent
+    assert_spoofed_account_rejected(
+        &mut context,
+        &submit_attestations,
+        5,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+    // sysvar::instructions
+    assert_spoofed_account_rejected(
+        &mut context,
+        &submit_attestations,
+        6,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+    // system_program
+    assert_spoofed_account_rejected(
+        &mut context,
+        &submit_attestations,
+        7,
+        &[],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+
+    let evaluate_attestations = instruction::evaluate_attestations(
+        &audius_reward_manager::id(),
+        &verified_messages,
+        &reward_manager,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &sender,
+        &context.payer.pubkey(),
+        10_000u64,
+        id,
+        rng.gen(),
+    )
+    .unwrap();
+
+    // rent
+    assert_spoofed_account_rejected(
+        &mut context,
+        &evaluate_attestations,
+        8,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+    // spl_token
+    assert_spoofed_account_rejected(
+        &mut context,
+        &evaluate_attestations,
+        9,
+        &[],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+    // system_program
+    assert_spoofed_account_rejected(
+        &mut context,
+        &evaluate_attestations,
+        10,
+        &[],
+        InstructionError::IncorrectProgramId,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_public_spoofed_accounts() {
+    let mut program_test = program_test();
+
+    let reward_manager = Pubkey::new_unique();
+    program_test.add_account(
+        reward_manager,
+        program_account(RewardManager::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+        )),
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    let update_config_public = instruction::update_reward_manager_config_public(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        1,
+        &[],
+    )
+    .unwrap();
+
+    // sysvar::instructions
+    assert_spoofed_account_rejected(
+        &mut context,
+        &update_config_public,
+        1,
+        &[],
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
@@ -0,0 +1,402 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    error::AudiusProgramError,
+    instruction,
+    processor::{SENDER_SEED_PREFIX, TRANSFER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX},
+    utils::{find_derived_pair, transfer_id_seed, EthereumAddress, TransferIdSeed},
+};
+use audius_secp_utils::eip191_message;
+use libsecp256k1::{PublicKey, SecretKey};
+use rand::{thread_rng, Rng};
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    secp256k1_instruction::construct_eth_pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction, transport::TransportError,
+};
+use utils::*;
+
+const TRANSFER_ID: &str = "4r4t23df32543f55";
+const ETH_RECIPIENT: EthereumAddress = [7; 20];
+const AMOUNT: u64 = 10_000;
+
+struct Setup {
+    context: ProgramTestContext,
+    reward_manager: Keypair,
+    token_account: Keypair,
+    recipient: Pubkey,
+    sender_key: SecretKey,
+    sender: Pubkey,
+    oracle_key: SecretKey,
+    oracle_eth_address: EthereumAddress,
+    oracle: Pubkey,
+}
+
+/// Creates a sender for `key`, returning its eth address and account
+async fn create_sender_from_key(
+    context: &mut ProgramTestContext,
+    reward_manager: &Keypair,
+    manager_account: &Keypair,
+    key: &SecretKey,
+) -> (EthereumAddress, Pubkey) {
+    let mut rng = thread_rng();
+    let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(key));
+    create_sender(
+        context,
+        &reward_manager.pubkey(),
+        manager_account,
+        eth_address,
+        rng.gen(),
+    )
+    .await;
+
+    let (_, sender, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager.pubkey(),
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    (eth_address, sender)
+}
+
+/// Initializes a funded reward manager with a single vote required,
+/// a sender, the bot oracle and the user bank of `ETH_RECIPIENT`
+async fn setup() -> Setup {
+    let mut rng = thread_rng();
+    let mut program_test = program_test();
+    program_test.add_program("claimable_tokens", claimable_tokens::id(), None);
+    let mut context = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        1,
+    )
+    .await;
+
+    mint_tokens_to(
+        &mut context,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        100_000,
+    )
+    .await
+    .unwrap();
+
+    let sender_key = SecretKey::parse(&rng.gen()).unwrap();
+    let (_, sender) =
+        create_sender_from_key(&mut context, &reward_manager, &manager_account, &sender_key).await;
+    let oracle_key = SecretKey::parse(&rng.gen()).unwrap();
+    let (oracle_eth_address, oracle) =
+        create_sender_from_key(&mut context, &reward_manager, &manager_account, &oracle_key).await;
+
+    create_recipient_with_claimable_program(&mut context, &mint.pubkey(), ETH_RECIPIENT).await;
+    let recipient = claimable_tokens::utils::program::find_address_pair(
+        &claimable_tokens::id(),
+        &mint.pubkey(),
+        ETH_RECIPIENT,
+    )
+    .unwrap()
+    .derive
+    .address;
+
+    Setup {
+        context,
+        reward_manager,
+        token_account,
+        recipient,
+        sender_key,
+        sender,
+        oracle_key,
+        oracle_eth_address,
+        oracle,
+    }
+}
+
+/// Sender and bot oracle attestations of a transfer of `amount`
+fn attestations(setup: &Setup, amount: u64) -> (Vec<u8>, Vec<u8>) {
+    let reward_manager = setup.reward_manager.pubkey();
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager,
+        &[
+            ETH_RECIPIENT.as_ref(),
+            b"_",
+            amount.to_le_bytes().as_ref(),
+            b"_",
+            TRANSFER_ID.as_ref(),
+        ]
+        .concat(),
+    );
+    let senders_message = transfer_attestation(
+        &reward_manager,
+        &[
+            ETH_RECIPIENT.as_ref(),
+            b"_",
+            amount.to_le_bytes().as_ref(),
+            b"_",
+            TRANSFER_ID.as_ref(),
+            b"_",
+            setup.oracle_eth_address.as_ref(),
+        ]
+        .concat(),
+    );
+    (senders_message, bot_oracle_message)
+}
+
+/// Sends `instructions` followed by `SubmitAndEvaluate`
+async fn submit_and_evaluate(
+    setup: &mut Setup,
+    instructions: Vec<Instruction>,
+) -> Result<(), TransportError> {
+    submit_and_evaluate_with_seed(setup, instructions, TransferIdSeed::Raw).await
+}
+
+/// Sends `instructions` followed by `SubmitAndEvaluate`,
+/// or `SubmitAndEvaluateV2` for hashed seeds
+async fn submit_and_evaluate_with_seed(
+    setup: &mut Setup,
+    mut instructions: Vec<Instruction>,
+    seed: TransferIdSeed,
+) -> Result<(), TransportError> {
+    let context = &mut setup.context;
+    let submit_and_evaluate = match seed {
+        TransferIdSeed::Raw => instruction::submit_and_evaluate::<&[Pubkey; 1]>,
+        TransferIdSeed::Hashed => instruction::submit_and_evaluate_v2::<&[Pubkey; 1]>,
+    };
+    instructions.push(
+        submit_and_evaluate(
+            &audius_reward_manager::id(),
+            &setup.reward_manager.pubkey(),
+            &setup.token_account.pubkey(),
+            &setup.recipient,
+            &setup.oracle,
+            &context.payer.pubkey(),
+            &[setup.sender],
+            AMOUNT,
+            TRANSFER_ID.to_string(),
+            ETH_RECIPIENT,
+        )
+        .unwrap(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_submit_and_evaluate() {
+    let mut setup = setup().await;
+
+    let (senders_message, bot_oracle_message) = attestations(&setup, AMOUNT);
+    let instructions = vec![
+        new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 0),
+        new_secp256k1_instruction_2_0(&setup.oracle_key, &bot_oracle_message, 1),
+    ];
+    submit_and_evaluate(&mut setup, instructions).await.unwrap();
+
+    let recipient = get_account(&mut setup.context, &setup.recipient)
+        .await
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&recipient.data)
+            .unwrap()
+            .amount,
+        AMOUNT
+    );
+
+    let reward_manager = setup.reward_manager.pubkey();
+    let (_, transfer_account, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &transfer_id_seed(TRANSFER_SEED_PREFIX, TRANSFER_ID).unwrap(),
+    );
+    assert!(get_account(&mut setup.context, &transfer_account)
+        .await
+        .is_some());
+
+    // No verified messages account is created
+    let (_, verified_messages, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, TRANSFER_ID).unwrap(),
+    );
+    assert!(get_account(&mut setup.context, &verified_messages)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn success_submit_and_evaluate_single_secp_instruction() {
+    let mut setup = setup().await;
+
+    // The whole quorum is checked by one secp instruction
+    let (senders_message, bot_oracle_message) = attestations(&setup, AMOUNT);
+    let instructions = vec![new_secp256k1_instruction_multi(
+        &[
+            (&setup.sender_key, &senders_message),
+            (&setup.oracle_key, &bot_oracle_message),
+        ],
+        0,
+    )];
+    submit_and_evaluate(&mut setup, instructions).await.unwrap();
+
+    let recipient = get_account(&mut setup.context, &setup.recipient)
+        .await
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&recipient.data)
+            .unwrap()
+            .amount,
+        AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn success_submit_and_evaluate_eip191() {
+    let mut setup = setup().await;
+
+    // The sender signs with `personal_sign`, the bot oracle signs the raw attestation
+    let (senders_message, bot_oracle_message) = attestations(&setup, AMOUNT);
+    let instructions = vec![
+        new_secp256k1_instruction_2_0(&setup.sender_key, &eip191_message(&senders_message), 0),
+        new_secp256k1_instruction_2_0(&setup.oracle_key, &bot_oracle_message, 1),
+    ];
+    submit_and_evaluate(&mut setup, instructions).await.unwrap();
+
+    let recipient = get_account(&mut setup.context, &setup.recipient)
+        .await
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&recipient.data)
+            .unwrap()
+            .amount,
+        AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn failure_submit_and_evaluate_wrong_amount() {
+    let mut setup = setup().await;
+
+    let (senders_message, bot_oracle_message) = attestations(&setup, AMOUNT - 1);
+    let instructions = vec![
+        new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 0),
+        new_secp256k1_instruction_2_0(&setup.oracle_key, &bot_oracle_message, 1),
+    ];
+    let res = submit_and_evaluate(&mut setup, instructions).await;
+    assert_custom_error(res, 2, AudiusProgramError::IncorrectMessages);
+}
+
+#[tokio::test]
+async fn failure_submit_and_evaluate_missing_oracle_attestation() {
+    let mut setup = setup().await;
+
+    let (senders_message, _) = attestations(&setup, AMOUNT);
+    let instructions = vec![new_secp256k1_instruction_2_0(
+        &setup.sender_key,
+        &senders_message,
+        0,
+    )];
+    let res = submit_and_evaluate(&mut setup, instructions).await;
+    assert_custom_error(res, 1, AudiusProgramError::Secp256InstructionMissing);
+}
+
+#[tokio::test]
+async fn failure_submit_and_evaluate_replayed_signer() {
+    let mut setup = setup().await;
+
+    // The sender signs twice in place of the bot oracle
+    let (senders_message, _) = attestations(&setup, AMOUNT);
+    let instructions = vec![
+        new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 0),
+        new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 1),
+    ];
+    let res = submit_and_evaluate(&mut setup, instructions).await;
+    assert_custom_error(res, 2, AudiusProgramError::SignCollision);
+}
+
+#[tokio::test]
+async fn success_submit_and_evaluate_v2() {
+    let mut setup = setup().await;
+
+    let (senders_message, bot_oracle_message) = attestations(&setup, AMOUNT);
+    let instructions = vec![
+        new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 0),
+        new_secp256k1_instruction_2_0(&setup.oracle_key, &bot_oracle_message, 1),
+    ];
+    submit_and_evaluate_with_seed(&mut setup, instructions, TransferIdSeed::Hashed)
+        .await
+        .unwrap();
+
+    let recipient = get_account(&mut setup.context, &setup.recipient)
+        .await
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&recipient.data)
+            .unwrap()
+            .amount,
+        AMOUNT
+    );
+
+    // The id fits into a seed, so the legacy transfer account is created too
+    let reward_manager = setup.reward_manager.pubkey();
+    for seed in [TransferIdSeed::Hashed, TransferIdSeed::Raw] {
+        let (_, transfer_account, _) = find_derived_pair(
+            &audius_reward_manager::id(),
+            &reward_manager,
+            &seed.seed(TRANSFER_SEED_PREFIX, TRANSFER_ID).unwrap(),
+        );
+        assert!(get_account(&mut setup.context, &transfer_account)
+            .await
+            .is_some());
+    }
+}
+
+#[tokio::test]
+async fn failure_submit_and_evaluate_paid_by_other_version() {
+    for (paid_by, replayed_by) in [
+        (TransferIdSeed::Raw, TransferIdSeed::Hashed),
+        (TransferIdSeed::Hashed, TransferIdSeed::Raw),
+    ] {
+        let mut setup = setup().await;
+
+        let (senders_message, bot_oracle_message) = attestations(&setup, AMOUNT);
+        let instructions = vec![
+            new_secp256k1_instruction_2_0(&setup.sender_key, &senders_message, 0),
+            new_secp256k1_instruction_2_0(&setup.oracle_key, &bot_oracle_message, 1),
+        ];
+        submit_and_evaluate_with_seed(&mut setup, instructions.clone(), paid_by)
+            .await
+            .unwrap();
+
+        // Replaying the attestations with the other version must not pay again
+        let res = submit_and_evaluate_with_seed(&mut setup, instructions, replayed_by).await;
+        assert_custom_error(res, 2, AudiusProgramError::AlreadySent);
+    }
+}
@@ -1,17 +1,43 @@
 #![cfg(feature = "test-bpf")]
 mod utils;
 
-use audius_reward_manager::{error::AudiusProgramError, instruction, processor::{SENDER_SEED_PREFIX, TRANSFER_ACC_SPACE, TRANSFER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX}, state::{VerifiedMessages}, utils::{find_derived_pair, EthereumAddress}, vote_message};
+use audius_reward_manager::{error::AudiusProgramError, instruction, processor::{SENDER_SEED_PREFIX, TRANSFER_ACC_SPACE, TRANSFER_SEED_PREFIX, VERIFY_TRANSFER_SEED_PREFIX}, state::{RewardManager, VerifiedMessages}, utils::{find_derived_pair, transfer_id_seed, EthereumAddress, TransferIdSeed}};
+use borsh::BorshSerialize;
 use libsecp256k1::{PublicKey, SecretKey};
 use rand::{thread_rng, Rng};
-use solana_program::{instruction::{Instruction}, program_pack::Pack, pubkey::Pubkey};
+use solana_program::{instruction::{Instruction, InstructionError}, program_pack::Pack, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::{secp256k1_instruction::*, signature::Keypair, signer::Signer, transaction::{Transaction}};
+use solana_sdk::{secp256k1_instruction::*, signature::Keypair, signer::Signer, transaction::{Transaction, TransactionError}};
 use std::{mem::MaybeUninit};
 use utils::*;
 
 #[tokio::test]
 async fn success_transfer() {
+    process_successful_transfer("4r4t23df32543f55", 3, TransferIdSeed::Raw).await;
+}
+
+#[tokio::test]
+async fn success_transfer_v2() {
+    process_successful_transfer("4r4t23df32543f55", 3, TransferIdSeed::Hashed).await;
+}
+
+#[tokio::test]
+async fn success_transfer_with_long_id() {
+    // Too long to be used as a seed as is
+    process_successful_transfer(
+        "trending-playlist:2021-08-13:0x7a3f41b27c0d2a9e5b11",
+        3,
+        TransferIdSeed::Hashed,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn success_transfer_with_large_quorum() {
+    process_successful_transfer("4r4t23df32543f55", 20, TransferIdSeed::Raw).await;
+}
+
+async fn process_successful_transfer(transfer_id: &str, min_votes: u8, seed: TransferIdSeed) {
     /* Create verified messages and initialize reward manager */
     let mut program_test = program_test();
 
@@ -44,7 +70,7 @@ async fn success_transfer() {
         &token_account,
         &mint.pubkey(),
         &manager_account.pubkey(),
-        3,
+        min_votes,
     )
     .await;
 
@@ -68,7 +94,6 @@ async fn success_transfer() {
 
     let tokens_amount = 10_000u64;
     let recipient_eth_key = [7u8; 20];
-    let transfer_id = "4r4t23df32543f55";
 
     mint_tokens_to(
         &mut context,
@@ -80,47 +105,327 @@ async fn success_transfer() {
     .await
     .unwrap();
 
-    let bot_oracle_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-    ]
-    .concat());
-
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
 
     // Generate data and create senders
+    let mut attestations = Vec::new();
+    for _ in 0..min_votes {
+        let key: [u8; 32] = rng.gen();
+        let derived_address = create_sender_from(&reward_manager, &manager_account, &mut context, &key, rng.gen()).await;
+        attestations.push((SecretKey::parse(&key).unwrap(), senders_message.as_slice(), derived_address));
+    }
+    attestations.push((oracle_priv_key, bot_oracle_message.as_slice(), oracle_derived_address));
+
+    let submit_attestations = match seed {
+        TransferIdSeed::Raw => instruction::submit_attestations,
+        TransferIdSeed::Hashed => instruction::submit_attestations_v2,
+    };
+    let evaluate_attestations = match seed {
+        TransferIdSeed::Raw => instruction::evaluate_attestations,
+        TransferIdSeed::Hashed => instruction::evaluate_attestations_v2,
+    };
+
+    // Add sender messages and bot oracle, at most 4 per transaction
+    for chunk in attestations.chunks(4) {
+        let mut instructions = Vec::<Instruction>::new();
+        for (priv_key, message, signer) in chunk {
+            let inst = new_secp256k1_instruction_2_0(priv_key, message, instructions.len() as u8);
+            instructions.push(inst);
+            instructions.push(
+                submit_attestations(
+                    &audius_reward_manager::id(),
+                    &reward_manager.pubkey(),
+                    signer,
+                    &context.payer.pubkey(),
+                    transfer_id.to_string()
+                )
+                .unwrap(),
+            );
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+
+    let transfer_account = get_transfer_account(&reward_manager, transfer_id, seed);
+    let verified_messages_account = get_messages_account(&reward_manager, transfer_id, seed);
+
+    let recipient_sol_key = claimable_tokens::utils::program::find_address_pair(
+        &claimable_tokens::id(),
+        &mint.pubkey(),
+        recipient_eth_key,
+    )
+    .unwrap();
+    println!("Creating...Recipient sol key = {:?}", &recipient_sol_key.derive.address);
+    create_recipient_with_claimable_program(&mut context, &mint.pubkey(), recipient_eth_key).await;
+    println!("Created recipient sol key = {:?}", &recipient_sol_key.derive.address);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[evaluate_attestations(
+            &audius_reward_manager::id(),
+            &verified_messages_account,
+            &reward_manager.pubkey(),
+            &token_account.pubkey(),
+            &recipient_sol_key.derive.address,
+            &oracle_derived_address,
+            &context.payer.pubkey(),
+            10_000u64,
+            transfer_id.to_string(),
+            recipient_eth_key,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let transfer_account_data = get_account(&mut context, &transfer_account)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transfer_account_data.lamports,
+        rent.minimum_balance(TRANSFER_ACC_SPACE)
+    );
+    assert_eq!(transfer_account_data.data.len(), TRANSFER_ACC_SPACE);
+
+    // The V2 instructions also mark ids that fit into a seed as paid
+    // for the legacy instructions
+    if seed == TransferIdSeed::Hashed && transfer_id_seed(TRANSFER_SEED_PREFIX, transfer_id).is_ok() {
+        let legacy_transfer_account =
+            get_transfer_account(&reward_manager, transfer_id, TransferIdSeed::Raw);
+        assert!(get_account(&mut context, &legacy_transfer_account).await.is_some());
+    }
+
+    let recipient_account_data = get_account(& mut context, &recipient_sol_key.derive.address).await.unwrap();
+    let recipient_account = spl_token::state::Account::unpack(&recipient_account_data.data.as_slice()).unwrap();
+    assert_eq!(recipient_account.amount, 10_000u64);
+
+    // Disbursement is tracked within the current window
+    let reward_manager_account = get_account(&mut context, &reward_manager.pubkey()).await.unwrap();
+    let reward_manager_data = RewardManager::unpack(&reward_manager_account.data).unwrap();
+    assert_eq!(reward_manager_data.window_disbursed, 10_000u64);
+}
+
+#[tokio::test]
+/// The legacy instructions use transfer ids as seeds as is,
+/// ids that don't fit into a seed need the V2 ones
+async fn failure_submit_attestations_long_id() {
+    let program_test = program_test();
+    let mut rng = thread_rng();
+    let mut context = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        3,
+    )
+    .await;
+
+    let key: [u8; 32] = rng.gen();
+    let sender = create_sender_from(&reward_manager, &manager_account, &mut context, &key, rng.gen()).await;
+
+    let transfer_id = "trending-playlist:2021-08-13:0x7a3f41b27c0d2a9e5b11";
+    assert_eq!(
+        instruction::submit_attestations(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &sender,
+            &context.payer.pubkey(),
+            transfer_id.to_string(),
+        )
+        .unwrap_err(),
+        AudiusProgramError::TransferIdTooLong.into()
+    );
+
+    // The program refuses it too, given the accounts of the V2 instruction
+    let mut submit = instruction::submit_attestations_v2(
+        &audius_reward_manager::id(),
+        &reward_manager.pubkey(),
+        &sender,
+        &context.payer.pubkey(),
+        transfer_id.to_string(),
+    )
+    .unwrap();
+    submit.data = instruction::Instructions::SubmitAttestations(instruction::SubmitAttestationsArgs {
+        id: transfer_id.to_string(),
+    })
+    .try_to_vec()
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[submit],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 0, AudiusProgramError::TransferIdTooLong);
+}
+
+#[tokio::test]
+/// Submits valid attestations for `recipient_eth_key`, then tries to redirect
+/// the payout to a token account that isn't the recipient's user bank.
+async fn failure_transfer_wrong_recipient() {
+    let mut program_test = program_test();
+
+    program_test.add_program("claimable_tokens", claimable_tokens::id(), None);
+    let mut rng = thread_rng();
+
+    let mut context = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        3,
+    )
+    .await;
+
+    let key: [u8; 32] = rng.gen();
+    let oracle_priv_key = SecretKey::parse(&key).unwrap();
+    let secp_oracle_pubkey = PublicKey::from_secret_key(&oracle_priv_key);
+    let eth_oracle_address = construct_eth_pubkey(&secp_oracle_pubkey);
+    let oracle_operator: EthereumAddress = rng.gen();
+
+    let oracle_derived_address = get_oracle_address(&reward_manager, eth_oracle_address);
+
+    create_sender(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        eth_oracle_address,
+        oracle_operator,
+    )
+    .await;
+
+    let tokens_amount = 10_000u64;
+    let recipient_eth_key = [7u8; 20];
+    let transfer_id = "4r4t23df32543f57";
+
+    mint_tokens_to(
+        &mut context,
+        &mint.pubkey(),
+        &token_account.pubkey(),
+        &mint_authority,
+        tokens_amount,
+    )
+    .await
+    .unwrap();
+
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
+
     let keys: [[u8; 32]; 3] = rng.gen();
     let operators: [EthereumAddress; 3] = rng.gen();
     let mut signers: [Pubkey; 3] = unsafe { MaybeUninit::zeroed().assume_init() };
     for (i, key) in keys.iter().enumerate() {
-        let derived_address = create_sender_from(&reward_manager, &manager_account, &mut context,key, operators[i]).await;
+        let derived_address = create_sender_from(&reward_manager, &manager_account, &mut context, key, operators[i]).await;
         signers[i] = derived_address;
     }
 
     let mut instructions = Vec::<Instruction>::new();
-    // Add 3 messages and bot oracle
-    let oracle_sign =
-        new_secp256k1_instruction_2_0(&oracle_priv_key, bot_oracle_message.as_ref(), 0);
-    instructions.push(oracle_sign);
-
     for item in keys.iter().enumerate() {
         let priv_key = SecretKey::parse(item.1).unwrap();
         let inst = new_secp256k1_instruction_2_0(
             &priv_key,
             senders_message.as_ref(),
-            (2 * item.0 + 1) as u8,
+            (2 * item.0) as u8,
         );
         instructions.push(inst);
         instructions.push(
@@ -138,7 +443,7 @@ async fn success_transfer() {
     let oracle_sign = new_secp256k1_instruction_2_0(
         &oracle_priv_key,
         bot_oracle_message.as_ref(),
-        (keys.len() * 2 + 1) as u8,
+        instructions.len() as u8,
     );
     instructions.push(oracle_sign);
     instructions.push(
@@ -158,56 +463,99 @@ async fn success_transfer() {
         &[&context.payer],
         context.last_blockhash,
     );
-
     context.banks_client.process_transaction(tx).await.unwrap();
 
+    let transfer_account = get_transfer_account(&reward_manager, transfer_id, TransferIdSeed::Raw);
+    let verified_messages_account =
+        get_messages_account(&reward_manager, transfer_id, TransferIdSeed::Raw);
 
-    let transfer_account = get_transfer_account(&reward_manager, transfer_id);
-    let verified_messages_account = get_messages_account(&reward_manager, transfer_id);
+    // Token account of the same mint, owned by someone other than the recipient
+    let attacker_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &attacker_token_account,
+        &mint.pubkey(),
+        &Keypair::new().pubkey(),
+        &rent,
+    )
+    .await
+    .unwrap();
 
-    let recipient_sol_key = claimable_tokens::utils::program::get_address_pair(
+    // User bank of a different eth address
+    let other_eth_key = [8u8; 20];
+    let other_bank = claimable_tokens::utils::program::find_address_pair(
         &claimable_tokens::id(),
         &mint.pubkey(),
-        recipient_eth_key,
+        other_eth_key,
     )
     .unwrap();
-    println!("Creating...Recipient sol key = {:?}", &recipient_sol_key.derive.address);
-    create_recipient_with_claimable_program(&mut context, &mint.pubkey(), recipient_eth_key).await;
-    println!("Created recipient sol key = {:?}", &recipient_sol_key.derive.address);
+    create_recipient_with_claimable_program(&mut context, &mint.pubkey(), other_eth_key).await;
+
+    for recipient in [attacker_token_account.pubkey(), other_bank.derive.address].iter() {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::evaluate_attestations(
+                &audius_reward_manager::id(),
+                &verified_messages_account,
+                &reward_manager.pubkey(),
+                &token_account.pubkey(),
+                recipient,
+                &oracle_derived_address,
+                &context.payer.pubkey(),
+                tokens_amount,
+                transfer_id.to_string(),
+                recipient_eth_key,
+            )
+            .unwrap()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let res = context.banks_client.process_transaction(tx).await;
+        assert_custom_error(res, 0, AudiusProgramError::WrongRecipientKey);
+    }
 
+    // Verified messages can't be evaluated for another transfer id
+    let recipient_sol_key = claimable_tokens::utils::program::find_address_pair(
+        &claimable_tokens::id(),
+        &mint.pubkey(),
+        recipient_eth_key,
+    )
+    .unwrap();
+    let evaluate_other_id = instruction::evaluate_attestations(
+        &audius_reward_manager::id(),
+        &verified_messages_account,
+        &reward_manager.pubkey(),
+        &token_account.pubkey(),
+        &recipient_sol_key.derive.address,
+        &oracle_derived_address,
+        &context.payer.pubkey(),
+        tokens_amount,
+        String::from("4r4t23df32543f58"),
+        recipient_eth_key,
+    )
+    .unwrap();
     let tx = Transaction::new_signed_with_payer(
-        &[instruction::evaluate_attestations(
-            &audius_reward_manager::id(),
-            &verified_messages_account,
-            &reward_manager.pubkey(),
-            &token_account.pubkey(),
-            &recipient_sol_key.derive.address,
-            &oracle_derived_address,
-            &context.payer.pubkey(),
-            10_000u64,
-            transfer_id.to_string(),
-            recipient_eth_key,
-        )
-        .unwrap()],
+        &[evaluate_other_id],
         Some(&context.payer.pubkey()),
         &[&context.payer],
         context.last_blockhash,
     );
-
-    context.banks_client.process_transaction(tx).await.unwrap();
-
-    let transfer_account_data = get_account(&mut context, &transfer_account)
-        .await
-        .unwrap();
-
     assert_eq!(
-        transfer_account_data.lamports,
-        rent.minimum_balance(TRANSFER_ACC_SPACE)
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
     );
-    assert_eq!(transfer_account_data.data.len(), TRANSFER_ACC_SPACE);
-    let recipient_account_data = get_account(& mut context, &recipient_sol_key.derive.address).await.unwrap();
-    let recipient_account = spl_token::state::Account::unpack(&recipient_account_data.data.as_slice()).unwrap();
-    assert_eq!(recipient_account.amount, 10_000u64)
+
+    // Ensure nothing was paid out and the transfer wasn't recorded
+    assert!(get_account(&mut context, &transfer_account).await.is_none());
+    let token_account_data = get_account(&mut context, &token_account.pubkey()).await.unwrap();
+    let token_account_state = spl_token::state::Account::unpack(&token_account_data.data.as_slice()).unwrap();
+    assert_eq!(token_account_state.amount, tokens_amount);
 }
 
 #[tokio::test]
@@ -296,25 +644,31 @@ async fn invalid_messages_are_wiped() {
     }
 
     
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
-
-    let bot_oracle_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-    ]
-    .concat());
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
         
     let mut instructions = Vec::<Instruction>::new();
 
@@ -348,10 +702,11 @@ async fn invalid_messages_are_wiped() {
 
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    let transfer_account = get_transfer_account(&reward_manager, transfer_id);
-    let verified_messages_account = get_messages_account(&reward_manager, transfer_id);
+    let transfer_account = get_transfer_account(&reward_manager, transfer_id, TransferIdSeed::Raw);
+    let verified_messages_account =
+        get_messages_account(&reward_manager, transfer_id, TransferIdSeed::Raw);
 
-    let recipient_sol_key = claimable_tokens::utils::program::get_address_pair(
+    let recipient_sol_key = claimable_tokens::utils::program::find_address_pair(
         &claimable_tokens::id(),
         &mint.pubkey(),
         recipient_eth_key,
@@ -550,26 +905,32 @@ async fn failure_transfer_invalid_message_format() {
     .await
     .unwrap();
 
-    let bot_oracle_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-    ]
-    .concat());
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
 
     // Use invalid message format
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b":",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b":",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
 
     // Generate data and create senders
     let keys: [[u8; 32]; 3] = rng.gen();
@@ -660,15 +1021,10 @@ async fn failure_transfer_invalid_message_format() {
     let (_, verified_messages_derived_address, _) = find_derived_pair(
         &audius_reward_manager::id(),
         &reward_manager.pubkey(),
-        [
-            VERIFY_TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            transfer_id.as_ref(),
-        ]
-        .concat()
-        .as_ref(),
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, transfer_id).unwrap(),
     );
 
-    let recipient_sol_key = claimable_tokens::utils::program::get_address_pair(
+    let recipient_sol_key = claimable_tokens::utils::program::find_address_pair(
         &claimable_tokens::id(),
         &mint.pubkey(),
         recipient_eth_key,
@@ -782,25 +1138,31 @@ async fn failure_transfer_invalid_oracle_message_format() {
     .await
     .unwrap();
 
-    let bot_oracle_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"|",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-    ]
-    .concat());
-
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"|",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
 
     // Generate data and create senders
     let keys: [[u8; 32]; 3] = rng.gen();
@@ -891,15 +1253,10 @@ async fn failure_transfer_invalid_oracle_message_format() {
     let (_, verified_messages_derived_address, _) = find_derived_pair(
         &audius_reward_manager::id(),
         &reward_manager.pubkey(),
-        [
-            VERIFY_TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            transfer_id.as_ref(),
-        ]
-        .concat()
-        .as_ref(),
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, transfer_id).unwrap(),
     );
 
-    let recipient_sol_key = claimable_tokens::utils::program::get_address_pair(
+    let recipient_sol_key = claimable_tokens::utils::program::find_address_pair(
         &claimable_tokens::id(),
         &mint.pubkey(),
         recipient_eth_key,
@@ -1013,25 +1370,31 @@ async fn failure_transfer_incorrect_number_of_verified_messages() {
     .await
     .unwrap();
 
-    let bot_oracle_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-    ]
-    .concat());
-
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
+    let bot_oracle_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
 
     // Generate data and create senders
     let keys: [[u8; 32]; 3] = rng.gen();
@@ -1123,30 +1486,20 @@ async fn failure_transfer_incorrect_number_of_verified_messages() {
 
 // Helpers
 
-fn get_transfer_account(reward_manager: &Keypair, transfer_id: &str) -> Pubkey {
+fn get_transfer_account(reward_manager: &Keypair, transfer_id: &str, seed: TransferIdSeed) -> Pubkey {
     let (_, transfer_derived_address, _) = find_derived_pair(
         &audius_reward_manager::id(),
         &reward_manager.pubkey(),
-        [
-            TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            transfer_id.as_ref(),
-        ]
-        .concat()
-        .as_ref(),
+        &seed.seed(TRANSFER_SEED_PREFIX, transfer_id).unwrap(),
     );
     transfer_derived_address
 }
 
-fn get_messages_account(reward_manager: &Keypair, transfer_id: &str) -> Pubkey {
+fn get_messages_account(reward_manager: &Keypair, transfer_id: &str, seed: TransferIdSeed) -> Pubkey {
     let (_, verified_messages_derived_address, _) = find_derived_pair(
         &audius_reward_manager::id(),
         &reward_manager.pubkey(),
-        [
-            VERIFY_TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            transfer_id.as_ref(),
-        ]
-        .concat()
-        .as_ref(),
+        &seed.seed(VERIFY_TRANSFER_SEED_PREFIX, transfer_id).unwrap(),
     );
     verified_messages_derived_address
 }
@@ -0,0 +1,191 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    error::AudiusProgramError, instruction, state::RewardManager, PROGRAM_VERSION,
+};
+use solana_program::{
+    clock::Slot, instruction::InstructionError, program_pack::Pack, pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+/// Initializes a reward manager and returns it with its manager and token account
+async fn setup() -> (ProgramTestContext, Keypair, Keypair, Keypair) {
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        3,
+    )
+    .await;
+
+    (context, reward_manager, manager_account, token_account)
+}
+
+async fn update_disbursement_limits(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    manager_account: &Keypair,
+) -> Result<(), TransportError> {
+    update_disbursement_limits_to(context, reward_manager, manager_account, 10_000, 50_000, 1_000)
+        .await
+}
+
+async fn update_disbursement_limits_to(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    manager_account: &Keypair,
+    max_transfer_amount: u64,
+    max_window_amount: u64,
+    window_slots: Slot,
+) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_disbursement_limits(
+            &audius_reward_manager::id(),
+            reward_manager,
+            &manager_account.pubkey(),
+            max_transfer_amount,
+            max_window_amount,
+            window_slots,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_update_disbursement_limits() {
+    let (mut context, reward_manager, manager_account, token_account) = setup().await;
+
+    update_disbursement_limits(&mut context, &reward_manager.pubkey(), &manager_account)
+        .await
+        .unwrap();
+
+    let mut expected = RewardManager::new(token_account.pubkey(), manager_account.pubkey(), 3);
+    expected.max_transfer_amount = 10_000;
+    expected.max_window_amount = 50_000;
+    expected.window_slots = 1_000;
+    assert_eq!(
+        expected,
+        context
+            .banks_client
+            .get_account_data_with_borsh(reward_manager.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn success_update_disbursement_limits_unlimited_window() {
+    let (mut context, reward_manager, manager_account, _) = setup().await;
+
+    // Only single transfers are limited
+    update_disbursement_limits_to(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        10_000,
+        u64::MAX,
+        0,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn failure_update_disbursement_limits_invalid() {
+    let (mut context, reward_manager, manager_account, _) = setup().await;
+
+    // A window limit without a window, and a transfer limit above the window limit
+    for (max_transfer_amount, max_window_amount, window_slots) in
+        [(10_000, 50_000, 0), (50_001, 50_000, 1_000)].iter()
+    {
+        let res = update_disbursement_limits_to(
+            &mut context,
+            &reward_manager.pubkey(),
+            &manager_account,
+            *max_transfer_amount,
+            *max_window_amount,
+            *window_slots,
+        )
+        .await;
+        assert_custom_error(res, 0, AudiusProgramError::InvalidDisbursementLimits);
+    }
+}
+
+#[tokio::test]
+async fn failure_update_disbursement_limits_bad_manager() {
+    let (mut context, reward_manager, _, _) = setup().await;
+
+    let res = update_disbursement_limits(&mut context, &reward_manager.pubkey(), &Keypair::new())
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_update_disbursement_limits_legacy() {
+    let mut program_test = program_test();
+
+    // Legacy reward managers have no room for the limits
+    let reward_manager = Pubkey::new_unique();
+    let manager_account = Keypair::new();
+    let mut legacy = RewardManager::new(Pubkey::new_unique(), manager_account.pubkey(), 3);
+    legacy.version = PROGRAM_VERSION;
+    let mut data = vec![0; 66];
+    RewardManager::pack(legacy, &mut data).unwrap();
+    program_test.add_account(
+        reward_manager,
+        Account {
+            lamports: 9000,
+            data,
+            owner: audius_reward_manager::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+
+    let res = update_disbursement_limits(&mut context, &reward_manager, &manager_account)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
@@ -0,0 +1,379 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    error::AudiusProgramError,
+    instruction,
+    processor::SENDER_SEED_PREFIX,
+    state::{RewardManager, UPDATE_CONFIG_MESSAGE_PREFIX},
+    utils::{find_derived_pair, EthereumAddress},
+};
+use libsecp256k1::{PublicKey, SecretKey};
+use rand::{thread_rng, Rng};
+use solana_program::{
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    secp256k1_instruction::construct_eth_pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use utils::*;
+
+/// Initializes reward manager with `min_votes` and a sender for every key
+async fn setup(
+    keys: &[[u8; 32]],
+    min_votes: u8,
+) -> (ProgramTestContext, Keypair, Keypair, Keypair, Vec<Pubkey>) {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        min_votes,
+    )
+    .await;
+
+    let mut signers = Vec::new();
+    for key in keys {
+        let sender_priv_key = SecretKey::parse(key).unwrap();
+        let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(&sender_priv_key));
+        let operator: EthereumAddress = rng.gen();
+        create_sender(
+            &mut context,
+            &reward_manager.pubkey(),
+            &manager_account,
+            eth_address,
+            operator,
+        )
+        .await;
+
+        let (_, derived_address, _) = find_derived_pair(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+                .concat()
+                .as_ref(),
+        );
+        signers.push(derived_address);
+    }
+
+    (
+        context,
+        reward_manager,
+        manager_account,
+        token_account,
+        signers,
+    )
+}
+
+/// Signs the config update for `config_nonce` with `keys` and submits it with `signers`
+async fn update_config_public(
+    context: &mut ProgramTestContext,
+    reward_manager: &Pubkey,
+    keys: &[[u8; 32]],
+    signers: &[Pubkey],
+    config_nonce: u64,
+    min_votes: u8,
+) -> Result<(), TransportError> {
+    let message = [
+        UPDATE_CONFIG_MESSAGE_PREFIX.as_ref(),
+        reward_manager.as_ref(),
+        config_nonce.to_le_bytes().as_ref(),
+        &[min_votes],
+    ]
+    .concat();
+
+    let mut instructions = Vec::<Instruction>::new();
+    for (i, key) in keys.iter().enumerate() {
+        let priv_key = SecretKey::parse(key).unwrap();
+        instructions.push(new_secp256k1_instruction_2_0(
+            &priv_key,
+            message.as_ref(),
+            i as _,
+        ));
+    }
+    instructions.push(
+        instruction::update_reward_manager_config_public(
+            &audius_reward_manager::id(),
+            reward_manager,
+            min_votes,
+            signers,
+        )
+        .unwrap(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_update_reward_manager_config() {
+    let (mut context, reward_manager, manager_account, token_account, _) = setup(&[], 3).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_reward_manager_config(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            4,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mut expected = RewardManager::new(token_account.pubkey(), manager_account.pubkey(), 4);
+    expected.config_nonce = 1;
+    assert_eq!(
+        expected,
+        context
+            .banks_client
+            .get_account_data_with_borsh(reward_manager.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_bad_manager() {
+    let (mut context, reward_manager, _, _, _) = setup(&[], 3).await;
+    let bad_manager = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_reward_manager_config(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &bad_manager.pubkey(),
+            1,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &bad_manager],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_invalid_min_votes() {
+    let (mut context, reward_manager, manager_account, _, _) = setup(&[], 3).await;
+
+    // Min votes plus the bot oracle overflow the verified messages capacity
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_reward_manager_config(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            u8::MAX,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 0, AudiusProgramError::InvalidMinVotes);
+
+    // Without votes any empty signer list would pass the quorum checks
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_reward_manager_config(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            0,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 0, AudiusProgramError::InvalidMinVotes);
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_public_zero_min_votes() {
+    let mut rng = thread_rng();
+    let keys: [[u8; 32]; 3] = rng.gen();
+    let (mut context, reward_manager, _, _, signers) = setup(&keys, 3).await;
+
+    let res = update_config_public(&mut context, &reward_manager.pubkey(), &keys, &signers, 0, 0)
+        .await;
+    assert_custom_error(res, 3, AudiusProgramError::InvalidMinVotes);
+}
+
+#[tokio::test]
+async fn success_update_reward_manager_config_public() {
+    let mut rng = thread_rng();
+    let keys: [[u8; 32]; 3] = rng.gen();
+    let (mut context, reward_manager, manager_account, token_account, signers) =
+        setup(&keys, 3).await;
+
+    update_config_public(&mut context, &reward_manager.pubkey(), &keys, &signers, 0, 2)
+        .await
+        .unwrap();
+
+    let mut expected = RewardManager::new(token_account.pubkey(), manager_account.pubkey(), 2);
+    expected.config_nonce = 1;
+    assert_eq!(
+        expected,
+        context
+            .banks_client
+            .get_account_data_with_borsh(reward_manager.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_public_not_enough_signers() {
+    let mut rng = thread_rng();
+    let keys: [[u8; 32]; 3] = rng.gen();
+    let (mut context, reward_manager, _, _, signers) = setup(&keys, 3).await;
+
+    let res = update_config_public(
+        &mut context,
+        &reward_manager.pubkey(),
+        &keys[..2],
+        &signers[..2],
+        0,
+        1,
+    )
+    .await;
+    assert_custom_error(res, 2, AudiusProgramError::NotEnoughSigners);
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_public_replayed() {
+    let mut rng = thread_rng();
+    let keys: [[u8; 32]; 3] = rng.gen();
+    let (mut context, reward_manager, manager_account, _, signers) = setup(&keys, 3).await;
+
+    update_config_public(&mut context, &reward_manager.pubkey(), &keys, &signers, 0, 2)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_reward_manager_config(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            1,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The first update's signatures no longer match the config nonce
+    let res = update_config_public(
+        &mut context,
+        &reward_manager.pubkey(),
+        &keys[..2],
+        &signers[..2],
+        0,
+        2,
+    )
+    .await;
+    assert_custom_error(res, 2, AudiusProgramError::SignatureVerificationFailed);
+
+    update_config_public(
+        &mut context,
+        &reward_manager.pubkey(),
+        &keys[..2],
+        &signers[..2],
+        2,
+        2,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn failure_update_reward_manager_config_public_wrong_message() {
+    let mut rng = thread_rng();
+    let keys: [[u8; 32]; 3] = rng.gen();
+    let (mut context, reward_manager, _, _, signers) = setup(&keys, 3).await;
+
+    // Signed for another reward manager
+    let message = [
+        UPDATE_CONFIG_MESSAGE_PREFIX.as_ref(),
+        Pubkey::new_unique().as_ref(),
+        0u64.to_le_bytes().as_ref(),
+        &[1],
+    ]
+    .concat();
+
+    let mut instructions = Vec::<Instruction>::new();
+    for (i, key) in keys.iter().enumerate() {
+        let priv_key = SecretKey::parse(key).unwrap();
+        instructions.push(new_secp256k1_instruction_2_0(
+            &priv_key,
+            message.as_ref(),
+            i as _,
+        ));
+    }
+    instructions.push(
+        instruction::update_reward_manager_config_public(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            1,
+            &signers,
+        )
+        .unwrap(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 3, AudiusProgramError::SignatureVerificationFailed);
+}
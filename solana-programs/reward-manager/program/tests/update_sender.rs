@@ -0,0 +1,479 @@
+#![cfg(feature = "test-bpf")]
+mod utils;
+use audius_reward_manager::{
+    instruction,
+    processor::SENDER_SEED_PREFIX,
+    state::{SenderAccount, UPDATE_SENDER_MESSAGE_PREFIX},
+    utils::{find_derived_pair, EthereumAddress},
+};
+use libsecp256k1::{PublicKey, SecretKey};
+use rand::{thread_rng, Rng};
+use solana_program::program_pack::Pack;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    secp256k1_instruction::construct_eth_pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+use utils::*;
+
+fn sender_address(reward_manager: &Pubkey, eth_address: EthereumAddress) -> Pubkey {
+    let (_, derived_address, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    derived_address
+}
+
+async fn setup(context: &mut ProgramTestContext, reward_manager: &Keypair, manager: &Pubkey) {
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let token_account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    create_mint(
+        context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        context,
+        reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        manager,
+        3,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn success_update_sender_operator() {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    setup(&mut context, &reward_manager, &manager_account.pubkey()).await;
+
+    let eth_address: EthereumAddress = rng.gen();
+    let operator: EthereumAddress = rng.gen();
+    let new_operator: EthereumAddress = rng.gen();
+    create_sender(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        eth_address,
+        operator,
+    )
+    .await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_sender(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            &context.payer.pubkey(),
+            eth_address,
+            eth_address,
+            new_operator,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let sender = sender_address(&reward_manager.pubkey(), eth_address);
+    let sender_data = get_account(&mut context, &sender).await.unwrap();
+    assert_eq!(
+        SenderAccount::unpack(&sender_data.data).unwrap(),
+        SenderAccount::new(reward_manager.pubkey(), eth_address, new_operator)
+    );
+}
+
+#[tokio::test]
+async fn success_update_sender_eth_address() {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    setup(&mut context, &reward_manager, &manager_account.pubkey()).await;
+
+    let eth_address: EthereumAddress = rng.gen();
+    let operator: EthereumAddress = rng.gen();
+    let new_eth_address: EthereumAddress = rng.gen();
+    let new_operator: EthereumAddress = rng.gen();
+    create_sender(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        eth_address,
+        operator,
+    )
+    .await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_sender(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            &context.payer.pubkey(),
+            eth_address,
+            new_eth_address,
+            new_operator,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let old_sender = sender_address(&reward_manager.pubkey(), eth_address);
+    assert!(get_account(&mut context, &old_sender).await.is_none());
+
+    let new_sender = sender_address(&reward_manager.pubkey(), new_eth_address);
+    let sender_data = get_account(&mut context, &new_sender).await.unwrap();
+    assert_eq!(sender_data.owner, audius_reward_manager::id());
+    assert_eq!(
+        SenderAccount::unpack(&sender_data.data).unwrap(),
+        SenderAccount::new(reward_manager.pubkey(), new_eth_address, new_operator)
+    );
+}
+
+#[tokio::test]
+async fn failure_update_sender_to_existing_sender() {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    setup(&mut context, &reward_manager, &manager_account.pubkey()).await;
+
+    let eth_address: EthereumAddress = rng.gen();
+    let other_eth_address: EthereumAddress = rng.gen();
+    for address in [eth_address, other_eth_address] {
+        let operator: EthereumAddress = rng.gen();
+        create_sender(
+            &mut context,
+            &reward_manager.pubkey(),
+            &manager_account,
+            address,
+            operator,
+        )
+        .await;
+    }
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_sender(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            &context.payer.pubkey(),
+            eth_address,
+            other_eth_address,
+            rng.gen(),
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+    assert!(context.banks_client.process_transaction(tx).await.is_err());
+
+    // Both senders are left as they were
+    let sender = sender_address(&reward_manager.pubkey(), eth_address);
+    assert!(get_account(&mut context, &sender).await.is_some());
+    let other_sender = sender_address(&reward_manager.pubkey(), other_eth_address);
+    let sender_data = get_account(&mut context, &other_sender).await.unwrap();
+    assert_eq!(
+        SenderAccount::unpack(&sender_data.data).unwrap().eth_address,
+        other_eth_address
+    );
+}
+
+#[tokio::test]
+async fn success_update_sender_public() {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    setup(&mut context, &reward_manager, &manager_account.pubkey()).await;
+
+    let keys: [[u8; 32]; 4] = rng.gen();
+    let mut eth_addresses = Vec::new();
+    let mut operators = Vec::new();
+    for key in &keys {
+        let sender_priv_key = SecretKey::parse(key).unwrap();
+        let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(&sender_priv_key));
+        let operator: EthereumAddress = rng.gen();
+        create_sender(
+            &mut context,
+            &reward_manager.pubkey(),
+            &manager_account,
+            eth_address,
+            operator,
+        )
+        .await;
+        eth_addresses.push(eth_address);
+        operators.push(operator);
+    }
+    let signers: Vec<Pubkey> = eth_addresses[..3]
+        .iter()
+        .map(|eth_address| sender_address(&reward_manager.pubkey(), *eth_address))
+        .collect();
+
+    // Rotate the key of the last sender
+    let eth_address = eth_addresses[3];
+    let new_eth_address: EthereumAddress = rng.gen();
+    let new_operator: EthereumAddress = rng.gen();
+
+    let message = [
+        UPDATE_SENDER_MESSAGE_PREFIX.as_ref(),
+        reward_manager.pubkey().as_ref(),
+        eth_address.as_ref(),
+        operators[3].as_ref(),
+        new_eth_address.as_ref(),
+        new_operator.as_ref(),
+    ]
+    .concat();
+    let mut instructions = Vec::<Instruction>::new();
+    for item in keys[..3].iter().enumerate() {
+        let priv_key = SecretKey::parse(item.1).unwrap();
+        let inst = new_secp256k1_instruction_2_0(&priv_key, message.as_ref(), item.0 as _);
+        instructions.push(inst);
+    }
+    instructions.push(
+        instruction::update_sender_public(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &context.payer.pubkey(),
+            eth_address,
+            new_eth_address,
+            new_operator,
+            &signers,
+        )
+        .unwrap(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let old_sender = sender_address(&reward_manager.pubkey(), eth_address);
+    assert!(get_account(&mut context, &old_sender).await.is_none());
+
+    let new_sender = sender_address(&reward_manager.pubkey(), new_eth_address);
+    let sender_data = get_account(&mut context, &new_sender).await.unwrap();
+    assert_eq!(
+        SenderAccount::unpack(&sender_data.data).unwrap(),
+        SenderAccount::new(reward_manager.pubkey(), new_eth_address, new_operator)
+    );
+}
+
+#[tokio::test]
+async fn failure_update_sender_public_wrong_message() {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    setup(&mut context, &reward_manager, &manager_account.pubkey()).await;
+
+    let keys: [[u8; 32]; 4] = rng.gen();
+    let mut eth_addresses = Vec::new();
+    let mut operators = Vec::new();
+    for key in &keys {
+        let sender_priv_key = SecretKey::parse(key).unwrap();
+        let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(&sender_priv_key));
+        let operator: EthereumAddress = rng.gen();
+        create_sender(
+            &mut context,
+            &reward_manager.pubkey(),
+            &manager_account,
+            eth_address,
+            operator,
+        )
+        .await;
+        eth_addresses.push(eth_address);
+        operators.push(operator);
+    }
+    let signers: Vec<Pubkey> = eth_addresses[..3]
+        .iter()
+        .map(|eth_address| sender_address(&reward_manager.pubkey(), *eth_address))
+        .collect();
+
+    let eth_address = eth_addresses[3];
+    let new_eth_address: EthereumAddress = rng.gen();
+    let new_operator: EthereumAddress = rng.gen();
+
+    // Senders attested a different operator
+    let other_operator: EthereumAddress = rng.gen();
+    let message = [
+        UPDATE_SENDER_MESSAGE_PREFIX.as_ref(),
+        reward_manager.pubkey().as_ref(),
+        eth_address.as_ref(),
+        operators[3].as_ref(),
+        new_eth_address.as_ref(),
+        other_operator.as_ref(),
+    ]
+    .concat();
+    let mut instructions = Vec::<Instruction>::new();
+    for item in keys[..3].iter().enumerate() {
+        let priv_key = SecretKey::parse(item.1).unwrap();
+        let inst = new_secp256k1_instruction_2_0(&priv_key, message.as_ref(), item.0 as _);
+        instructions.push(inst);
+    }
+    instructions.push(
+        instruction::update_sender_public(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &context.payer.pubkey(),
+            eth_address,
+            new_eth_address,
+            new_operator,
+            &signers,
+        )
+        .unwrap(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let tx_result = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(
+        tx_result,
+        3,
+        audius_reward_manager::error::AudiusProgramError::SignatureVerificationFailed,
+    );
+
+    let new_sender = sender_address(&reward_manager.pubkey(), new_eth_address);
+    assert!(get_account(&mut context, &new_sender).await.is_none());
+}
+
+#[tokio::test]
+async fn failure_update_sender_public_replayed() {
+    let mut rng = thread_rng();
+    let mut context = program_test().start_with_context().await;
+
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+    setup(&mut context, &reward_manager, &manager_account.pubkey()).await;
+
+    let keys: [[u8; 32]; 4] = rng.gen();
+    let mut eth_addresses = Vec::new();
+    for key in &keys {
+        let sender_priv_key = SecretKey::parse(key).unwrap();
+        let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(&sender_priv_key));
+        create_sender(
+            &mut context,
+            &reward_manager.pubkey(),
+            &manager_account,
+            eth_address,
+            rng.gen(),
+        )
+        .await;
+        eth_addresses.push(eth_address);
+    }
+    let signers: Vec<Pubkey> = eth_addresses
+        .iter()
+        .map(|eth_address| sender_address(&reward_manager.pubkey(), *eth_address))
+        .collect();
+
+    // The manager sets a known operator on the last sender
+    let eth_address = eth_addresses[3];
+    let operator: EthereumAddress = rng.gen();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_sender(
+            &audius_reward_manager::id(),
+            &reward_manager.pubkey(),
+            &manager_account.pubkey(),
+            &context.payer.pubkey(),
+            eth_address,
+            eth_address,
+            operator,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &manager_account],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Senders move the operator away
+    let new_operator: EthereumAddress = rng.gen();
+    let message = [
+        UPDATE_SENDER_MESSAGE_PREFIX.as_ref(),
+        reward_manager.pubkey().as_ref(),
+        eth_address.as_ref(),
+        operator.as_ref(),
+        eth_address.as_ref(),
+        new_operator.as_ref(),
+    ]
+    .concat();
+
+    let signed_update = |count: usize| {
+        let mut instructions = Vec::<Instruction>::new();
+        for (i, key) in keys[..count].iter().enumerate() {
+            let priv_key = SecretKey::parse(key).unwrap();
+            instructions.push(new_secp256k1_instruction_2_0(
+                &priv_key,
+                message.as_ref(),
+                i as _,
+            ));
+        }
+        instructions.push(
+            instruction::update_sender_public(
+                &audius_reward_manager::id(),
+                &reward_manager.pubkey(),
+                &context.payer.pubkey(),
+                eth_address,
+                eth_address,
+                new_operator,
+                &signers[..count],
+            )
+            .unwrap(),
+        );
+        Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        )
+    };
+    let tx = signed_update(3);
+    let replay = signed_update(4);
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The signatures were for the previous operator
+    let tx_result = context.banks_client.process_transaction(replay).await;
+    assert_custom_error(
+        tx_result,
+        4,
+        audius_reward_manager::error::AudiusProgramError::SignatureVerificationFailed,
+    );
+
+    let sender_data = get_account(&mut context, &signers[3]).await.unwrap();
+    assert_eq!(
+        SenderAccount::unpack(&sender_data.data).unwrap().operator,
+        new_operator
+    );
+}
@@ -1,15 +1,16 @@
 #![cfg(feature = "test-bpf")]
 #![allow(dead_code)]
 use audius_reward_manager::instruction;
-use audius_reward_manager::utils::EthereumAddress;
+use audius_reward_manager::state::{VoteMessage, TRANSFER_MESSAGE_PREFIX};
+use audius_reward_manager::utils::{transfer_attestation_domain, EthereumAddress};
+use audius_reward_manager::vote_message;
 use audius_reward_manager::{id, processor::Processor};
-use sha3::Digest;
-use solana_program::{
-    instruction::Instruction, program_pack::Pack, pubkey::Pubkey, rent::Rent, system_instruction,
+pub use audius_secp_utils::client::{
+    new_secp256k1_instruction_2_0, new_secp256k1_instruction_multi,
 };
+use solana_program::{program_pack::Pack, pubkey::Pubkey, rent::Rent, system_instruction};
 use solana_program_test::*;
 use solana_program_test::{processor, ProgramTest};
-use solana_sdk::secp256k1_instruction::*;
 use solana_sdk::transaction::TransactionError;
 use solana_sdk::{
     account::Account,
@@ -28,6 +29,25 @@ pub fn program_test() -> ProgramTest {
     )
 }
 
+/// Transfer attestation `message` as signed for `reward_manager`
+pub fn transfer_attestation(reward_manager: &Pubkey, message: &[u8]) -> Vec<u8> {
+    [
+        transfer_attestation_domain(&id(), reward_manager).as_ref(),
+        message,
+    ]
+    .concat()
+}
+
+/// Signed transfer `attestation` as stored in `VerifiedMessages`
+pub fn verified_attestation(reward_manager: &Pubkey, attestation: &[u8]) -> VoteMessage {
+    let domain = transfer_attestation_domain(&id(), reward_manager);
+    vote_message!([
+        TRANSFER_MESSAGE_PREFIX.as_bytes(),
+        &attestation[domain.len()..]
+    ]
+    .concat())
+}
+
 pub async fn get_account(
     program_context: &mut ProgramTestContext,
     pubkey: &Pubkey,
@@ -39,69 +59,6 @@ pub async fn get_account(
         .expect("account not found")
 }
 
-pub fn new_secp256k1_instruction_2_0(
-    priv_key: &libsecp256k1::SecretKey,
-    message_arr: &[u8],
-    instruction_index: u8,
-) -> Instruction {
-    let secp_pubkey = libsecp256k1::PublicKey::from_secret_key(priv_key);
-    let eth_pubkey = construct_eth_pubkey(&secp_pubkey);
-    let mut hasher = sha3::Keccak256::new();
-    hasher.update(&message_arr);
-    let message_hash = hasher.finalize();
-    let mut message_hash_arr = [0u8; 32];
-    message_hash_arr.copy_from_slice(&message_hash.as_slice());
-    let message = libsecp256k1::Message::parse(&message_hash_arr);
-    let (signature, recovery_id) = libsecp256k1::sign(&message, priv_key);
-    let signature_arr = signature.serialize();
-    assert_eq!(signature_arr.len(), SIGNATURE_SERIALIZED_SIZE);
-
-    let mut instruction_data = vec![];
-    instruction_data.resize(
-        DATA_START
-            .saturating_add(eth_pubkey.len())
-            .saturating_add(signature_arr.len())
-            .saturating_add(message_arr.len())
-            .saturating_add(1),
-        0,
-    );
-    let eth_address_offset = DATA_START;
-    instruction_data[eth_address_offset..eth_address_offset.saturating_add(eth_pubkey.len())]
-        .copy_from_slice(&eth_pubkey);
-
-    let signature_offset = DATA_START.saturating_add(eth_pubkey.len());
-    instruction_data[signature_offset..signature_offset.saturating_add(signature_arr.len())]
-        .copy_from_slice(&signature_arr);
-
-    instruction_data[signature_offset.saturating_add(signature_arr.len())] =
-        recovery_id.serialize();
-
-    let message_data_offset = signature_offset
-        .saturating_add(signature_arr.len())
-        .saturating_add(1);
-    instruction_data[message_data_offset..].copy_from_slice(message_arr);
-
-    let num_signatures = 1;
-    instruction_data[0] = num_signatures;
-    let offsets = SecpSignatureOffsets {
-        signature_offset: signature_offset as u16,
-        signature_instruction_index: instruction_index,
-        eth_address_offset: eth_address_offset as u16,
-        eth_address_instruction_index: instruction_index,
-        message_data_offset: message_data_offset as u16,
-        message_data_size: message_arr.len() as u16,
-        message_instruction_index: instruction_index,
-    };
-    let writer = std::io::Cursor::new(&mut instruction_data[1..DATA_START]);
-    bincode::serialize_into(writer, &offsets).unwrap();
-
-    Instruction {
-        program_id: solana_sdk::secp256k1_program::id(),
-        accounts: vec![],
-        data: instruction_data,
-    }
-}
-
 pub async fn create_sender(
     context: &mut ProgramTestContext,
     reward_manager: &Pubkey,
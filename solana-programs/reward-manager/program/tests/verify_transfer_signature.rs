@@ -2,19 +2,20 @@
 mod utils;
 
 use audius_reward_manager::{
+    error::AudiusProgramError,
     instruction,
     processor::SENDER_SEED_PREFIX,
     processor::VERIFY_TRANSFER_SEED_PREFIX,
-    state::VerifiedMessages,
-    utils::{find_derived_pair, EthereumAddress},
-    vote_message,
+    state::{RewardManager, SenderAccount, VerifiedMessages, VERIFIED_MESSAGES_VERSION},
+    utils::{find_derived_pair, transfer_id_seed, EthereumAddress},
 };
 use libsecp256k1::{PublicKey, SecretKey};
 use rand::{thread_rng, Rng};
-use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey, rent::Rent};
 use solana_program_test::*;
 use solana_sdk::{
-    secp256k1_instruction::*, signature::Keypair, signer::Signer, transaction::Transaction,
+    account::Account, secp256k1_instruction::*, signature::Keypair, signer::Signer,
+    transaction::Transaction,
 };
 use std::mem::MaybeUninit;
 use utils::*;
@@ -64,16 +65,19 @@ async fn success_verify_transfer_signature() {
     let recipient_eth_key = [7u8; 20];
     let transfer_id = "4r4t23df32543f55";
 
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
 
     // Generate data and create senders
     let keys: [[u8; 32]; 3] = rng.gen();
@@ -174,6 +178,230 @@ async fn success_verify_transfer_signature() {
     context.banks_client.process_transaction(tx2).await.unwrap();
 }
 
+#[tokio::test]
+async fn failure_verify_transfer_signature_other_reward_manager() {
+    let program_test = program_test();
+    let mut rng = thread_rng();
+
+    let mut context = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+
+    let token_account = Keypair::new();
+    let reward_manager = Keypair::new();
+    let manager_account = Keypair::new();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    create_mint(
+        &mut context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    init_reward_manager(
+        &mut context,
+        &reward_manager,
+        &token_account,
+        &mint.pubkey(),
+        &manager_account.pubkey(),
+        3,
+    )
+    .await;
+
+    let key: [u8; 32] = rng.gen();
+    let priv_key = SecretKey::parse(&key).unwrap();
+    let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(&priv_key));
+    create_sender(
+        &mut context,
+        &reward_manager.pubkey(),
+        &manager_account,
+        eth_address,
+        rng.gen(),
+    )
+    .await;
+
+    let (_, sender, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager.pubkey(),
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+
+    let recipient_eth_key = [7u8; 20];
+    let transfer_id = "4r4t23df32543f55";
+
+    // Valid attestation, but signed for another reward manager
+    let senders_message = transfer_attestation(
+        &Pubkey::new_unique(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            10_000u64.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            new_secp256k1_instruction_2_0(&priv_key, senders_message.as_ref(), 0),
+            instruction::submit_attestations(
+                &audius_reward_manager::id(),
+                &reward_manager.pubkey(),
+                &sender,
+                &context.payer.pubkey(),
+                transfer_id.to_string(),
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let res = context.banks_client.process_transaction(tx).await;
+    assert_custom_error(res, 1, AudiusProgramError::SignatureVerificationFailed);
+}
+
+#[tokio::test]
+/// Submits an attestation into a legacy verified messages account after
+/// `min_votes` was raised beyond what it can hold
+async fn success_grow_legacy_verified_messages() {
+    let mut program_test = program_test();
+    let mut rng = thread_rng();
+
+    let reward_manager = Pubkey::new_unique();
+    let min_votes = 5;
+    let mut reward_manager_data = vec![0; RewardManager::LEN];
+    RewardManager::pack(
+        RewardManager::new(Pubkey::new_unique(), Pubkey::new_unique(), min_votes),
+        &mut reward_manager_data,
+    )
+    .unwrap();
+    program_test.add_account(
+        reward_manager,
+        Account {
+            lamports: Rent::default().minimum_balance(RewardManager::LEN),
+            data: reward_manager_data,
+            owner: audius_reward_manager::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let key: [u8; 32] = rng.gen();
+    let priv_key = SecretKey::parse(&key).unwrap();
+    let eth_address = construct_eth_pubkey(&PublicKey::from_secret_key(&priv_key));
+    let (_, sender, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        [SENDER_SEED_PREFIX.as_ref(), eth_address.as_ref()]
+            .concat()
+            .as_ref(),
+    );
+    let mut sender_data = vec![0; SenderAccount::LEN];
+    SenderAccount::pack(
+        SenderAccount::new(reward_manager, eth_address, rng.gen()),
+        &mut sender_data,
+    )
+    .unwrap();
+    program_test.add_account(
+        sender,
+        Account {
+            lamports: Rent::default().minimum_balance(SenderAccount::LEN),
+            data: sender_data,
+            owner: audius_reward_manager::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    // Legacy account holding five messages, one already submitted
+    let recipient_eth_key = [7u8; 20];
+    let transfer_id = "4r4t23df32543f55";
+    let (_, verified_messages, _) = find_derived_pair(
+        &audius_reward_manager::id(),
+        &reward_manager,
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, transfer_id).unwrap(),
+    );
+    // 34 byte header and room for five messages
+    let legacy_len = 874;
+    let mut legacy_data = vec![0; legacy_len];
+    legacy_data[0] = 1;
+    legacy_data[1..33].copy_from_slice(reward_manager.as_ref());
+    legacy_data[33] = 1;
+    legacy_data[34..54].copy_from_slice(&[1; 20]);
+    legacy_data[182..202].copy_from_slice(&[1; 20]);
+    program_test.add_account(
+        verified_messages,
+        Account {
+            lamports: Rent::default().minimum_balance(legacy_len),
+            data: legacy_data,
+            owner: audius_reward_manager::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    let senders_message = transfer_attestation(
+        &reward_manager,
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            10_000u64.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            new_secp256k1_instruction_2_0(&priv_key, senders_message.as_ref(), 0),
+            instruction::submit_attestations(
+                &audius_reward_manager::id(),
+                &reward_manager,
+                &sender,
+                &context.payer.pubkey(),
+                transfer_id.to_string(),
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Grown to hold `min_votes` and the bot oracle, keeping the earlier message
+    let account = get_account(&mut context, &verified_messages).await.unwrap();
+    let capacity = min_votes + 1;
+    assert_eq!(account.data.len(), VerifiedMessages::size(capacity));
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(VerifiedMessages::size(capacity))
+    );
+    let verified_messages_data = VerifiedMessages::unpack(&account.data).unwrap();
+    assert_eq!(verified_messages_data.version, VERIFIED_MESSAGES_VERSION);
+    assert_eq!(verified_messages_data.funder, context.payer.pubkey());
+    assert_eq!(
+        verified_messages_data.seed,
+        transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, transfer_id).unwrap()
+    );
+    assert_eq!(verified_messages_data.capacity, capacity);
+    assert_eq!(verified_messages_data.messages.len(), 2);
+    assert_eq!(verified_messages_data.messages[0].address, [1; 20]);
+    assert_eq!(verified_messages_data.messages[1].address, eth_address);
+}
+
 #[tokio::test]
 async fn success_multiple_recovery_1_tx() {
     let program_test = program_test();
@@ -220,25 +448,31 @@ async fn success_multiple_recovery_1_tx() {
     let recipient_eth_key = [7u8; 20];
     let transfer_id = "4r4t23df32543f55";
 
-    let senders_message = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-        b"_",
-        eth_oracle_address.as_ref(),
-    ]
-    .concat());
-
-    let bot_oracle_msg = vote_message!([
-        recipient_eth_key.as_ref(),
-        b"_",
-        tokens_amount.to_le_bytes().as_ref(),
-        b"_",
-        transfer_id.as_ref(),
-    ]
-    .concat());
+    let senders_message = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+            b"_",
+            eth_oracle_address.as_ref(),
+        ]
+        .concat(),
+    );
+
+    let bot_oracle_msg = transfer_attestation(
+        &reward_manager.pubkey(),
+        &[
+            recipient_eth_key.as_ref(),
+            b"_",
+            tokens_amount.to_le_bytes().as_ref(),
+            b"_",
+            transfer_id.as_ref(),
+        ]
+        .concat(),
+    );
     // Add bot oracle as sender
     let (_, oracle_derived_address, _) = find_derived_pair(
         &audius_reward_manager::id(),
@@ -361,12 +595,7 @@ async fn success_multiple_recovery_1_tx() {
     let (_reward_manager_authority, verified_msgs_derived_acct, _) = find_derived_pair(
         &audius_reward_manager::id(),
         &reward_manager.pubkey(),
-        [
-            VERIFY_TRANSFER_SEED_PREFIX.as_bytes().as_ref(),
-            transfer_id.as_ref(),
-        ]
-        .concat()
-        .as_ref(),
+        &transfer_id_seed(VERIFY_TRANSFER_SEED_PREFIX, transfer_id).unwrap(),
     );
 
     let verified_msg_acct_data = get_account(&mut context, &verified_msgs_derived_acct)
@@ -393,10 +622,16 @@ async fn success_multiple_recovery_1_tx() {
             let eth_address = construct_eth_pubkey(&secp_pubkey);
             assert_eq!(x.address, eth_address);
             assert_eq!(x.operator, operators[i]);
-            assert_eq!(x.message, senders_message);
+            assert_eq!(
+                x.message,
+                verified_attestation(&reward_manager.pubkey(), &senders_message)
+            );
         }
         if i == 3 {
-            assert_eq!(x.message, bot_oracle_msg);
+            assert_eq!(
+                x.message,
+                verified_attestation(&reward_manager.pubkey(), &bot_oracle_msg)
+            );
             assert_eq!(x.address, eth_oracle_address);
             assert_eq!(x.operator, oracle_operator);
         }
@@ -6,7 +6,7 @@ use solana_program::{
     instruction::{AccountMeta, Instruction},
     program_error::ProgramError,
     pubkey::Pubkey,
-    sysvar,
+    system_program, sysvar,
 };
 
 /// Instruction arguments
@@ -33,6 +33,18 @@ pub enum TemplateInstruction {
     ///   4. [] Sysvar instruction account
     ///   5. [] Sysvar clock account
     TrackListenInstruction(InstructionArgs),
+    ///   TrackListen counted at most once, marking the signed listen as used
+    ///
+    ///   1. [] Valid signer account
+    ///   2. [] Signer group
+    ///   3. [] Audius program account
+    ///   4. [] Sysvar instruction account
+    ///   5. [] Sysvar clock account
+    ///   6. [ws] Funder of the used message account
+    ///   7. [w] Audius used message account
+    ///   8. [] Sysvar rent account
+    ///   9. [] System program account
+    TrackListenOnceInstruction(InstructionArgs),
 }
 
 /// Create `TrackListen` instruction
@@ -59,3 +71,44 @@ pub fn init(
         data,
     })
 }
+
+/// Create `TrackListenOnce` instruction for the valid signer with `eth_address`
+pub fn init_once(
+    program_id: &Pubkey,
+    valid_signer_account: &Pubkey,
+    signer_group: &Pubkey,
+    funder: &Pubkey,
+    eth_address: &[u8; audius_eth_registry::state::SecpSignatureOffsets::ETH_ADDRESS_SIZE],
+    track_data: InstructionArgs,
+) -> Result<Instruction, ProgramError> {
+    let message = track_data
+        .track_data
+        .try_to_vec()
+        .or(Err(ProgramError::InvalidArgument))?;
+    let (used_message, _) = audius_eth_registry::instruction::find_used_message_address(
+        &audius_eth_registry::id(),
+        signer_group,
+        eth_address,
+        &message,
+    );
+    let init_data = TemplateInstruction::TrackListenOnceInstruction(track_data);
+    let data = init_data
+        .try_to_vec()
+        .or(Err(ProgramError::InvalidArgument))?;
+    let accounts = vec![
+        AccountMeta::new_readonly(*valid_signer_account, false),
+        AccountMeta::new_readonly(*signer_group, false),
+        AccountMeta::new_readonly(audius_eth_registry::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(used_message, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
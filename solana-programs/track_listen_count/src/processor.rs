@@ -5,11 +5,13 @@ use crate::{
     instruction::{InstructionArgs, TemplateInstruction},
 };
 use solana_program::clock::UnixTimestamp;
-use audius_eth_registry::instruction::SignatureData;
+use audius_eth_registry::{instruction::SignatureData, state::ValidSigner};
+use audius_secp_utils::{assert_clock_sysvar, assert_instructions_sysvar};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::next_account_info, account_info::AccountInfo, entrypoint::ProgramResult, msg,
-    program::invoke, pubkey::Pubkey, sysvar::clock::Clock, sysvar::Sysvar,
+    program::invoke, program_error::ProgramError, pubkey::Pubkey, sysvar::clock::Clock,
+    sysvar::Sysvar,
 };
 
 // Maximum time between multiple signer submission for adding additional
@@ -20,6 +22,35 @@ const MAX_TIME_DIFF_SECONDS: UnixTimestamp = 600;
 /// Program state handler.
 pub struct Processor {}
 impl Processor {
+    /// Checks the program and sysvar accounts and the listen timestamp,
+    /// returning the signature data to validate
+    fn listen_signature_data(
+        audius_account_info: &AccountInfo,
+        sysvar_instruction: &AccountInfo,
+        clock_account_info: &AccountInfo,
+        instruction_data: InstructionArgs,
+    ) -> Result<SignatureData, ProgramError> {
+        if *audius_account_info.key != audius_eth_registry::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        assert_instructions_sysvar(sysvar_instruction)?;
+        assert_clock_sysvar(clock_account_info)?;
+
+        let clock = Clock::from_account_info(&clock_account_info)?;
+
+        if (clock.unix_timestamp - instruction_data.track_data.timestamp).abs() > MAX_TIME_DIFF_SECONDS {
+            return Err(TrackListenCountError::InvalidTimestamp.into());
+        }
+
+        Ok(SignatureData {
+            recovery_id: instruction_data.recovery_id,
+            message: instruction_data
+                .track_data
+                .try_to_vec()
+                .or(Err(TrackListenCountError::InvalidTrackData))?,
+        })
+    }
+
     /// Call Audius program to verify signature
     pub fn process_track_listen_instruction(
         _program_id: &Pubkey,
@@ -37,19 +68,13 @@ impl Processor {
         let sysvar_instruction = next_account_info(account_info_iter)?;
         // clock sysvar account
         let clock_account_info = next_account_info(account_info_iter)?;
-        let clock = Clock::from_account_info(&clock_account_info)?;
-
-        if (clock.unix_timestamp - instruction_data.track_data.timestamp).abs() > MAX_TIME_DIFF_SECONDS {
-            return Err(TrackListenCountError::InvalidTimestamp.into());
-        }
 
-        let signature_data = Box::new(SignatureData {
-            recovery_id: instruction_data.recovery_id,
-            message: instruction_data
-                .track_data
-                .try_to_vec()
-                .or(Err(TrackListenCountError::InvalidTrackData))?,
-        });
+        let signature_data = Box::new(Self::listen_signature_data(
+            audius_account_info,
+            sysvar_instruction,
+            clock_account_info,
+            instruction_data,
+        )?);
 
         invoke(
             &audius_eth_registry::instruction::validate_signature_with_sysvar(
@@ -71,6 +96,68 @@ impl Processor {
         Ok(())
     }
 
+    /// Call Audius program to verify signature, rejecting listens counted before
+    pub fn process_track_listen_once_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: InstructionArgs,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        // initialized valid signer account
+        let valid_signer_info = next_account_info(account_info_iter)?;
+        // signer group account
+        let signer_group_info = next_account_info(account_info_iter)?;
+        // audius account
+        let audius_account_info = next_account_info(account_info_iter)?;
+        // sysvar instruction
+        let sysvar_instruction = next_account_info(account_info_iter)?;
+        // clock sysvar account
+        let clock_account_info = next_account_info(account_info_iter)?;
+        // account paying for the used message account
+        let funder_info = next_account_info(account_info_iter)?;
+        // audius used message account
+        let used_message_info = next_account_info(account_info_iter)?;
+        // rent sysvar account
+        let rent_info = next_account_info(account_info_iter)?;
+        // system program account
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        let signature_data = Box::new(Self::listen_signature_data(
+            audius_account_info,
+            sysvar_instruction,
+            clock_account_info,
+            instruction_data,
+        )?);
+
+        // Audius checks the valid signer and the used message address itself
+        let valid_signer = ValidSigner::try_from_slice(&valid_signer_info.data.borrow())?;
+
+        invoke(
+            &audius_eth_registry::instruction::validate_signature_once(
+                &audius_eth_registry::id(),
+                valid_signer_info.key,
+                signer_group_info.key,
+                funder_info.key,
+                &valid_signer.eth_address,
+                *signature_data,
+            )
+            .unwrap(),
+            &[
+                audius_account_info.clone(),
+                valid_signer_info.clone(),
+                signer_group_info.clone(),
+                sysvar_instruction.clone(),
+                funder_info.clone(),
+                used_message_info.clone(),
+                rent_info.clone(),
+                system_program_info.clone(),
+                clock_account_info.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
     /// Processes an instruction
     pub fn process_instruction(
         program_id: &Pubkey,
@@ -84,6 +171,10 @@ impl Processor {
                 msg!("Instruction: TrackListenInstruction");
                 Self::process_track_listen_instruction(program_id, accounts, signature_data)
             }
+            TemplateInstruction::TrackListenOnceInstruction(signature_data) => {
+                msg!("Instruction: TrackListenOnceInstruction");
+                Self::process_track_listen_once_instruction(program_id, accounts, signature_data)
+            }
         }
     }
 }
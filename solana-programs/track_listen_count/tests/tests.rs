@@ -5,13 +5,18 @@ use borsh::BorshSerialize;
 use rand::{thread_rng, Rng};
 use secp256k1::{PublicKey, SecretKey};
 use sha3::Digest;
-use solana_program::{hash::Hash, pubkey::Pubkey, system_instruction};
+use solana_program::{
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    system_instruction,
+};
 use track_listen_count::*;
 use solana_program_test::*;
 use solana_sdk::{
     secp256k1_instruction,
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
     transport::TransportError,
 };
 use chrono::Utc;
@@ -137,8 +142,9 @@ fn construct_eth_address(
     addr
 }
 
-#[tokio::test]
-async fn test_call_track_listen_instruction() {
+/// Returns the secp and `TrackListen` instructions of a signed listen,
+/// followed by a `TrackListenOnce` instruction of the same listen funded by the payer
+async fn prepare_track_listen() -> (BanksClient, Keypair, Hash, Vec<Instruction>, Instruction) {
     let mut rng = thread_rng();
     let key: [u8; 32] = rng.gen();
     let priv_key = SecretKey::parse(&key).unwrap();
@@ -216,20 +222,108 @@ async fn test_call_track_listen_instruction() {
         recovery_id,
     };
 
+    let once_instruction = instruction::init_once(
+        &id(),
+        &valid_signer.pubkey(),
+        &signer_group.pubkey(),
+        &payer.pubkey(),
+        &eth_address,
+        instruction_args.clone(),
+    )
+    .unwrap();
+
+    let instructions = vec![
+        secp256_program_instruction,
+        instruction::init(
+            &id(),
+            &valid_signer.pubkey(),
+            &signer_group.pubkey(),
+            instruction_args,
+        )
+        .unwrap(),
+    ];
+
+    (banks_client, payer, recent_blockhash, instructions, once_instruction)
+}
+
+#[tokio::test]
+async fn test_call_track_listen_instruction() {
+    let (mut banks_client, payer, recent_blockhash, instructions, _) = prepare_track_listen().await;
+
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_track_listen_with_spoofed_accounts() {
+    let (mut banks_client, payer, recent_blockhash, instructions, _) = prepare_track_listen().await;
+
+    for (position, expected_error) in [
+        // audius eth registry program
+        (2, InstructionError::IncorrectProgramId),
+        // sysvar instruction
+        (3, InstructionError::InvalidArgument),
+        // clock sysvar
+        (4, InstructionError::InvalidArgument),
+    ] {
+        let mut spoofed = instructions.clone();
+        spoofed[1].accounts[position].pubkey = Pubkey::new_unique();
+
+        let mut transaction = Transaction::new_with_payer(&spoofed, Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        assert_eq!(
+            banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            TransactionError::InstructionError(1, expected_error)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_track_listen_once() {
+    let (mut banks_client, payer, recent_blockhash, instructions, once_instruction) =
+        prepare_track_listen().await;
+
     let mut transaction = Transaction::new_with_payer(
-        &[
-            secp256_program_instruction,
-            instruction::init(
-                &id(),
-                &valid_signer.pubkey(),
-                &signer_group.pubkey(),
-                instruction_args,
-            )
-            .unwrap(),
-        ],
+        &[instructions[0].clone(), once_instruction.clone()],
         Some(&payer.pubkey()),
     );
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
 
+    // Replaying the listen through another funder is rejected
+    let relayer = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[system_instruction::transfer(&payer.pubkey(), &relayer.pubkey(), 10_000_000)],
+        Some(&payer.pubkey()),
+    );
     transaction.sign(&[&payer], recent_blockhash);
     banks_client.process_transaction(transaction).await.unwrap();
+
+    let mut replay = once_instruction;
+    replay.accounts[5].pubkey = relayer.pubkey();
+    let mut transaction = Transaction::new_with_payer(
+        &[instructions[0].clone(), replay],
+        Some(&relayer.pubkey()),
+    );
+    transaction.sign(&[&relayer], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(
+                audius_eth_registry::error::AudiusError::MessageAlreadyUsed as u32
+            )
+        )
+    );
 }